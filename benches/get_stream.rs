@@ -0,0 +1,90 @@
+//! Measures [`get_stream`] over a large table for different [`MySqlStoreConfig::fetch_size`]s.
+//!
+//! Requires the MySQL server at `DATABASE_URL` or a docker daemon to start a container, see the
+//! `common` module of the tests. Run with `cargo bench --bench get_stream`.
+//!
+//! [`get_stream`]: datastore_mysql::MySqlStore::get_stream
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::time::{Duration, Instant};
+
+use datastore::{Store, StoreData};
+use datastore_mysql::{Filter, MySqlStore, MySqlStoreConfig, PrimaryKey};
+use futures::TryStreamExt;
+
+use common::Server;
+
+/// The number of rows in the streamed table.
+const ROWS: i64 = 100_000;
+/// The number of streams read for every fetch size.
+const ITERATIONS: u32 = 10;
+const FETCH_SIZES: [usize; 5] = [1, 16, 128, 1024, 8192];
+
+#[derive(Debug, StoreData)]
+#[datastore(name = "bench_get_stream")]
+struct Item {
+    id: PrimaryKey<i64>,
+    name: String,
+    value: f64,
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let server = Server::start().await;
+
+    let store = MySqlStore::connect(server.url()).await.unwrap();
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let items = (0..ROWS)
+        .map(|id| Item {
+            id: PrimaryKey(id),
+            name: format!("item {}", id),
+            value: id as f64 / 3.0,
+        })
+        .collect();
+    store.insert_batched(ItemDescriptor, items).await.unwrap();
+
+    println!("{} rows, {} streams per fetch size", ROWS, ITERATIONS);
+
+    for fetch_size in FETCH_SIZES {
+        let config = MySqlStoreConfig {
+            fetch_size,
+            ..Default::default()
+        };
+        let store = MySqlStore::connect_with(server.url(), config)
+            .await
+            .unwrap();
+
+        // The first stream prepares the statement and warms up the caches of the server.
+        read_all(&store).await;
+
+        let mut elapsed = Duration::ZERO;
+        for _ in 0..ITERATIONS {
+            let start = Instant::now();
+            read_all(&store).await;
+            elapsed += start.elapsed();
+        }
+
+        println!(
+            "fetch_size {:>5}: {:>10.2?} per stream, {:>8.0} rows/s",
+            fetch_size,
+            elapsed / ITERATIONS,
+            (ROWS * i64::from(ITERATIONS)) as f64 / elapsed.as_secs_f64(),
+        );
+    }
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Reads all items of the table using `get_stream`.
+async fn read_all(store: &MySqlStore) {
+    let count = store
+        .get_stream::<Item, _, _>(ItemDescriptor, Filter::new())
+        .try_fold(0, |count, _| async move { Ok(count + 1) })
+        .await
+        .unwrap();
+    assert_eq!(count, ROWS);
+}
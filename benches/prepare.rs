@@ -0,0 +1,116 @@
+//! Counts the statements prepared by the server for repeated inserts of different items, and
+//! compares them with inserts whose values are written into the sql as literals.
+//!
+//! The store uses a single connection, the counts are read from the `Com_stmt_prepare` status
+//! variable of its session. Requires the MySQL server at `DATABASE_URL` or a docker daemon to
+//! start a container, see the `common` module of the tests. Run with
+//! `cargo bench --bench prepare`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::time::{Duration, Instant};
+
+use datastore::{Store, StoreData};
+use datastore_mysql::{MySqlStore, MySqlStoreConfig, PrimaryKey};
+
+use common::Server;
+
+/// The number of inserted items per run.
+const INSERTS: i64 = 1_000;
+
+#[derive(Debug, StoreData)]
+#[datastore(name = "bench_prepare")]
+struct Item {
+    id: PrimaryKey<i64>,
+    name: String,
+    value: f64,
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let server = Server::start().await;
+
+    let config = MySqlStoreConfig {
+        max_connections: 1,
+        ..Default::default()
+    };
+    let store = MySqlStore::connect_with(server.url(), config)
+        .await
+        .unwrap();
+    let store = &store;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    println!("{} inserts per run", INSERTS);
+
+    let (prepared, elapsed) = measure(store, |id| async move {
+        store.insert(ItemDescriptor, item(id)).await.unwrap();
+    })
+    .await;
+    report("insert", prepared, elapsed);
+
+    let (prepared, elapsed) = measure(store, |id| async move {
+        let item = item(id + INSERTS);
+        let sql = format!(
+            "INSERT INTO bench_prepare (id,name,value) VALUES ({},'{}',{}E0)",
+            item.id.0, item.name, item.value
+        );
+        store.execute_raw(&sql, &[]).await.unwrap();
+    })
+    .await;
+    report("literal sql", prepared, elapsed);
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+fn item(id: i64) -> Item {
+    Item {
+        id: PrimaryKey(id),
+        name: format!("item {}", id),
+        value: id as f64 / 3.0,
+    }
+}
+
+/// Calls `insert` for the ids `0..INSERTS` and returns the number of statements prepared by the
+/// session of the store and the elapsed time.
+async fn measure<'a, F, Fut>(store: &'a MySqlStore, mut insert: F) -> (u64, Duration)
+where
+    F: FnMut(i64) -> Fut,
+    Fut: std::future::Future<Output = ()> + 'a,
+{
+    // Reading the counter prepares its own statement the first time.
+    prepare_count(store).await;
+    let before = prepare_count(store).await;
+
+    let start = Instant::now();
+    for id in 0..INSERTS {
+        insert(id).await;
+    }
+    let elapsed = start.elapsed();
+
+    (prepare_count(store).await - before, elapsed)
+}
+
+/// Returns the number of statements prepared by the session of the store.
+async fn prepare_count(store: &MySqlStore) -> u64 {
+    store
+        .query_raw_scalar::<String>(
+            "SELECT VARIABLE_VALUE FROM performance_schema.session_status WHERE VARIABLE_NAME = 'Com_stmt_prepare'",
+            &[],
+        )
+        .await
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+fn report(name: &str, prepared: u64, elapsed: Duration) {
+    println!(
+        "{:<11}: {:>5} statements prepared, {:>10.2?} per insert",
+        name,
+        prepared,
+        elapsed / INSERTS as u32,
+    );
+}
@@ -0,0 +1,112 @@
+use sqlx::mysql::MySqlConnectOptions;
+
+use crate::{Error, MySqlStore, MySqlStoreConfig};
+
+/// A builder for the connection of a [`MySqlStore`], returned by [`MySqlStore::builder`].
+///
+/// Unlike a uri, the components of the connection are passed to the driver as is. They don't
+/// need to be percent-encoded, so a password may contain characters like `@`, `:` or `/`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore_mysql::MySqlStore;
+///
+/// let store = MySqlStore::builder()
+///     .host("db.example.com")
+///     .username("user")
+///     .password("p@ss:w/rd")
+///     .database("database")
+///     .connect()
+///     .await?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct MySqlStoreBuilder {
+    host: String,
+    port: u16,
+    username: String,
+    password: Option<String>,
+    database: Option<String>,
+    config: MySqlStoreConfig,
+}
+
+impl MySqlStoreBuilder {
+    /// Creates a new `MySqlStoreBuilder` connecting as `root` to `localhost:3306` without a
+    /// password or database, using the default [`MySqlStoreConfig`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the host name or ip address of the server.
+    pub fn host(mut self, host: &str) -> Self {
+        self.host = host.to_owned();
+        self
+    }
+
+    /// Sets the port of the server.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the name of the user to connect as.
+    pub fn username(mut self, username: &str) -> Self {
+        self.username = username.to_owned();
+        self
+    }
+
+    /// Sets the password of the user.
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_owned());
+        self
+    }
+
+    /// Sets the database to use.
+    pub fn database(mut self, database: &str) -> Self {
+        self.database = Some(database.to_owned());
+        self
+    }
+
+    /// Sets the [`MySqlStoreConfig`] of the store.
+    pub fn config(mut self, config: MySqlStoreConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Connects to the store.
+    pub async fn connect(self) -> Result<MySqlStore, Error> {
+        let options = self.connect_options();
+        MySqlStore::connect_options(options, self.config).await
+    }
+
+    pub(crate) fn connect_options(&self) -> MySqlConnectOptions {
+        let mut options = MySqlConnectOptions::new()
+            .host(&self.host)
+            .port(self.port)
+            .username(&self.username);
+
+        if let Some(password) = &self.password {
+            options = options.password(password);
+        }
+
+        if let Some(database) = &self.database {
+            options = options.database(database);
+        }
+
+        self.config.ssl_options(options)
+    }
+}
+
+impl Default for MySqlStoreBuilder {
+    fn default() -> Self {
+        Self {
+            host: String::from("localhost"),
+            port: 3306,
+            username: String::from("root"),
+            password: None,
+            database: None,
+            config: MySqlStoreConfig::default(),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Operations shared by [`Session`] and [`Transaction`].
+//!
+//! Both run all operations on a single connection, a [`Session`] on a connection of the pool
+//! and a [`Transaction`] on a connection in a transaction. The operations available on both
+//! are implemented once here on the [`MySqlConnection`].
+//!
+//! [`Session`]: crate::Session
+//! [`Transaction`]: crate::Transaction
+
+use crate::mysql::{
+    self, execute, fetch_all, fetch_all_as, fetch_all_with_children, fetch_optional, fetch_scalar,
+    insert_with_children, QueryOptions,
+};
+use crate::{Error, MySqlStore, Query, Value};
+
+use datastore::{DataDescriptor, DataQuery, Read, StoreData};
+use sqlx::mysql::MySqlConnection;
+use sqlx::{Connection, MySql};
+
+/// Deletes all items `T` matching the query `Q` and returns the number of deleted items.
+pub(crate) async fn delete<T, D, Q>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    descriptor: D,
+    query: Q,
+) -> Result<u64, Error>
+where
+    T: StoreData<MySqlStore> + Send + Sync + 'static,
+    D: DataDescriptor<T, MySqlStore> + Send,
+    Q: DataQuery<T, MySqlStore> + Send,
+{
+    let mut query = mysql::delete(&descriptor, &query);
+
+    if !query.has_conditions() {
+        return Err(Error::EmptyDeleteConditions);
+    }
+
+    let res = execute(conn, options, &mut query).await?;
+    Ok(res.rows_affected())
+}
+
+/// Returns all items `T` matching the query `Q`.
+pub(crate) async fn get<T, D, Q>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    order_by_primary_key: bool,
+    descriptor: D,
+    query: Q,
+) -> Result<Vec<T>, Error>
+where
+    T: StoreData<MySqlStore> + Send + Sync + 'static,
+    D: DataDescriptor<T, MySqlStore> + Send,
+    Q: DataQuery<T, MySqlStore> + Send,
+{
+    let mut query = mysql::select(&descriptor, &query);
+    if order_by_primary_key {
+        query.order_by_primary_key(&mysql::primary_key(&descriptor));
+    }
+
+    if !query.children().is_empty() {
+        return fetch_all_with_children(
+            conn,
+            options,
+            &mysql::primary_key(&descriptor),
+            query,
+            None,
+        )
+        .await;
+    }
+
+    fetch_all(conn, options, &mut query).await
+}
+
+/// Returns an item `T` matching the query `Q`.
+pub(crate) async fn get_one<T, D, Q>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    order_by_primary_key: bool,
+    descriptor: D,
+    query: Q,
+) -> Result<Option<T>, Error>
+where
+    T: StoreData<MySqlStore> + Send + Sync + 'static,
+    D: DataDescriptor<T, MySqlStore> + Send,
+    Q: DataQuery<T, MySqlStore> + Send,
+{
+    let mut query = mysql::select_one(&descriptor, &query);
+    if order_by_primary_key {
+        query.order_by_primary_key(&mysql::primary_key(&descriptor));
+    }
+
+    if !query.children().is_empty() {
+        let entries =
+            fetch_all_with_children(conn, options, &mysql::primary_key(&descriptor), query, None)
+                .await?;
+        return Ok(entries.into_iter().next());
+    }
+
+    fetch_optional(conn, options, &mut query).await
+}
+
+/// Inserts a new item `T`. See [`execute_insert`].
+pub(crate) async fn insert<T, D>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    descriptor: D,
+    data: T,
+) -> Result<(), Error>
+where
+    T: StoreData<MySqlStore> + Send + Sync + 'static,
+    D: DataDescriptor<T, MySqlStore> + Send,
+{
+    let mut query = mysql::insert(&descriptor, &data);
+
+    execute_insert(conn, options, &mysql::primary_key(&descriptor), &mut query).await?;
+    Ok(())
+}
+
+/// Executes the `Insert` query and returns the number of affected rows. The child rows of
+/// [`Children`] fields are inserted in a transaction, a savepoint if `conn` is already in a
+/// transaction. `primary_key` contains the primary key columns of the table.
+///
+/// [`Children`]: crate::Children
+pub(crate) async fn execute_insert(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    primary_key: &[String],
+    query: &mut Query<'_>,
+) -> Result<u64, Error> {
+    if query.children().is_empty() {
+        let res = execute(conn, options, query).await?;
+        return Ok(res.rows_affected());
+    }
+
+    let mut tx = conn.begin().await?;
+    let rows = insert_with_children(&mut tx, options, primary_key, query).await?;
+    tx.commit().await?;
+    Ok(rows)
+}
+
+/// Inserts a new item `T` and returns the id generated for the `AUTO_INCREMENT` column.
+pub(crate) async fn insert_returning_id<T, D>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    descriptor: D,
+    data: T,
+) -> Result<u64, Error>
+where
+    T: StoreData<MySqlStore> + Send + Sync + 'static,
+    D: DataDescriptor<T, MySqlStore> + Send,
+{
+    let mut query = mysql::insert(&descriptor, &data);
+    mysql::unsupported_children(&mut query, "Children field into insert_returning_id");
+
+    let res = execute(conn, options, &mut query).await?;
+    Ok(res.last_insert_id())
+}
+
+/// Updates all items `T` matching the query `Q` to `data` and returns the number of matched
+/// items.
+pub(crate) async fn update<T, D, Q>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    descriptor: D,
+    data: T,
+    query: Q,
+) -> Result<u64, Error>
+where
+    T: StoreData<MySqlStore> + Send + Sync + 'static,
+    D: DataDescriptor<T, MySqlStore> + Send,
+    Q: DataQuery<T, MySqlStore> + Send,
+{
+    let mut query = mysql::update(&descriptor, &data, &query);
+
+    let res = execute(conn, options, &mut query).await?;
+    Ok(res.rows_affected())
+}
+
+/// Executes the raw `sql` and reads all returned rows into `T`.
+pub(crate) async fn query_raw<T>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    sql: &str,
+    binds: &[Value],
+) -> Result<Vec<T>, Error>
+where
+    T: StoreData<MySqlStore> + Send + Sync + 'static,
+{
+    let mut query = Query::raw(sql.to_owned(), binds.to_vec());
+
+    fetch_all(conn, options, &mut query).await
+}
+
+/// Executes the raw `sql` and reads the columns of all returned rows into `T` in order.
+pub(crate) async fn query_raw_as<T>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    sql: &str,
+    binds: &[Value],
+) -> Result<Vec<T>, Error>
+where
+    T: Read<MySqlStore> + Send,
+{
+    let mut query = Query::raw(sql.to_owned(), binds.to_vec());
+
+    fetch_all_as(conn, options, &mut query).await
+}
+
+/// Executes the raw `sql` and returns the first column of the first returned row.
+pub(crate) async fn query_raw_scalar<O>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    sql: &str,
+    binds: &[Value],
+) -> Result<O, Error>
+where
+    O: Send + Unpin + for<'r> sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
+{
+    let mut query = Query::raw(sql.to_owned(), binds.to_vec());
+
+    fetch_scalar(conn, options, &mut query).await
+}
+
+/// Executes the raw `sql` and returns the number of affected rows.
+pub(crate) async fn execute_raw(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    sql: &str,
+    binds: &[Value],
+) -> Result<u64, Error> {
+    let mut query = Query::raw(sql.to_owned(), binds.to_vec());
+
+    let res = execute(conn, options, &mut query).await?;
+    Ok(res.rows_affected())
+}
@@ -0,0 +1,137 @@
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
+
+use crate::Value;
+
+/// Formats the identifiers and literals of generated sql.
+///
+/// All identifiers and string literals written by the `Display` impls of queries go through a
+/// `Dialect`, so quoting rules live in a single place.
+pub(crate) trait Dialect {
+    /// Quotes `ident`, e.g. the name of a table or column, as an identifier.
+    fn quote_ident(&self, ident: &str) -> String;
+
+    /// Quotes and escapes `value` as a string literal.
+    fn quote_string_literal(&self, value: &str) -> String;
+
+    /// Formats the `literal` of a value, e.g. `'Robb'` or `1`, written by the writer of the
+    /// store. The literal is written as is by default.
+    fn value(&self, literal: &str) -> String {
+        literal.to_owned()
+    }
+}
+
+/// The sql dialect of MySQL and MariaDB.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct MySqlDialect;
+
+impl Dialect for MySqlDialect {
+    fn quote_ident(&self, ident: &str) -> String {
+        // Plain identifiers are written as is to keep the logged sql readable.
+        if is_plain_ident(ident) {
+            ident.to_owned()
+        } else {
+            format!("`{}`", ident.replace('`', "``"))
+        }
+    }
+
+    fn quote_string_literal(&self, value: &str) -> String {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+    }
+}
+
+/// The MySQL dialect writing a `?` placeholder in place of each value literal that can be bound
+/// as a [`Value`] without changing its meaning, see [`Value::from_literal`]. The values of the
+/// placeholders are collected in the order they are written.
+///
+/// All other literals, e.g. `DECIMAL` values or sql expressions, are written as is.
+#[derive(Debug, Default)]
+pub(crate) struct PlaceholderDialect {
+    binds: RefCell<Vec<Value>>,
+}
+
+impl PlaceholderDialect {
+    /// Returns the values of the written placeholders.
+    pub fn into_binds(self) -> Vec<Value> {
+        self.binds.into_inner()
+    }
+}
+
+impl Dialect for PlaceholderDialect {
+    fn quote_ident(&self, ident: &str) -> String {
+        MySqlDialect.quote_ident(ident)
+    }
+
+    fn quote_string_literal(&self, value: &str) -> String {
+        MySqlDialect.quote_string_literal(value)
+    }
+
+    fn value(&self, literal: &str) -> String {
+        match Value::from_literal(literal) {
+            Some(value) => {
+                self.binds.borrow_mut().push(value);
+                String::from("?")
+            }
+            None => literal.to_owned(),
+        }
+    }
+}
+
+/// Reserved words that are likely used as the name of a column. These must be quoted.
+#[rustfmt::skip]
+const RESERVED_WORDS: &[&str] = &[
+    "add", "all", "alter", "and", "as", "asc", "between", "by", "case", "change", "check", "column",
+    "condition", "constraint", "create", "cross", "database", "default", "delete", "desc",
+    "distinct", "div", "drop", "else", "exists", "from", "function", "group", "groups", "having",
+    "in", "index", "inner", "insert", "interval", "into", "is", "join", "key", "keys", "lag",
+    "lead", "left", "like", "limit", "lock", "match", "mod", "natural", "not", "null", "on",
+    "option", "or", "order", "outer", "primary", "range", "rank", "read", "references", "rename",
+    "replace", "right", "row", "rows", "schema", "select", "set", "show", "table", "then", "to",
+    "trigger", "union", "unique", "update", "usage", "use", "using", "values", "when", "where",
+    "window", "with", "write",
+];
+
+/// Returns `true` if `ident` can be written without quotes: It only contains ascii
+/// alphanumeric characters, `_` and `$`, isn't a number and isn't a reserved word.
+pub(crate) fn is_plain_ident(ident: &str) -> bool {
+    !ident.is_empty()
+        && ident
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && !ident.chars().all(|c| c.is_ascii_digit())
+        && !RESERVED_WORDS.contains(&ident.to_ascii_lowercase().as_str())
+}
+
+/// A value formatted as sql using a [`Dialect`].
+pub(crate) trait FmtWith {
+    fn fmt_with<D>(&self, dialect: &D, f: &mut Formatter) -> fmt::Result
+    where
+        D: Dialect;
+}
+
+/// Displays `T` using the dialect `D`.
+pub(crate) struct WithDialect<'a, T, D>(pub &'a T, pub &'a D)
+where
+    T: ?Sized;
+
+impl<'a, T, D> Display for WithDialect<'a, T, D>
+where
+    T: ?Sized + FmtWith,
+    D: Dialect,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt_with(self.1, f)
+    }
+}
+
+/// Quotes all `idents` and joins them with `,`.
+pub(crate) fn join_idents<D>(dialect: &D, idents: &[String]) -> String
+where
+    D: Dialect,
+{
+    idents
+        .iter()
+        .map(|ident| dialect.quote_ident(ident))
+        .collect::<Vec<String>>()
+        .join(",")
+}
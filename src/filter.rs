@@ -0,0 +1,380 @@
+use crate::mysql::format_value;
+use crate::{hints, Comparator, Condition, Conditions, MySqlStore};
+
+use std::fmt::{self, Display, Formatter};
+
+use datastore::{DataQuery, StoreData, Write, Writer};
+
+/// A [`DataQuery`] built from conditions on individual columns.
+///
+/// `Filter` can express conditions that the query types generated by `datastore` can't, like
+/// matching a column against a list of values. A `Filter` can be used for every [`StoreData`]
+/// type of a [`MySqlStore`]. All conditions must match for an item to match the `Filter`.
+///
+/// Conditions may reference any column of the table, not only the columns of the fields of the
+/// type. This allows reading a projection of the table, e.g. only the ids of the items matching
+/// a name.
+///
+/// Values are written like the values of inserted items, strings as quoted literals and bytes as
+/// hex literals. Ordering comparisons like [`gt`] compare strings using the collation of the
+/// column and bytes byte by byte.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::{Store, StoreExt};
+/// use datastore_mysql::Filter;
+///
+/// let filter = Filter::new().is_in("id", &[1, 2, 3]);
+/// let persons: Vec<Person> = store.get(store.descriptor::<Person>(), filter).await?;
+/// ```
+///
+/// [`gt`]: Self::gt
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    conditions: Conditions,
+}
+
+impl Filter {
+    /// Creates a new `Filter` without any conditions. An empty `Filter` matches all items.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches items where `column` is equal to `value`. If `value` is `None` items where
+    /// `column` is `NULL` are matched.
+    pub fn eq<V>(mut self, column: &str, value: &V) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        let value = format_value(value);
+
+        // `= NULL` never matches. Only `NULL` values are written as an unquoted `NULL`.
+        if value == "NULL" {
+            return self.is_null(column);
+        }
+
+        self.conditions
+            .push(Condition::new(column.to_owned(), value, Comparator::Eq));
+        self
+    }
+
+    /// Matches items where `column` is equal to `value`, comparing the values using
+    /// `collation` instead of the collation of the column.
+    ///
+    /// This allows case-sensitive (e.g. `utf8mb4_bin`) or case-insensitive (e.g.
+    /// `utf8mb4_general_ci`) matches independent of the column. The collation must belong to
+    /// the character set of the column.
+    pub fn eq_collate<V>(mut self, column: &str, value: &V, collation: &str) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.conditions.push(
+            Condition::new(column.to_owned(), format_value(value), Comparator::Eq)
+                .collate(collation.to_owned()),
+        );
+        self
+    }
+
+    /// Matches items where `column` is greater than `value`.
+    pub fn gt<V>(self, column: &str, value: &V) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.compare(column, value, Comparator::Gt)
+    }
+
+    /// Matches items where `column` is greater than or equal to `value`.
+    pub fn ge<V>(self, column: &str, value: &V) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.compare(column, value, Comparator::Ge)
+    }
+
+    /// Matches items where `column` is less than `value`.
+    pub fn lt<V>(self, column: &str, value: &V) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.compare(column, value, Comparator::Lt)
+    }
+
+    /// Matches items where `column` is less than or equal to `value`.
+    pub fn le<V>(self, column: &str, value: &V) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.compare(column, value, Comparator::Le)
+    }
+
+    /// Matches items where `column` compares to the column `other` as given by `comparison`,
+    /// e.g. `WHERE updated_at > created_at`. Both columns are written as identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{Comparison, Filter};
+    ///
+    /// // WHERE updated_at > created_at
+    /// let filter = Filter::new().column_cmp_column("updated_at", Comparison::Gt, "created_at");
+    /// ```
+    pub fn column_cmp_column(mut self, column: &str, comparison: Comparison, other: &str) -> Self {
+        self.conditions.push(Condition::columns(
+            column.to_owned(),
+            other.to_owned(),
+            comparison.into(),
+        ));
+        self
+    }
+
+    /// Matches items where the value at the JSON `path` within the `JSON` `column` compares to
+    /// `value` as given by `comparison`, written as `column->>'path' = value`.
+    ///
+    /// The `->>` operator unquotes the value at `path`, so it compares like a string column:
+    /// Strings match without their JSON quotes and numbers are converted when compared to a
+    /// number. Rows where `path` doesn't exist have a `NULL` value and never match. The `path`
+    /// is written as an escaped string literal, MySQL rejects paths that are not valid JSON
+    /// paths, e.g. paths not starting with `$`. MariaDB doesn't support the `->>` operator.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{Comparison, Filter};
+    ///
+    /// // WHERE data->>'$.status' = 'active'
+    /// let filter = Filter::new().json_path("data", "$.status", Comparison::Eq, "active");
+    /// ```
+    pub fn json_path<V>(
+        mut self,
+        column: &str,
+        path: &str,
+        comparison: Comparison,
+        value: &V,
+    ) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.conditions.push(
+            Condition::new(column.to_owned(), format_value(value), comparison.into())
+                .json_path(path.to_owned()),
+        );
+        self
+    }
+
+    /// Matches items where `column` is `NULL`.
+    pub fn is_null(mut self, column: &str) -> Self {
+        self.conditions.push(Condition::is_null(column.to_owned()));
+        self
+    }
+
+    /// Matches items where `column` is not `NULL`.
+    pub fn is_not_null(mut self, column: &str) -> Self {
+        self.conditions
+            .push(Condition::is_not_null(column.to_owned()));
+        self
+    }
+
+    /// Matches items where `column` is between `low` and `high`, inclusive.
+    pub fn between<V>(mut self, column: &str, low: &V, high: &V) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.conditions.push(Condition::between(
+            column.to_owned(),
+            format_value(low),
+            format_value(high),
+        ));
+        self
+    }
+
+    /// Matches items where the `FLOAT` or `DOUBLE` `column` differs from `value` by at most
+    /// `epsilon`. This is written as `column BETWEEN value - epsilon AND value + epsilon`.
+    ///
+    /// Most decimal values have no exact float representation. Comparing a float column for
+    /// equality, e.g. a `FLOAT` column storing `19.99` with `eq("price", &19.99)`, often matches
+    /// nothing. Use `approx_eq` for these columns, or store exact values in a [`FixedDecimal`]
+    /// column instead.
+    ///
+    /// [`FixedDecimal`]: crate::FixedDecimal
+    pub fn approx_eq(self, column: &str, value: f64, epsilon: f64) -> Self {
+        self.between(column, &(value - epsilon), &(value + epsilon))
+    }
+
+    /// Matches items where `column` matches the `LIKE` `pattern`. `%` matches any number of
+    /// characters and `_` matches a single character. Use `\%` and `\_` to match the wildcard
+    /// characters literally.
+    pub fn like(mut self, column: &str, pattern: &str) -> Self {
+        self.conditions.push(Condition::new(
+            column.to_owned(),
+            format_value(pattern),
+            Comparator::Like,
+        ));
+        self
+    }
+
+    /// Matches items where the `columns` contain the search `terms`, written as
+    /// `MATCH (columns) AGAINST ('terms' mode)`.
+    ///
+    /// MySQL requires a `FULLTEXT` index covering exactly the `columns`, otherwise the query
+    /// fails. The `terms` are written as an escaped string literal, but operators like `+` and
+    /// `-` are still interpreted in [`MatchMode::Boolean`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{Filter, MatchMode};
+    ///
+    /// // WHERE MATCH (title,body) AGAINST ('+rust -java' IN BOOLEAN MODE)
+    /// let filter = Filter::new().match_against(&["title", "body"], "+rust -java", MatchMode::Boolean);
+    /// ```
+    pub fn match_against(mut self, columns: &[&str], terms: &str, mode: MatchMode) -> Self {
+        self.conditions.push_match(
+            columns.iter().map(|column| column.to_string()).collect(),
+            format_value(terms),
+            mode,
+        );
+        self
+    }
+
+    /// Matches items where `column` is equal to any of the `values`. If `values` is empty no
+    /// items match.
+    pub fn is_in<V>(mut self, column: &str, values: &[V]) -> Self
+    where
+        V: Write<MySqlStore>,
+    {
+        self.conditions.push(Condition::in_list(
+            column.to_owned(),
+            values.iter().map(format_value).collect(),
+        ));
+        self
+    }
+
+    /// Matches items where `column` is not equal to any of the `values`. If `values` is empty
+    /// all items match.
+    ///
+    /// Like in sql, items where `column` is `NULL` never match.
+    pub fn not_in<V>(mut self, column: &str, values: &[V]) -> Self
+    where
+        V: Write<MySqlStore>,
+    {
+        self.conditions.push(Condition::not_in_list(
+            column.to_owned(),
+            values.iter().map(format_value).collect(),
+        ));
+        self
+    }
+
+    /// Matches items that match either all conditions of this `Filter` or all conditions of
+    /// `other`.
+    ///
+    /// Conditions added after calling `or` must match in addition to the combined conditions.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::Filter;
+    ///
+    /// // WHERE ((id = 1 AND name = 'Robb') OR (id = 2)) AND age = 30
+    /// let filter = Filter::new()
+    ///     .eq("id", &1)
+    ///     .eq("name", "Robb")
+    ///     .or(Filter::new().eq("id", &2))
+    ///     .eq("age", &30);
+    /// ```
+    pub fn or(mut self, other: Filter) -> Self {
+        let conditions = std::mem::take(&mut self.conditions);
+        self.conditions.push_any(vec![conditions, other.conditions]);
+        self
+    }
+
+    /// Returns the conditions of the `Filter`.
+    pub(crate) fn conditions(&self) -> &Conditions {
+        &self.conditions
+    }
+
+    fn compare<V>(mut self, column: &str, value: &V, comparator: Comparator) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.conditions.push(Condition::new(
+            column.to_owned(),
+            format_value(value),
+            comparator,
+        ));
+        self
+    }
+}
+
+/// The comparison of two columns used by [`Filter::column_cmp_column`], or of a JSON value
+/// used by [`Filter::json_path`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// The values are equal (`=`).
+    Eq,
+    /// The values are not equal (`<>`).
+    Ne,
+    /// The first value is greater than the second (`>`).
+    Gt,
+    /// The first value is greater than or equal to the second (`>=`).
+    Ge,
+    /// The first value is less than the second (`<`).
+    Lt,
+    /// The first value is less than or equal to the second (`<=`).
+    Le,
+}
+
+impl From<Comparison> for Comparator {
+    fn from(comparison: Comparison) -> Self {
+        match comparison {
+            Comparison::Eq => Self::Eq,
+            Comparison::Ne => Self::Ne,
+            Comparison::Gt => Self::Gt,
+            Comparison::Ge => Self::Ge,
+            Comparison::Lt => Self::Lt,
+            Comparison::Le => Self::Le,
+        }
+    }
+}
+
+/// The search modifier of a full-text search using [`Filter::match_against`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MatchMode {
+    /// Searches for the terms as natural language (`IN NATURAL LANGUAGE MODE`). This is the
+    /// default of MySQL.
+    #[default]
+    NaturalLanguage,
+    /// Searches using the boolean operators in the terms, e.g. `+` for required and `-` for
+    /// excluded words (`IN BOOLEAN MODE`).
+    Boolean,
+    /// Searches for the terms as natural language, repeating the search with the words of the
+    /// most relevant rows (`WITH QUERY EXPANSION`).
+    QueryExpansion,
+}
+
+impl Display for MatchMode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NaturalLanguage => write!(f, "IN NATURAL LANGUAGE MODE"),
+            Self::Boolean => write!(f, "IN BOOLEAN MODE"),
+            Self::QueryExpansion => write!(f, "WITH QUERY EXPANSION"),
+        }
+    }
+}
+
+impl<T> DataQuery<T, MySqlStore> for Filter
+where
+    T: StoreData<MySqlStore>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        // The value is ignored by the MySQL writer. It only carries the conditions hint.
+        hints::with(
+            |hints| hints.conditions = Some(self.conditions.clone()),
+            || writer.write_bool(true),
+        )
+    }
+}
@@ -0,0 +1,361 @@
+//! Out-of-band hints for the MySQL writers and reader.
+//!
+//! The [`Writer`], [`TypeWriter`] and [`Reader`] traits are defined by `datastore` and only
+//! provide hooks for a fixed set of primitive types. Types that need MySQL specific handling
+//! (e.g. `NULL` values) set a hint and then call the closest primitive hook. The MySQL writers
+//! and reader take the hints when the hook is called. Other writers just see the primitive call.
+//!
+//! Hints are stored thread-locally and only live for the duration of a single [`with`] call.
+//!
+//! [`Writer`]: datastore::Writer
+//! [`TypeWriter`]: datastore::TypeWriter
+//! [`Reader`]: datastore::Reader
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use datastore::{Read, Reader, TypeWriter, Write};
+use sqlx::mysql::MySqlRow;
+use sqlx::{Decode, MySql, Row, Type};
+
+use crate::{Conditions, MySqlStore};
+
+/// Decodes a column of a row into a boxed value.
+pub(crate) type Decoder = fn(&MySqlRow, &str) -> Result<Box<dyn Any>, sqlx::Error>;
+
+#[derive(Debug, Default)]
+pub(crate) struct Hints {
+    /// Write a `NULL` value instead of the written value.
+    pub null: bool,
+    /// Return whether the current column is `NULL` instead of reading the column.
+    pub is_null: bool,
+    /// Return whether the current column is missing from the row instead of reading the column.
+    pub is_missing: bool,
+    /// The written column type is nullable.
+    pub nullable: bool,
+    /// The written column is part of the primary key.
+    pub primary_key: bool,
+    /// The written column is an `AUTO_INCREMENT` column.
+    pub auto_increment: bool,
+    /// Write this literal instead of the written value.
+    pub literal: Option<String>,
+    /// Write the column type instead of the type of the primitive.
+    pub column_type: Option<String>,
+    /// Don't write the value, the column keeps its default value.
+    pub skip: bool,
+    /// The maximum length in bytes of the written value. Defaults to the length of a `BLOB`.
+    pub max_len: Option<usize>,
+    /// The written column only holds values in the range `min..=max`. Types get a `CHECK`
+    /// constraint, values are only written with this hint if they are outside of the range.
+    pub range: Option<(i64, i64)>,
+    /// The written column has this `DEFAULT` clause.
+    pub column_default: Option<&'static str>,
+    /// The written column uses this collation.
+    pub collation: Option<&'static str>,
+    /// Write these conditions instead of the written value.
+    pub conditions: Option<Conditions>,
+    /// Decode the current column using the decoder instead of reading the column. The decoded
+    /// value, whose type has the given name, is returned by [`read_with`].
+    pub decode: Option<(Decoder, &'static str)>,
+    /// The written column is stored in a child table. Contains the written values of the child
+    /// rows, or the column type of the child column when writing types.
+    pub child: Option<Vec<String>>,
+}
+
+thread_local! {
+    static HINTS: RefCell<Hints> = RefCell::new(Hints::default());
+    static DECODED: RefCell<Option<Box<dyn Any>>> = RefCell::new(None);
+    /// The prefix of all columns, set while writing or reading a flattened struct.
+    static PREFIX: RefCell<String> = const { RefCell::new(String::new()) };
+    /// The column of the field that is currently written or read.
+    static COLUMN: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Calls `f` with the hints updated by `hint`. All hints that were not taken by `f` are
+/// discarded afterwards.
+pub(crate) fn with<H, F, R>(hint: H, f: F) -> R
+where
+    H: FnOnce(&mut Hints),
+    F: FnOnce() -> R,
+{
+    HINTS.with(|hints| hint(&mut hints.borrow_mut()));
+    let res = f();
+    take();
+    res
+}
+
+/// Sets the current column to the column of the field `key`, prefixed with the names of all
+/// flattened parent fields. The column stays the current column until the next call.
+pub(crate) fn set_column(key: &str) {
+    let column = PREFIX.with(|prefix| format!("{}{}", prefix.borrow(), key));
+    COLUMN.with(|current| *current.borrow_mut() = column);
+}
+
+/// Calls `f` with the column of the field `key` as the current column, restoring the current
+/// column afterwards. Values written or read in `f` after writing or reading other fields still
+/// use the column of the parent field.
+pub(crate) fn with_column<F, R>(key: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let column = PREFIX.with(|prefix| format!("{}{}", prefix.borrow(), key));
+    let parent = COLUMN.with(|current| current.replace(column));
+    let res = f();
+    COLUMN.with(|current| *current.borrow_mut() = parent);
+    res
+}
+
+/// Unsets the current column. Values read without a current column are read by position.
+pub(crate) fn clear_column() {
+    COLUMN.with(|current| current.borrow_mut().clear());
+}
+
+/// Returns the column of the field that is currently written or read.
+pub(crate) fn column() -> String {
+    COLUMN.with(|current| current.borrow().clone())
+}
+
+/// Sets the current column to the column a `T` is written to, e.g. the column of a
+/// [`Renamed`] `T`. This allows wrappers to write the column without a `T`, like the `NULL` of
+/// an `Option<T>`. The hints are kept for the written value.
+///
+/// [`Renamed`]: crate::Renamed
+pub(crate) fn set_column_of_write<T>()
+where
+    T: Write<MySqlStore>,
+{
+    let hints = take();
+    let _ = T::write_type(&mut ColumnProbe);
+    HINTS.with(|current| *current.borrow_mut() = hints);
+}
+
+/// Sets the current column to the column a `T` is read from, like [`set_column_of_write`].
+/// This allows wrappers to check the column before reading a `T`, like whether the column of an
+/// `Option<T>` is `NULL`.
+pub(crate) fn set_column_of_read<T>()
+where
+    T: Read<MySqlStore>,
+{
+    let hints = take();
+    let _ = T::read(&mut ColumnProbe);
+    HINTS.with(|current| *current.borrow_mut() = hints);
+}
+
+/// A writer and reader that only lets types set the current column. Writing a type does
+/// nothing and reading a value always fails.
+struct ColumnProbe;
+
+macro_rules! probe_write_type {
+    ($($name:ident),*) => {
+        $(
+            fn $name(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl TypeWriter<MySqlStore> for ColumnProbe {
+    type Error = Infallible;
+
+    probe_write_type!(
+        write_bool,
+        write_i8,
+        write_i16,
+        write_i32,
+        write_i64,
+        write_u8,
+        write_u16,
+        write_u32,
+        write_u64,
+        write_f32,
+        write_f64,
+        write_bytes,
+        write_str
+    );
+
+    fn write_field<T>(&mut self, _key: &'static str) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Write<MySqlStore>,
+    {
+        Ok(())
+    }
+}
+
+macro_rules! probe_read {
+    ($($name:ident: $t:ty),*) => {
+        $(
+            fn $name(&mut self) -> Result<$t, Self::Error> {
+                Err(())
+            }
+        )*
+    };
+}
+
+impl Reader<MySqlStore> for ColumnProbe {
+    type Error = ();
+
+    probe_read!(
+        read_bool: bool,
+        read_i8: i8,
+        read_i16: i16,
+        read_i32: i32,
+        read_i64: i64,
+        read_u8: u8,
+        read_u16: u16,
+        read_u32: u32,
+        read_u64: u64,
+        read_f32: f32,
+        read_f64: f64,
+        read_byte_buf: Vec<u8>,
+        read_string: String
+    );
+
+    fn read_field<T>(&mut self, _key: &'static str) -> Result<T, Self::Error>
+    where
+        T: Sized + Read<MySqlStore>,
+    {
+        Err(())
+    }
+}
+
+/// Calls `f` with the current column, followed by `_`, added to the prefix of all columns.
+/// This flattens the fields written or read in `f` into the parent.
+pub(crate) fn flatten<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let prefix = COLUMN.with(|column| format!("{}_", column.borrow()));
+    let parent = PREFIX.with(|current| current.replace(prefix));
+    let res = f();
+    PREFIX.with(|current| *current.borrow_mut() = parent);
+    res
+}
+
+/// Calls `f` without a prefix and current column, restoring both afterwards. This allows
+/// writing or reading values that are stored outside of the current table, like the rows of a
+/// child table.
+pub(crate) fn nested<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let prefix = PREFIX.with(|current| current.take());
+    let column = COLUMN.with(|current| current.take());
+    let res = f();
+    PREFIX.with(|current| *current.borrow_mut() = prefix);
+    COLUMN.with(|current| *current.borrow_mut() = column);
+    res
+}
+
+/// Takes all currently set hints.
+pub(crate) fn take() -> Hints {
+    HINTS.with(|hints| std::mem::take(&mut *hints.borrow_mut()))
+}
+
+/// Takes only the [`Hints::max_len`] hint, leaving the other hints for the written value.
+pub(crate) fn take_max_len() -> Option<usize> {
+    HINTS.with(|hints| hints.borrow_mut().max_len.take())
+}
+
+/// Stores a value decoded by a [`Decoder`] for [`read_with`].
+pub(crate) fn set_decoded(value: Box<dyn Any>) {
+    DECODED.with(|decoded| *decoded.borrow_mut() = Some(value));
+}
+
+/// Reads a `T` from `reader` using the `decoder`. This allows reading types that have no hook
+/// on the [`Reader`] trait. `decoder` must return a boxed `T`.
+///
+/// # Panics
+///
+/// Panics if `reader` is not the MySQL reader.
+pub(crate) fn read_with<T, R>(reader: &mut R, decoder: Decoder) -> Result<T, R::Error>
+where
+    T: 'static,
+    R: Reader<MySqlStore>,
+{
+    with(
+        |hints| hints.decode = Some((decoder, std::any::type_name::<T>())),
+        || reader.read_byte_buf(),
+    )?;
+
+    let value = DECODED
+        .with(|decoded| decoded.borrow_mut().take())
+        .expect("reader does not support decoding MySQL values");
+
+    match value.downcast() {
+        Ok(value) => Ok(*value),
+        Err(_) => unreachable!(),
+    }
+}
+
+/// Reads a `T` from `reader` using the sqlx [`Decode`] implementation of `T`.
+///
+/// # Panics
+///
+/// Panics if `reader` is not the MySQL reader.
+#[cfg_attr(
+    not(any(
+        feature = "chrono",
+        feature = "decimal",
+        feature = "time",
+        feature = "uuid"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn read_decoded<T, R>(reader: &mut R) -> Result<T, R::Error>
+where
+    T: for<'r> Decode<'r, MySql> + Type<MySql> + 'static,
+    R: Reader<MySqlStore>,
+{
+    read_with(reader, decode::<T>)
+}
+
+/// Reads a `T` from `reader` by parsing the textual representation of the column.
+///
+/// # Panics
+///
+/// Panics if `reader` is not the MySQL reader.
+pub(crate) fn read_parsed<T, R>(reader: &mut R) -> Result<T, R::Error>
+where
+    T: FromStr + 'static,
+    T::Err: std::error::Error + Send + Sync + 'static,
+    R: Reader<MySqlStore>,
+{
+    read_with(reader, parse::<T>)
+}
+
+#[cfg_attr(
+    not(any(
+        feature = "chrono",
+        feature = "decimal",
+        feature = "time",
+        feature = "uuid"
+    )),
+    allow(dead_code)
+)]
+fn decode<T>(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error>
+where
+    T: for<'r> Decode<'r, MySql> + Type<MySql> + 'static,
+{
+    let value: T = row.try_get(column)?;
+    Ok(Box::new(value))
+}
+
+fn parse<T>(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error>
+where
+    T: FromStr + 'static,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    // Numeric columns like DECIMAL are transferred as strings, skip the type check of `&str`.
+    let value: &str = row.try_get_unchecked(column)?;
+
+    match value.parse::<T>() {
+        Ok(value) => Ok(Box::new(value)),
+        Err(err) => Err(sqlx::Error::ColumnDecode {
+            index: column.to_owned(),
+            source: Box::new(err),
+        }),
+    }
+}
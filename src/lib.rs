@@ -5,11 +5,89 @@
 //!
 //! [`MySqlStore`] supports these types:
 //! - `bool`
-//! - `i8`, `i16`, `i32`, `i64`
-//! - `u8`, `u16`, `u32`, `u64`
-//! - `f32`, `f64`
-//! - `&str`, `String`
-//! - `&[u8]`, `Vec<u8>`
+//! - `i8`, `i16`, `i32`, `i64`, `i128`
+//! - `u8`, `u16`, `u32`, `u64`, `u128`
+//! - `f32`, stored as `FLOAT`, `f64`, stored as `DOUBLE` and [`FixedDecimal<M, D>`], stored as
+//!   `DECIMAL(M,D)`. `NaN` and infinite values are written as `NULL`. `f64` values round-trip
+//!   exactly, MySQL may return `f32` values with fewer significant digits than written.
+//! - `char`, stored as `CHAR(1)`
+//! - `&str`, `String`, `Cow<str>`, `Box<str>`, `Rc<str>`, `Arc<str>`, [`Varchar<N>`],
+//!   [`MediumText`], [`LongText`]
+//! - `&[u8]`, `Vec<u8>`, `Cow<[u8]>`, `Box<[u8]>`, `Rc<[u8]>`, `Arc<[u8]>`, [`MediumBytes`],
+//!   [`LongBytes`]
+//! - `bytes::Bytes`, stored as `BLOB` (requires the `bytes` feature)
+//! - `chrono::NaiveDateTime`, `chrono::DateTime<Utc>`, stored as `DATETIME(6)` (requires the
+//!   `chrono` feature)
+//! - `time::OffsetDateTime`, stored as `DATETIME(6)` in UTC, `time::Date`, stored as `DATE`
+//!   and `time::Time`, stored as `TIME(6)` (requires the `time` feature)
+//! - `rust_decimal::Decimal`, stored as `DECIMAL(65,30)` (requires the `decimal` feature)
+//! - `Json<T>` where `T` implements `Serialize` and `Deserialize`, stored as `JSON` (requires the
+//!   `json` feature)
+//! - `serde_json::Value`, stored as `JSON` (requires the `json` feature)
+//! - `HashMap<K, V>` where `K` and `V` implement `Serialize` and `Deserialize`, stored as a JSON
+//!   object in a `JSON` column (requires the `json` feature)
+//! - `uuid::Uuid`, stored as `BINARY(16)` (requires the `uuid` feature)
+//! - `Ipv4Addr`, stored as `INT UNSIGNED`, `Ipv6Addr`, stored as `BINARY(16)` and `IpAddr`,
+//!   stored as `VARBINARY(16)`
+//! - `Duration`, stored as the number of nanoseconds in a `BIGINT UNSIGNED`
+//! - [`Bits<N>`], stored as `BIT(N)`
+//! - [`Year`], stored as `YEAR`
+//! - [`Point`], stored as `POINT`
+//! - [`Enum<T>`] where `T` implements [`SqlEnum`], stored as `ENUM`
+//! - [`Set<T>`] where `T` implements [`SqlSet`], stored as `SET`
+//! - `Option<T>` where `T` is any of the above, stored as a nullable column. Borrowed values
+//!   like `Option<&str>` and `Option<&[u8]>` are written without copying.
+//! - [`PrimaryKey<T>`] where `T` is any of the above, stored as part of the primary key
+//! - [`Bounded<T, MIN, MAX>`] where `T` is an integer type, stored with a `CHECK` constraint
+//!   limiting the column to `MIN..=MAX`
+//! - [`AutoIncrement<T>`] where `T` is an integer type, filled in by the database using
+//!   `AUTO_INCREMENT` when an item is inserted
+//! - [`OrDefault<T>`] where `T` is any of the above, read as `T::default()` if the column is
+//!   missing from the row
+//! - [`AsTimestamp<T>`] where `T` implements [`Timestamp`], stored as `TIMESTAMP(6)` instead of
+//!   `DATETIME(6)`
+//! - [`CreatedAt<T>`] and [`UpdatedAt<T>`] where `T` implements [`Timestamp`], filled in by the
+//!   database when an item is inserted or updated
+//! - [`Defaulted<T>`] where `T` is any of the above, filled in by the database with the
+//!   `DEFAULT` of the column if it isn't written
+//! - [`Nullable<T>`] where `T` is any of the above, stored as a nullable column but read and
+//!   written as `T`
+//! - [`Collated<T, C>`] where `T` is any of the string types, stored with the collation `C`
+//! - [`Renamed<T, N>`] where `T` is any of the above, stored in the column [`ColumnName`] `N`
+//! - [`Flatten<T>`] where `T` implements [`StoreData`], stored in one column per field of `T`
+//! - [`Children<T>`] where `T` is any of the above, stored in a child table referencing the
+//!   primary key
+//!
+//! All columns except `Option<T>` and [`Nullable<T>`] columns are `NOT NULL`. Use
+//! [`assert_storable!`] to check that the types of all fields are supported.
+//!
+//! Other types can be stored by implementing [`Write`] using [`RawWriter`] and
+//! [`RawTypeWriter`], which write arbitrary sql as the value and column type.
+//!
+//! ## Prepared statements
+//!
+//! Every query is executed as a prepared statement using the binary protocol. The values of
+//! generated queries are sent as bound parameters, the sql contains a `?` placeholder for each
+//! value. The sql of a query only depends on its table, operation and columns, e.g. all inserts
+//! of an item share the statement. sqlx caches the prepared statements per connection, keyed
+//! by the sql, so repeated queries are only prepared once per connection. Raw queries bind their
+//! [`Value`]s, see [`MySqlStore::query_raw`].
+//!
+//! `DECIMAL` values, integers outside of the 64 bit range and sql written by [`RawWriter`] are
+//! written into the sql as literals, as are the operands of [`UpdateExpr`]s other than `Set`.
+//! The logged sql and the sql of errors contain the values as escaped literals.
+//!
+//! Statements that can't be prepared, like `SAVEPOINT` or the statements of
+//! [`Transaction::run_migration`], are sent using the text protocol instead.
+//!
+//! ## Logging
+//!
+//! All executed queries are logged using the [`log`] crate. With the `tracing` feature enabled
+//! events are emitted using [`tracing`] instead. Each query runs in a span named after the
+//! operation (e.g. `select`) with the table as a field.
+//!
+//! [`log`]: https://docs.rs/log
+//! [`tracing`]: https://docs.rs/tracing
 //!
 //! ## Examples
 //!
@@ -42,65 +120,494 @@
 //! ```
 //!
 //! [`Store`]: datastore::Store
+//! [`StoreData`]: datastore::StoreData
+//! [`Write`]: datastore::Write
 
 use std::fmt::{self, Display, Formatter};
 
+use dialect::{Dialect, FmtWith, MySqlDialect, PlaceholderDialect, WithDialect};
+
+mod builder;
+mod config;
+mod connection;
+mod dialect;
+mod filter;
+mod hints;
 mod mysql;
+pub mod query;
+mod session;
+mod table;
+mod transaction;
 mod types;
+mod value;
 
-pub use mysql::MySqlStore;
+pub use builder::MySqlStoreBuilder;
+pub use config::{AfterConnect, MySqlStoreConfig, SslMode};
+pub use filter::{Comparison, Filter, MatchMode};
+pub use mysql::{MySqlStore, PoolStatus};
+pub use session::{LockMode, Session};
+pub use table::WithTable;
+pub use transaction::Transaction;
+#[cfg(feature = "json")]
+pub use types::Json;
+pub use types::{
+    AsTimestamp, AutoIncrement, Bits, Bounded, CaseInsensitive, CaseSensitive, Children, Collated,
+    Collation, ColumnName, CreatedAt, Defaulted, Enum, FixedDecimal, Flatten, LongBytes, LongText,
+    MediumBytes, MediumText, Nullable, OrDefault, Point, PrimaryKey, RawTypeWriter, RawWriter,
+    Renamed, Set, SqlEnum, SqlSet, Storable, Timestamp, UpdatedAt, Varchar, Year,
+};
+pub use value::{UpdateExpr, Value};
 
+/// The error type returned by [`MySqlStore`].
 #[derive(Debug)]
-pub struct Error(ErrorKind);
+#[non_exhaustive]
+pub enum Error {
+    /// An error returned by the database or the underlying sqlx driver.
+    Sqlx(sqlx::Error),
+    /// A custom error, e.g. returned by a [`Read`] or [`Write`] implementation.
+    ///
+    /// [`Read`]: datastore::Read
+    /// [`Write`]: datastore::Write
+    Custom(String),
+    /// A raw query returns multiple columns with the same name, e.g. an alias that collides with
+    /// a selected column. Fields are read by name, so the column to read is ambiguous.
+    AmbiguousColumn {
+        /// The name of the column.
+        name: String,
+    },
+    /// The rows of a multi-row insert write different columns.
+    ColumnMismatch,
+    /// A query writes a different number of columns and values, e.g. because a [`Write`]
+    /// implementation writes a value without a field.
+    ///
+    /// [`Write`]: datastore::Write
+    ColumnValueMismatch {
+        /// The number of columns.
+        columns: usize,
+        /// The number of values.
+        values: usize,
+    },
+    /// A column is written more than once by a type, e.g. because a flattened type contains a
+    /// field with the same name as a field of the outer type.
+    DuplicateColumn {
+        /// The name of the column.
+        name: String,
+    },
+    /// A column of a returned row can't be decoded as the type of its field, e.g. because a
+    /// `TEXT` column is read as an integer.
+    Decode {
+        /// The name of the column.
+        column: String,
+        /// The name of the Rust type the column is decoded as.
+        rust_type: &'static str,
+        /// The error returned by the underlying sqlx driver.
+        source: sqlx::Error,
+    },
+    /// A delete query has no conditions or only conditions that match every item, e.g.
+    /// `NOT IN` with an empty list. Use [`MySqlStore::delete_all`] to delete all items.
+    EmptyDeleteConditions,
+    /// [`Session::lock_tables`] is called without any tables to lock.
+    EmptyLockTables,
+    /// An [`AutoIncrement`] column doesn't have an integer type.
+    InvalidAutoIncrement {
+        /// The name of the column.
+        column: String,
+    },
+    /// A [`ForeignKey`] passed to [`MySqlStore::create_with_foreign_keys`] has no columns, a
+    /// column that is not a column of the table or a different number of columns and referenced
+    /// columns.
+    InvalidForeignKey {
+        /// The referencing columns of the foreign key.
+        columns: Vec<String>,
+        /// The referenced table.
+        table: String,
+    },
+    /// The table name passed to [`WithTable::new`] is not a valid unquoted identifier.
+    InvalidTableName {
+        /// The rejected table name.
+        name: String,
+    },
+    /// A unique key passed to [`MySqlStore::create_with_unique_keys`] has no columns.
+    InvalidUniqueKey {
+        /// The name of the unique key.
+        name: String,
+    },
+    /// A create, insert or update query has no columns, e.g. because the type doesn't have any
+    /// fields.
+    NoColumns,
+    /// A type with [`Children`] fields has no primary key referenced by the child tables, or
+    /// the type passed to [`MySqlStore::get_by_key`] doesn't have a primary key of a single
+    /// column.
+    NoPrimaryKey,
+    /// No item matches the query of [`MySqlStore::get_exactly_one`].
+    NotFound,
+    /// A written value of a [`Bits`], [`Bounded`] or [`Year`] column is outside of the range of
+    /// the column.
+    OutOfRange {
+        /// The name of the column.
+        column: String,
+        /// The written value.
+        value: String,
+        /// The smallest value of the column.
+        min: i64,
+        /// The largest value of the column.
+        max: i64,
+    },
+    /// No connection of the pool became available within the [`acquire_timeout`], e.g.
+    /// because all connections are in use by other operations.
+    ///
+    /// [`acquire_timeout`]: MySqlStoreConfig::acquire_timeout
+    PoolExhausted {
+        /// The name of the operation waiting for the connection, e.g. `select`.
+        operation: &'static str,
+        /// The name of the table of the operation, `None` for raw queries and operations without
+        /// a table.
+        table: Option<String>,
+    },
+    /// A query failed to execute. Contains the executed sql and the error returned by the
+    /// database or the underlying sqlx driver.
+    Query {
+        /// The executed sql.
+        sql: String,
+        /// The name of the table of the query, `None` for raw queries.
+        table: Option<String>,
+        /// The error returned by the database or the underlying sqlx driver.
+        source: sqlx::Error,
+    },
+    /// The column of a table doesn't match the field of the type stored in the table. Returned
+    /// by [`MySqlStore::validate_schema`].
+    SchemaMismatch {
+        /// The name of the column.
+        column: String,
+        /// The type of the column expected by the field.
+        expected: String,
+        /// The type of the existing column, `None` if the column doesn't exist.
+        found: Option<String>,
+    },
+    /// The store was closed using [`MySqlStore::close`]. Unlike connection errors this is not
+    /// transient, operations on a closed store never succeed and should not be retried.
+    StoreClosed,
+    /// A query didn't complete within the [`query_timeout`].
+    ///
+    /// [`query_timeout`]: MySqlStoreConfig::query_timeout
+    Timeout,
+    /// [`get_all`] would return more than [`max_rows`] items, or more than one item matches
+    /// the query of [`MySqlStore::get_exactly_one`].
+    ///
+    /// [`get_all`]: datastore::Store::get_all
+    /// [`max_rows`]: MySqlStoreConfig::max_rows
+    TooManyRows,
+    /// A value read from an `ENUM` column is not a variant of the [`SqlEnum`] type of its
+    /// field, e.g. because it was written by a newer version of the application. See
+    /// [`SqlEnum::from_unknown`].
+    UnknownEnumVariant {
+        /// The name of the column.
+        column: String,
+        /// The unknown value.
+        value: String,
+    },
+    /// A [`Write`] or [`DataQuery`] implementation writes something the query doesn't support,
+    /// e.g. a condition into an insert or a column value into a delete.
+    ///
+    /// [`Write`]: datastore::Write
+    /// [`DataQuery`]: datastore::DataQuery
+    UnsupportedOperation {
+        /// What was written, e.g. `condition`.
+        operation: &'static str,
+        /// The kind of the query, e.g. `INSERT`.
+        kind: &'static str,
+    },
+    /// A value is longer than its column can store. `&[u8]` and `Vec<u8>` values are stored
+    /// in a `BLOB` column holding up to 64 KiB, use [`MediumBytes`] or [`LongBytes`] for larger
+    /// values.
+    ValueTooLarge {
+        /// The length of the value in bytes.
+        len: usize,
+        /// The maximum length in bytes of the column.
+        max: usize,
+    },
+}
 
 impl Display for Error {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match &self.0 {
-            ErrorKind::Sqlx(err) => write!(f, "{}", err),
-            ErrorKind::Custom(s) => write!(f, "{}", s),
+        match self {
+            Self::Sqlx(err) => write!(f, "{}", err),
+            Self::Custom(s) => write!(f, "{}", s),
+            Self::AmbiguousColumn { name } => {
+                write!(f, "column {} is returned more than once", name)
+            }
+            Self::ColumnMismatch => {
+                write!(f, "rows of a multi-row insert have different columns")
+            }
+            Self::ColumnValueMismatch { columns, values } => {
+                write!(f, "query writes {} columns but {} values", columns, values)
+            }
+            Self::DuplicateColumn { name } => {
+                write!(f, "column {} is written more than once", name)
+            }
+            Self::Decode {
+                column,
+                rust_type,
+                source,
+            } => write!(
+                f,
+                "failed to decode column {} as {}: {}",
+                column, rust_type, source
+            ),
+            Self::EmptyDeleteConditions => write!(
+                f,
+                "delete query has no conditions, use delete_all to delete all items"
+            ),
+            Self::EmptyLockTables => write!(f, "lock_tables called without any tables"),
+            Self::InvalidAutoIncrement { column } => {
+                write!(
+                    f,
+                    "AUTO_INCREMENT column {} is not an integer column",
+                    column
+                )
+            }
+            Self::InvalidForeignKey { columns, table } => write!(
+                f,
+                "invalid foreign key ({}) referencing table {}",
+                columns.join(","),
+                table
+            ),
+            Self::InvalidTableName { name } => write!(f, "invalid table name {:?}", name),
+            Self::InvalidUniqueKey { name } => write!(f, "unique key {} has no columns", name),
+            Self::NoColumns => write!(f, "query has no columns, the type has no fields"),
+            Self::NoPrimaryKey => write!(f, "table has no suitable primary key"),
+            Self::NotFound => write!(f, "no rows match the query"),
+            Self::OutOfRange {
+                column,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "value {} of column {} is outside of the range {}..={}",
+                value, column, min, max
+            ),
+            Self::PoolExhausted {
+                operation,
+                table: Some(table),
+            } => write!(
+                f,
+                "timed out waiting for a connection for {} on table {}",
+                operation, table
+            ),
+            Self::PoolExhausted {
+                operation,
+                table: None,
+            } => write!(f, "timed out waiting for a connection for {}", operation),
+            Self::Query {
+                sql,
+                table: Some(table),
+                source,
+            } => write!(f, "{} (table {}, sql: \"{}\")", source, table, sql),
+            Self::Query {
+                sql,
+                table: None,
+                source,
+            } => write!(f, "{} (sql: \"{}\")", source, sql),
+            Self::SchemaMismatch {
+                column,
+                expected,
+                found: Some(found),
+            } => write!(
+                f,
+                "column {} has type {}, expected {}",
+                column, found, expected
+            ),
+            Self::SchemaMismatch {
+                column,
+                expected,
+                found: None,
+            } => write!(f, "column {} is missing, expected {}", column, expected),
+            Self::StoreClosed => write!(f, "the store is closed"),
+            Self::Timeout => write!(f, "query timed out"),
+            Self::TooManyRows => write!(f, "query returned more rows than expected"),
+            Self::UnknownEnumVariant { column, value } => {
+                write!(f, "unknown enum variant {:?} in column {}", value, column)
+            }
+            Self::UnsupportedOperation { operation, kind } => {
+                write!(f, "{} queries don't support writing a {}", kind, operation)
+            }
+            Self::ValueTooLarge { len, max } => write!(
+                f,
+                "value of {} bytes exceeds the maximum of {} bytes of the column",
+                len, max
+            ),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Sqlx(err) => Some(err),
+            Self::Decode { source, .. } | Self::Query { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl datastore::Error for Error {
     fn custom<T>(msg: T) -> Self
     where
         T: Display,
     {
-        Self(ErrorKind::Custom(msg.to_string()))
+        Self::Custom(msg.to_string())
     }
 }
 
-#[derive(Debug)]
-pub(crate) enum ErrorKind {
-    Sqlx(sqlx::Error),
-    Custom(String),
+impl From<sqlx::Error> for Error {
+    #[inline]
+    fn from(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::PoolClosed => Self::StoreClosed,
+            err => Self::Sqlx(err),
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
+/// An aggregate function used by [`MySqlStore::aggregate`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AggFunc {
+    /// The average of all values (`AVG`).
+    Avg,
+    /// The number of non-`NULL` values (`COUNT`).
+    Count,
+    /// The largest value (`MAX`).
+    Max,
+    /// The smallest value (`MIN`).
+    Min,
+    /// The sum of all values (`SUM`).
+    Sum,
+}
+
+impl Display for AggFunc {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let string = match self {
+            Self::Avg => "AVG",
+            Self::Count => "COUNT",
+            Self::Max => "MAX",
+            Self::Min => "MIN",
+            Self::Sum => "SUM",
+        };
+
+        write!(f, "{}", string)
+    }
+}
+
+/// The maximum number of placeholders in a prepared statement.
+const MAX_PLACEHOLDERS: usize = u16::MAX as usize;
+
+#[derive(Debug)]
 struct Query<'a> {
     table: &'a str,
     inner: QueryInner,
+    /// The first error recorded while writing the query, e.g. a value that is too large for its
+    /// column. The query fails with the error instead of being executed.
+    error: Option<Error>,
+    /// The columns stored in child tables, with the written values of their rows or the type
+    /// of the child column for `Create` queries.
+    children: Vec<(String, Vec<String>)>,
 }
 
 #[derive(Clone, Debug)]
 enum QueryInner {
+    /// Selects the aggregate `func` of `column` for each distinct value of `group_by`.
+    Aggregate {
+        func: AggFunc,
+        column: String,
+        group_by: String,
+        conditions: Conditions,
+    },
+    Alter {
+        /// The added columns.
+        columns: Vec<String>,
+        values: Vec<String>,
+    },
+    Count {
+        /// Count the distinct non-`NULL` values of this column instead of the rows.
+        distinct: Option<String>,
+        conditions: Conditions,
+    },
     Create {
         columns: Vec<String>,
         values: Vec<String>,
+        primary_key: Vec<String>,
+        /// The names and columns of the `UNIQUE KEY`s of the table.
+        unique_keys: Vec<(String, Vec<String>)>,
+        foreign_keys: Vec<ForeignKey>,
+        charset: Option<String>,
+        collation: Option<String>,
+        engine: Option<String>,
+        /// The first value of the `AUTO_INCREMENT` column of the table.
+        auto_increment: Option<u64>,
+        /// Do nothing if the table already exists instead of failing.
+        if_not_exists: bool,
+    },
+    CreateIndex {
+        name: String,
+        /// The indexed columns and the length of their indexed prefix.
+        columns: Vec<(String, Option<u16>)>,
+        unique: bool,
     },
     Delete {
         conditions: Conditions,
+        limit: Option<u64>,
+    },
+    /// Selects the [`ColumnInfo`] of the existing columns of the table.
+    Describe,
+    /// Drops the table if it exists.
+    Drop,
+    Exists {
+        conditions: Conditions,
+    },
+    /// Selects whether the index `name` exists on the table.
+    IndexExists {
+        name: String,
     },
     Insert {
         columns: Vec<String>,
-        values: Vec<String>,
+        /// The values of each inserted row.
+        values: Vec<Vec<String>>,
+        /// Update the existing row if a row with the same key already exists.
+        upsert: bool,
+        /// Replace the existing row if a row with the same key already exists.
+        replace: bool,
+        /// Skip the row if a row with the same key already exists.
+        ignore: bool,
+        /// The `AUTO_INCREMENT` column of the inserted rows.
+        auto_increment: Option<String>,
+        /// Return the value of the `auto_increment` column as the insert id if an upsert updates
+        /// an existing row.
+        returning_id: bool,
+    },
+    /// A query written by the user.
+    Raw {
+        sql: String,
+        /// The values bound to the parameters of `sql`.
+        binds: Vec<Value>,
     },
     Select {
+        /// Only return distinct rows.
+        distinct: bool,
+        columns: Vec<String>,
+        conditions: Conditions,
+        order_by: Vec<OrderBy>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+        /// Lock the returned rows (`FOR UPDATE`).
+        for_update: bool,
+    },
+    /// Deletes all rows of the table and resets the `AUTO_INCREMENT` counter.
+    Truncate,
+    Update {
         columns: Vec<String>,
+        values: Vec<String>,
         conditions: Conditions,
     },
 }
@@ -108,181 +615,1644 @@ enum QueryInner {
 impl<'a> Query<'a> {
     pub fn new(table: &'a str, kind: QueryKind) -> Self {
         let inner = match kind {
+            QueryKind::Alter => QueryInner::Alter {
+                columns: Vec::new(),
+                values: Vec::new(),
+            },
+            QueryKind::Count => QueryInner::Count {
+                distinct: None,
+                conditions: Conditions::default(),
+            },
             QueryKind::Create => QueryInner::Create {
                 columns: Vec::new(),
                 values: Vec::new(),
+                primary_key: Vec::new(),
+                unique_keys: Vec::new(),
+                foreign_keys: Vec::new(),
+                charset: None,
+                collation: None,
+                engine: None,
+                auto_increment: None,
+                if_not_exists: true,
             },
             QueryKind::Delete => QueryInner::Delete {
                 conditions: Conditions::default(),
+                limit: None,
+            },
+            QueryKind::Describe => QueryInner::Describe,
+            QueryKind::Drop => QueryInner::Drop,
+            QueryKind::Exists => QueryInner::Exists {
+                conditions: Conditions::default(),
             },
             QueryKind::Insert => QueryInner::Insert {
                 columns: Vec::new(),
-                values: Vec::new(),
+                values: vec![Vec::new()],
+                upsert: false,
+                replace: false,
+                ignore: false,
+                auto_increment: None,
+                returning_id: false,
             },
             QueryKind::Select => QueryInner::Select {
+                distinct: false,
+                columns: Vec::new(),
+                conditions: Conditions::default(),
+                order_by: Vec::new(),
+                limit: None,
+                offset: None,
+                for_update: false,
+            },
+            QueryKind::Truncate => QueryInner::Truncate,
+            QueryKind::Update => QueryInner::Update {
                 columns: Vec::new(),
+                values: Vec::new(),
                 conditions: Conditions::default(),
             },
         };
 
-        Self { table, inner }
+        Self {
+            table,
+            inner,
+            error: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Creates a new query selecting the aggregate `func` of `column` of `table` for each
+    /// distinct value of `group_by`.
+    pub fn aggregate(table: &'a str, func: AggFunc, column: String, group_by: String) -> Self {
+        Self {
+            table,
+            inner: QueryInner::Aggregate {
+                func,
+                column,
+                group_by,
+                conditions: Conditions::default(),
+            },
+            error: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Creates a new query executing the user-provided `sql` with the parameters `binds`.
+    pub fn raw(sql: String, binds: Vec<Value>) -> Self {
+        Self {
+            table: "",
+            inner: QueryInner::Raw { sql, binds },
+            error: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Creates a new query creating the index `name` on the `columns` of `table`. Columns with a
+    /// prefix length only index the first characters or bytes of the value.
+    pub fn create_index(
+        table: &'a str,
+        name: String,
+        columns: Vec<(String, Option<u16>)>,
+        unique: bool,
+    ) -> Self {
+        Self {
+            table,
+            inner: QueryInner::CreateIndex {
+                name,
+                columns,
+                unique,
+            },
+            error: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Creates a new query selecting whether the index `name` exists on `table`.
+    pub fn index_exists(table: &'a str, name: String) -> Self {
+        Self {
+            table,
+            inner: QueryInner::IndexExists { name },
+            error: None,
+            children: Vec::new(),
+        }
     }
 
     pub fn push(&mut self, key: String, value: String) {
+        let columns = match &self.inner {
+            QueryInner::Alter { columns, .. }
+            | QueryInner::Create { columns, .. }
+            | QueryInner::Insert { columns, .. }
+            | QueryInner::Select { columns, .. }
+            | QueryInner::Update { columns, .. } => Some(columns),
+            _ => None,
+        };
+
+        if columns.is_some_and(|columns| columns.contains(&key)) {
+            self.set_error(Error::DuplicateColumn { name: key.clone() });
+        }
+
         match &mut self.inner {
-            QueryInner::Create { columns, values } => {
+            QueryInner::Aggregate { .. }
+            | QueryInner::Count { .. }
+            | QueryInner::CreateIndex { .. }
+            | QueryInner::Delete { .. }
+            | QueryInner::Describe
+            | QueryInner::Drop
+            | QueryInner::Exists { .. }
+            | QueryInner::IndexExists { .. }
+            | QueryInner::Raw { .. }
+            | QueryInner::Truncate => {
+                self.set_unsupported("column value");
+            }
+            QueryInner::Alter { columns, values }
+            | QueryInner::Create {
+                columns, values, ..
+            } => {
                 columns.push(key);
                 values.push(value);
             }
-            QueryInner::Delete { conditions: _ } => {
-                unreachable!()
+            QueryInner::Insert {
+                columns, values, ..
+            } => {
+                columns.push(key);
+                values[0].push(value);
             }
-            QueryInner::Insert { columns, values } => {
+            QueryInner::Select { columns, .. } => {
                 columns.push(key);
-                values.push(value);
             }
-            QueryInner::Select {
-                columns,
-                conditions: _,
+            QueryInner::Update {
+                columns, values, ..
             } => {
                 columns.push(key);
+                values.push(value);
             }
         }
     }
 
     pub fn push_condition(&mut self, condition: Condition) {
         match &mut self.inner {
-            QueryInner::Create {
-                columns: _,
-                values: _,
-            } => unreachable!(),
-            QueryInner::Delete { conditions } => {
+            QueryInner::Aggregate { conditions, .. } | QueryInner::Count { conditions, .. } => {
                 conditions.push(condition);
             }
-            QueryInner::Insert {
-                columns: _,
-                values: _,
-            } => {
-                unreachable!()
+            QueryInner::Alter { .. }
+            | QueryInner::Create { .. }
+            | QueryInner::CreateIndex { .. }
+            | QueryInner::Describe
+            | QueryInner::Drop
+            | QueryInner::IndexExists { .. }
+            | QueryInner::Insert { .. }
+            | QueryInner::Raw { .. }
+            | QueryInner::Truncate => {
+                self.set_unsupported("condition");
             }
-            QueryInner::Select {
-                columns: _,
-                conditions,
-            } => {
+            QueryInner::Delete { conditions, .. } | QueryInner::Exists { conditions } => {
+                conditions.push(condition);
+            }
+            QueryInner::Select { conditions, .. } => {
+                conditions.push(condition);
+            }
+            QueryInner::Update { conditions, .. } => {
                 conditions.push(condition);
             }
         }
     }
-}
 
-impl<'a> Display for Query<'a> {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    /// Adds the column `key` to the primary key of a `Create` query. Does nothing for an
+    /// `Alter` or `Select` query.
+    pub fn push_primary_key(&mut self, key: String) {
+        match &mut self.inner {
+            QueryInner::Create { primary_key, .. } => primary_key.push(key),
+            QueryInner::Alter { .. } | QueryInner::Select { .. } => (),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Records the `AUTO_INCREMENT` `column` written into an `Insert` query. Other queries
+    /// ignore the column.
+    pub fn set_auto_increment(&mut self, column: String) {
+        if let QueryInner::Insert { auto_increment, .. } = &mut self.inner {
+            auto_increment.get_or_insert(column);
+        }
+    }
+
+    /// Returns the columns of the primary key of a `Create` query.
+    pub fn primary_key(&self) -> &[String] {
         match &self.inner {
-            QueryInner::Create { columns, values } => write!(
-                f,
-                "CREATE TABLE IF NOT EXISTS {} ({})",
-                self.table,
-                columns
-                    .iter()
-                    .zip(values)
-                    .map(|(column, value)| format!("{} {}", column, value))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ),
-            QueryInner::Delete { conditions } => {
-                write!(f, "DELETE FROM {}{}", self.table, conditions)
-            }
-            QueryInner::Insert { columns, values } => write!(
-                f,
-                "INSERT INTO {} ({}) VALUES ({})",
-                self.table,
-                columns.join(","),
-                values.join(",")
-            ),
-            QueryInner::Select {
-                columns,
-                conditions,
-            } => write!(
-                f,
-                "SELECT {} FROM {}{}",
-                columns.join(","),
-                self.table,
-                conditions
-            ),
+            QueryInner::Create { primary_key, .. } => primary_key,
+            _ => unreachable!(),
         }
     }
-}
 
-#[derive(Clone, Debug, Default)]
-struct Conditions {
-    conditions: Vec<Condition>,
-}
+    /// Adds the `foreign_key` to the table created by a `Create` query.
+    pub fn push_foreign_key(&mut self, foreign_key: ForeignKey) {
+        match &mut self.inner {
+            QueryInner::Create { foreign_keys, .. } => foreign_keys.push(foreign_key),
+            _ => unreachable!(),
+        }
+    }
 
-impl Conditions {
-    pub fn push(&mut self, value: Condition) {
-        self.conditions.push(value);
+    /// Returns an [`Error::InvalidForeignKey`] for the first foreign key of a `Create` query
+    /// that has no columns, a column that is not a column of the table or a different number of
+    /// columns and referenced columns.
+    pub fn invalid_foreign_key(&self) -> Option<Error> {
+        let QueryInner::Create {
+            columns,
+            foreign_keys,
+            ..
+        } = &self.inner
+        else {
+            return None;
+        };
+
+        foreign_keys
+            .iter()
+            .find(|foreign_key| {
+                foreign_key.columns.is_empty()
+                    || foreign_key.columns.len() != foreign_key.references.len()
+                    || foreign_key
+                        .columns
+                        .iter()
+                        .any(|column| !columns.contains(column))
+            })
+            .map(|foreign_key| Error::InvalidForeignKey {
+                columns: foreign_key.columns.clone(),
+                table: foreign_key.table.clone(),
+            })
     }
-}
 
-impl Display for Conditions {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        if self.conditions.is_empty() {
-            return Ok(());
+    /// Records that the column `key` is stored in a child table. `values` contains the values
+    /// of the child rows, or the type of the child column for a `Create` query.
+    pub fn push_child(&mut self, key: String, values: Vec<String>) {
+        self.children.push((key, values));
+    }
+
+    /// Returns the columns stored in child tables, see [`push_child`].
+    ///
+    /// [`push_child`]: Self::push_child
+    pub fn children(&self) -> &[(String, Vec<String>)] {
+        &self.children
+    }
+
+    /// Sets the default charset and collation of the table created by a `Create` query. `None`
+    /// uses the default of the database.
+    pub fn table_options(&mut self, charset: Option<String>, collation: Option<String>) {
+        match &mut self.inner {
+            QueryInner::Create {
+                charset: c,
+                collation: col,
+                ..
+            } => {
+                *c = charset;
+                *col = collation;
+            }
+            _ => unreachable!(),
         }
+    }
 
-        write!(f, " WHERE {}", self.conditions[0])?;
+    /// Sets the storage engine of the table created by a `Create` query. `None` uses the default
+    /// of the server.
+    pub fn engine(&mut self, engine: Option<String>) {
+        match &mut self.inner {
+            QueryInner::Create { engine: e, .. } => *e = engine,
+            _ => unreachable!(),
+        }
+    }
 
-        for condition in self.conditions.iter().skip(1) {
-            write!(f, " AND {}", condition)?;
+    /// Sets the first value of the `AUTO_INCREMENT` column of the table created by a `Create`
+    /// query.
+    pub fn auto_increment_start(&mut self, start: u64) {
+        match &mut self.inner {
+            QueryInner::Create { auto_increment, .. } => *auto_increment = Some(start),
+            _ => unreachable!(),
         }
+    }
 
-        Ok(())
+    /// Adds the `UNIQUE KEY` `name` on `columns` to the table created by a `Create` query.
+    /// Records [`Error::InvalidUniqueKey`] if `columns` is empty.
+    pub fn push_unique_key(&mut self, name: String, columns: Vec<String>) {
+        if columns.is_empty() {
+            self.set_error(Error::InvalidUniqueKey { name });
+            return;
+        }
+
+        match &mut self.inner {
+            QueryInner::Create { unique_keys, .. } => unique_keys.push((name, columns)),
+            _ => unreachable!(),
+        }
     }
-}
 
-/// A single sql condition. (e.g. id = 1)
-#[derive(Clone, Debug)]
-struct Condition {
-    column: String,
-    value: String,
-    comparator: Comparator,
-}
+    /// Makes the `Create` query fail if the table already exists instead of doing nothing.
+    pub fn strict(&mut self) {
+        match &mut self.inner {
+            QueryInner::Create { if_not_exists, .. } => *if_not_exists = false,
+            _ => unreachable!(),
+        }
+    }
 
-impl Condition {
-    pub fn new(column: String, value: String, comparator: Comparator) -> Self {
-        Self {
-            column,
-            value,
-            comparator,
+    /// Appends all `conditions` to the conditions of the query.
+    pub fn push_conditions(&mut self, conditions: Conditions) {
+        match &mut self.inner {
+            QueryInner::Aggregate { conditions: c, .. }
+            | QueryInner::Count { conditions: c, .. }
+            | QueryInner::Delete { conditions: c, .. }
+            | QueryInner::Exists { conditions: c }
+            | QueryInner::Select { conditions: c, .. }
+            | QueryInner::Update { conditions: c, .. } => c.extend(conditions),
+            QueryInner::Alter { .. }
+            | QueryInner::Create { .. }
+            | QueryInner::CreateIndex { .. }
+            | QueryInner::Describe
+            | QueryInner::Drop
+            | QueryInner::IndexExists { .. }
+            | QueryInner::Insert { .. }
+            | QueryInner::Raw { .. }
+            | QueryInner::Truncate => self.set_unsupported("condition"),
         }
     }
-}
 
-impl Display for Condition {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{} {} {}", self.column, self.comparator, self.value)
+    /// Returns `true` if the query has any conditions that don't match every row. Conditions
+    /// that always match, e.g. an `OR` with an empty group or a `NOT IN` with an empty list,
+    /// count as no conditions.
+    pub fn has_conditions(&self) -> bool {
+        match &self.inner {
+            QueryInner::Aggregate { conditions, .. }
+            | QueryInner::Count { conditions, .. }
+            | QueryInner::Delete { conditions, .. }
+            | QueryInner::Exists { conditions }
+            | QueryInner::Select { conditions, .. }
+            | QueryInner::Update { conditions, .. } => !conditions.always_matches(),
+            QueryInner::Alter { .. }
+            | QueryInner::Create { .. }
+            | QueryInner::CreateIndex { .. }
+            | QueryInner::Describe
+            | QueryInner::Drop
+            | QueryInner::IndexExists { .. }
+            | QueryInner::Insert { .. }
+            | QueryInner::Raw { .. }
+            | QueryInner::Truncate => false,
+        }
     }
-}
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum Comparator {
-    Eq,
-}
+    /// Returns `true` if the query is a `Create`, `Insert` or `Update` query without columns.
+    /// MySQL rejects these queries.
+    pub fn missing_columns(&self) -> bool {
+        match &self.inner {
+            QueryInner::Create { columns, .. }
+            | QueryInner::Insert { columns, .. }
+            | QueryInner::Update { columns, .. } => columns.is_empty(),
+            _ => false,
+        }
+    }
 
-impl Display for Comparator {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let string = match self {
-            Self::Eq => "=",
+    /// Returns the error for an `Insert`, `Update` or `Alter` query with a different number of
+    /// columns and values, e.g. a row of `INSERT INTO t (a,b) VALUES (1)`.
+    pub fn column_value_mismatch(&self) -> Option<Error> {
+        let columns = match &self.inner {
+            QueryInner::Alter { columns, .. }
+            | QueryInner::Insert { columns, .. }
+            | QueryInner::Update { columns, .. } => columns,
+            _ => return None,
         };
 
-        write!(f, "{}", string)
+        let values = match &self.inner {
+            QueryInner::Alter { values, .. } | QueryInner::Update { values, .. } => values.len(),
+            QueryInner::Insert { values, .. } => values
+                .iter()
+                .map(Vec::len)
+                .find(|len| *len != columns.len())?,
+            _ => return None,
+        };
+
+        (values != columns.len()).then_some(Error::ColumnValueMismatch {
+            columns: columns.len(),
+            values,
+        })
+    }
+
+    /// Records the `err` of a value or operation written into the query. Only the first error
+    /// is recorded.
+    pub fn set_error(&mut self, err: Error) {
+        self.error.get_or_insert(err);
+    }
+
+    /// Records that the `operation` was written into the query, but isn't supported by the kind
+    /// of the query.
+    pub fn set_unsupported(&mut self, operation: &'static str) {
+        let kind = self.kind();
+        self.set_error(Error::UnsupportedOperation { operation, kind });
+    }
+
+    /// Takes the first error recorded while writing the query.
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    /// Returns the error the query fails with instead of being executed. The recorded error is
+    /// taken, see [`take_error`], so the query is validated once right before it's executed.
+    ///
+    /// [`take_error`]: Self::take_error
+    pub fn validate(&mut self) -> Result<(), Error> {
+        if self.missing_columns() {
+            return Err(Error::NoColumns);
+        }
+
+        if let Some(err) = self.take_error() {
+            return Err(err);
+        }
+
+        match self.column_value_mismatch() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the table reported in an [`Error::Query`] of the query, `None` for raw queries.
+    pub fn error_table(&self) -> Option<&'a str> {
+        match self.inner {
+            QueryInner::Raw { .. } => None,
+            _ => Some(self.table),
+        }
+    }
+
+    /// Returns `true` if the `Alter` query adds any columns.
+    pub fn has_columns(&self) -> bool {
+        match &self.inner {
+            QueryInner::Alter { columns, .. } => !columns.is_empty(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Removes all columns contained in `existing` from an `Alter` query. Column names are
+    /// compared case-insensitively, like MySQL does.
+    pub fn remove_columns(&mut self, existing: &[String]) {
+        match &mut self.inner {
+            QueryInner::Alter { columns, values } => {
+                let (kept_columns, kept_values) = columns
+                    .drain(..)
+                    .zip(values.drain(..))
+                    .filter(|(column, _)| {
+                        !existing
+                            .iter()
+                            .any(|existing| existing.eq_ignore_ascii_case(column))
+                    })
+                    .unzip();
+
+                *columns = kept_columns;
+                *values = kept_values;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the value written to `column` in the first row of an `Insert` query.
+    pub fn value(&self, column: &str) -> Option<&str> {
+        match &self.inner {
+            QueryInner::Insert {
+                columns, values, ..
+            } => columns
+                .iter()
+                .position(|c| c == column)
+                .map(|index| values[0][index].as_str()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Consumes the `Insert` query, returning the values of the first row.
+    pub fn into_values(self) -> Vec<String> {
+        match self.inner {
+            QueryInner::Insert { mut values, .. } => values.swap_remove(0),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the names and types of all columns of a `Create` query.
+    pub fn column_types(&self) -> impl Iterator<Item = (&str, &str)> {
+        match &self.inner {
+            QueryInner::Create {
+                columns, values, ..
+            } => columns
+                .iter()
+                .zip(values)
+                .map(|(column, value)| (column.as_str(), value.as_str())),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the name of the query kind, e.g. `SELECT`.
+    pub fn kind(&self) -> &'static str {
+        match self.inner {
+            QueryInner::Aggregate { .. } => "SELECT",
+            QueryInner::Alter { .. } => "ALTER",
+            QueryInner::Count { .. } => "SELECT",
+            QueryInner::Create { .. } => "CREATE",
+            QueryInner::CreateIndex { .. } => "CREATE",
+            QueryInner::Delete { .. } => "DELETE",
+            QueryInner::Describe => "SELECT",
+            QueryInner::Drop => "DROP",
+            QueryInner::Exists { .. } => "SELECT",
+            QueryInner::IndexExists { .. } => "SELECT",
+            QueryInner::Insert { .. } => "INSERT",
+            QueryInner::Raw { .. } => "RAW",
+            QueryInner::Select { .. } => "SELECT",
+            QueryInner::Truncate => "TRUNCATE",
+            QueryInner::Update { .. } => "UPDATE",
+        }
+    }
+
+    /// Returns the name of the operation executed by the query, e.g. `select`.
+    pub fn operation(&self) -> &'static str {
+        match self.inner {
+            QueryInner::Aggregate { .. } => "aggregate",
+            QueryInner::Alter { .. } => "alter",
+            QueryInner::Count { .. } => "count",
+            QueryInner::Create { .. } => "create",
+            QueryInner::CreateIndex { .. } => "create_index",
+            QueryInner::Delete { .. } => "delete",
+            QueryInner::Describe => "describe",
+            QueryInner::Drop => "drop",
+            QueryInner::Exists { .. } => "exists",
+            QueryInner::IndexExists { .. } => "index_exists",
+            QueryInner::Insert { .. } => "insert",
+            QueryInner::Raw { .. } => "raw",
+            QueryInner::Select { .. } => "select",
+            QueryInner::Truncate => "truncate",
+            QueryInner::Update { .. } => "update",
+        }
+    }
+
+    /// Appends all rows of the `Insert` query `other` to this `Insert` query. Returns `false`
+    /// and leaves this query unchanged if the columns of both queries differ.
+    pub fn append(&mut self, other: Query<'a>) -> bool {
+        match (&mut self.inner, other.inner) {
+            (
+                QueryInner::Insert {
+                    columns, values, ..
+                },
+                QueryInner::Insert {
+                    columns: other_columns,
+                    values: other_values,
+                    ..
+                },
+            ) => {
+                if *columns != other_columns {
+                    return false;
+                }
+
+                values.extend(other_values);
+                if let Some(err) = other.error {
+                    self.set_error(err);
+                }
+                true
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Makes the `Insert` query update all columns of an existing row instead of failing if a
+    /// row with the same key already exists.
+    pub fn upsert(&mut self) {
+        match &mut self.inner {
+            QueryInner::Insert { upsert, .. } => *upsert = true,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Makes the `Insert` query update an existing row like [`upsert`] and return the value of
+    /// the written `AUTO_INCREMENT` column of the updated row as the insert id. The column keeps
+    /// its value. Without an `AUTO_INCREMENT` column this is the same as [`upsert`].
+    ///
+    /// [`upsert`]: Self::upsert
+    pub fn upsert_returning_id(&mut self) {
+        match &mut self.inner {
+            QueryInner::Insert {
+                upsert,
+                returning_id,
+                ..
+            } => {
+                *upsert = true;
+                *returning_id = true;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Makes the `Insert` query delete an existing row with the same key before inserting the
+    /// new row instead of failing.
+    pub fn replace(&mut self) {
+        match &mut self.inner {
+            QueryInner::Insert { replace, .. } => *replace = true,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Makes the `Insert` query skip rows with the same key as an existing row instead of
+    /// failing.
+    pub fn ignore(&mut self) {
+        match &mut self.inner {
+            QueryInner::Insert { ignore, .. } => *ignore = true,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Makes a `Count` query count the distinct non-`NULL` values of `column` instead of the
+    /// rows.
+    pub fn count_distinct(&mut self, column: String) {
+        match &mut self.inner {
+            QueryInner::Count { distinct, .. } => *distinct = Some(column),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Orders the rows returned by a `Select` query by `column`. Rows are ordered by the
+    /// columns in the order they were added.
+    pub fn order_by(&mut self, column: String, order: Order) {
+        self.push_order_by(OrderBy {
+            column,
+            order,
+            nulls: NullsOrder::Default,
+        });
+    }
+
+    /// Orders the rows returned by a `Select` query by `order_by`, after the columns that were
+    /// already added.
+    pub fn push_order_by(&mut self, order_by: OrderBy) {
+        match &mut self.inner {
+            QueryInner::Select { order_by: o, .. } => o.push(order_by),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Orders the rows returned by a `Select` query by the `primary_key` columns, unless the
+    /// query is already ordered.
+    pub fn order_by_primary_key(&mut self, primary_key: &[String]) {
+        match &mut self.inner {
+            QueryInner::Select { order_by, .. } if order_by.is_empty() => {
+                order_by.extend(primary_key.iter().map(|column| OrderBy::asc(column)));
+            }
+            QueryInner::Select { .. } => (),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Sets the maximum number of rows returned by a `Select` query or deleted by a `Delete`
+    /// query.
+    pub fn limit(&mut self, value: u64) {
+        match &mut self.inner {
+            QueryInner::Delete { limit, .. } | QueryInner::Select { limit, .. } => {
+                *limit = Some(value)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Makes the `Select` query only return distinct rows.
+    pub fn distinct(&mut self) {
+        match &mut self.inner {
+            QueryInner::Select { distinct, .. } => *distinct = true,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Sets the number of rows skipped by a `Select` query.
+    pub fn offset(&mut self, value: u64) {
+        match &mut self.inner {
+            QueryInner::Select { offset, .. } => *offset = Some(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Makes the `Select` query lock the returned rows until the end of the transaction.
+    pub fn for_update(&mut self) {
+        match &mut self.inner {
+            QueryInner::Select { for_update, .. } => *for_update = true,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Removes all columns not contained in `keep` from a `Select` query.
+    pub fn retain_columns(&mut self, keep: &[&str]) {
+        match &mut self.inner {
+            QueryInner::Select { columns, .. } => {
+                columns.retain(|column| keep.contains(&column.as_str()))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the sql of the query with a `?` placeholder in place of every value that can be
+    /// bound, and the bound values in the order of their placeholders. The sql only depends on
+    /// the table, the kind of the query and its columns, not on the values.
+    ///
+    /// Queries with more values than MySQL accepts placeholders in a statement are returned
+    /// with the literals and no bound values. Raw queries are returned as is.
+    pub fn prepare(&self) -> (String, Vec<Value>) {
+        if let QueryInner::Raw { sql, binds } = &self.inner {
+            return (sql.clone(), binds.clone());
+        }
+
+        let dialect = PlaceholderDialect::default();
+        let sql = WithDialect(self, &dialect).to_string();
+        let binds = dialect.into_binds();
+
+        if binds.len() > MAX_PLACEHOLDERS {
+            return (self.to_string(), Vec::new());
+        }
+
+        (sql, binds)
+    }
+}
+
+impl<'a> Display for Query<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_with(&MySqlDialect, f)
+    }
+}
+
+impl<'a> FmtWith for Query<'a> {
+    fn fmt_with<D>(&self, dialect: &D, f: &mut Formatter) -> fmt::Result
+    where
+        D: Dialect,
+    {
+        let table = dialect.quote_ident(self.table);
+
+        match &self.inner {
+            // sqlx only decodes `DOUBLE` values into `f64`, but `SUM` and `AVG` return a
+            // `DECIMAL` and `COUNT` returns a `BIGINT`.
+            QueryInner::Aggregate {
+                func,
+                column,
+                group_by,
+                conditions,
+            } => {
+                let group_by = dialect.quote_ident(group_by);
+                let column = match column.as_str() {
+                    "*" => String::from("*"),
+                    column => dialect.quote_ident(column),
+                };
+
+                write!(
+                    f,
+                    "SELECT {},CAST({}({}) AS DOUBLE) FROM {}{} GROUP BY {}",
+                    group_by,
+                    func,
+                    column,
+                    table,
+                    WithDialect(conditions, dialect),
+                    group_by
+                )
+            }
+            QueryInner::Alter { columns, values } => write!(
+                f,
+                "ALTER TABLE {} {}",
+                table,
+                columns
+                    .iter()
+                    .zip(values)
+                    .map(|(column, value)| {
+                        format!("ADD COLUMN {} {}", dialect.quote_ident(column), value)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            QueryInner::Count {
+                distinct,
+                conditions,
+            } => match distinct {
+                Some(column) => write!(
+                    f,
+                    "SELECT COUNT(DISTINCT {}) FROM {}{}",
+                    dialect.quote_ident(column),
+                    table,
+                    WithDialect(conditions, dialect)
+                ),
+                None => write!(
+                    f,
+                    "SELECT COUNT(*) FROM {}{}",
+                    table,
+                    WithDialect(conditions, dialect)
+                ),
+            },
+            QueryInner::Create {
+                columns,
+                values,
+                primary_key,
+                unique_keys,
+                foreign_keys,
+                charset,
+                collation,
+                engine,
+                auto_increment,
+                if_not_exists,
+            } => {
+                write!(
+                    f,
+                    "CREATE TABLE {}{} ({}",
+                    if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                    table,
+                    columns
+                        .iter()
+                        .zip(values)
+                        .map(|(column, value)| format!("{} {}", dialect.quote_ident(column), value))
+                        .collect::<Vec<String>>()
+                        .join(",")
+                )?;
+
+                if !primary_key.is_empty() {
+                    write!(
+                        f,
+                        ",PRIMARY KEY ({})",
+                        dialect::join_idents(dialect, primary_key)
+                    )?;
+                }
+
+                for (name, columns) in unique_keys {
+                    write!(
+                        f,
+                        ",UNIQUE KEY {} ({})",
+                        dialect.quote_ident(name),
+                        dialect::join_idents(dialect, columns)
+                    )?;
+                }
+
+                for foreign_key in foreign_keys {
+                    write!(
+                        f,
+                        ",FOREIGN KEY ({}) REFERENCES {} ({})",
+                        dialect::join_idents(dialect, &foreign_key.columns),
+                        dialect.quote_ident(&foreign_key.table),
+                        dialect::join_idents(dialect, &foreign_key.references)
+                    )?;
+
+                    if let Some(action) = foreign_key.on_delete {
+                        write!(f, " ON DELETE {}", action)?;
+                    }
+                }
+
+                write!(f, ")")?;
+
+                if let Some(engine) = engine {
+                    write!(f, " ENGINE={}", engine)?;
+                }
+
+                if let Some(auto_increment) = auto_increment {
+                    write!(f, " AUTO_INCREMENT={}", auto_increment)?;
+                }
+
+                if let Some(charset) = charset {
+                    write!(f, " DEFAULT CHARSET={}", charset)?;
+                }
+
+                if let Some(collation) = collation {
+                    write!(f, " COLLATE={}", collation)?;
+                }
+
+                Ok(())
+            }
+            QueryInner::CreateIndex {
+                name,
+                columns,
+                unique,
+            } => write!(
+                f,
+                "CREATE {}INDEX {} ON {} ({})",
+                if *unique { "UNIQUE " } else { "" },
+                dialect.quote_ident(name),
+                table,
+                columns
+                    .iter()
+                    .map(|(column, prefix_len)| match prefix_len {
+                        Some(prefix_len) => {
+                            format!("{}({})", dialect.quote_ident(column), prefix_len)
+                        }
+                        None => dialect.quote_ident(column),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            QueryInner::Delete { conditions, limit } => {
+                write!(
+                    f,
+                    "DELETE FROM {}{}",
+                    table,
+                    WithDialect(conditions, dialect)
+                )?;
+
+                if let Some(limit) = limit {
+                    write!(f, " LIMIT {}", dialect.value(&limit.to_string()))?;
+                }
+
+                Ok(())
+            }
+            QueryInner::Describe => write!(
+                f,
+                "SELECT COLUMN_NAME,COLUMN_TYPE,IS_NULLABLE,COLUMN_KEY,COLUMN_DEFAULT FROM information_schema.columns WHERE table_schema = DATABASE() AND table_name = {} ORDER BY ORDINAL_POSITION",
+                dialect.quote_string_literal(self.table)
+            ),
+            QueryInner::Drop => write!(f, "DROP TABLE IF EXISTS {}", table),
+            QueryInner::Exists { conditions } => write!(
+                f,
+                "SELECT EXISTS(SELECT 1 FROM {}{})",
+                table,
+                WithDialect(conditions, dialect)
+            ),
+            QueryInner::IndexExists { name } => write!(
+                f,
+                "SELECT EXISTS(SELECT 1 FROM information_schema.statistics WHERE table_schema = DATABASE() AND table_name = {} AND index_name = {})",
+                dialect.quote_string_literal(self.table),
+                dialect.quote_string_literal(name)
+            ),
+            QueryInner::Insert {
+                columns,
+                values,
+                upsert,
+                replace,
+                ignore,
+                auto_increment,
+                returning_id,
+            } => {
+                write!(
+                    f,
+                    "{}{} INTO {} ({}) VALUES {}",
+                    if *replace { "REPLACE" } else { "INSERT" },
+                    if *ignore { " IGNORE" } else { "" },
+                    table,
+                    dialect::join_idents(dialect, columns),
+                    values
+                        .iter()
+                        .map(|row| {
+                            let row = row
+                                .iter()
+                                .map(|value| dialect.value(value))
+                                .collect::<Vec<String>>();
+                            format!("({})", row.join(","))
+                        })
+                        .collect::<Vec<String>>()
+                        .join(",")
+                )?;
+
+                if *upsert {
+                    let returning_id = auto_increment.as_ref().filter(|_| *returning_id);
+                    let mut assignments = columns
+                        .iter()
+                        .filter(|column| Some(*column) != returning_id)
+                        .map(|column| {
+                            let column = dialect.quote_ident(column);
+                            format!("{}=VALUES({})", column, column)
+                        })
+                        .collect::<Vec<String>>();
+
+                    // LAST_INSERT_ID(expr) makes the id of the updated row the insert id.
+                    if let Some(column) = returning_id {
+                        let column = dialect.quote_ident(column);
+                        assignments.push(format!("{}=LAST_INSERT_ID({})", column, column));
+                    }
+
+                    write!(f, " ON DUPLICATE KEY UPDATE {}", assignments.join(","))?;
+                }
+
+                Ok(())
+            }
+            QueryInner::Raw { sql, .. } => write!(f, "{}", sql),
+            QueryInner::Select {
+                distinct,
+                columns,
+                conditions,
+                order_by,
+                limit,
+                offset,
+                for_update,
+            } => {
+                // Queries without columns select all columns of the table.
+                write!(
+                    f,
+                    "SELECT {}{} FROM {}{}",
+                    if *distinct { "DISTINCT " } else { "" },
+                    if columns.is_empty() {
+                        String::from("*")
+                    } else {
+                        dialect::join_idents(dialect, columns)
+                    },
+                    table,
+                    WithDialect(conditions, dialect)
+                )?;
+
+                if !order_by.is_empty() {
+                    write!(
+                        f,
+                        " ORDER BY {}",
+                        order_by
+                            .iter()
+                            .map(|order_by| order_by.to_sql(dialect))
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    )?;
+                }
+
+                // MySQL only accepts an OFFSET after a LIMIT. Use the largest possible
+                // limit if only the offset is given.
+                match (limit, offset) {
+                    (Some(limit), _) => write!(f, " LIMIT {}", dialect.value(&limit.to_string()))?,
+                    (None, Some(_)) => write!(f, " LIMIT {}", dialect.value(&u64::MAX.to_string()))?,
+                    (None, None) => (),
+                }
+
+                if let Some(offset) = offset {
+                    write!(f, " OFFSET {}", dialect.value(&offset.to_string()))?;
+                }
+
+                if *for_update {
+                    write!(f, " FOR UPDATE")?;
+                }
+
+                Ok(())
+            }
+            QueryInner::Truncate => write!(f, "TRUNCATE TABLE {}", table),
+            QueryInner::Update {
+                columns,
+                values,
+                conditions,
+            } => write!(
+                f,
+                "UPDATE {} SET {}{}",
+                table,
+                columns
+                    .iter()
+                    .zip(values)
+                    .map(|(column, value)| {
+                        format!("{} = {}", dialect.quote_ident(column), dialect.value(value))
+                    })
+                    .collect::<Vec<String>>()
+                    .join(","),
+                WithDialect(conditions, dialect)
+            ),
+        }
+    }
+}
+
+/// A list of conditions that must all match.
+#[derive(Clone, Debug, Default)]
+struct Conditions {
+    conditions: Vec<ConditionNode>,
+}
+
+impl Conditions {
+    pub fn push(&mut self, value: Condition) {
+        self.conditions.push(ConditionNode::Condition(value));
+    }
+
+    /// Adds a group of conditions of which at least one must match.
+    pub fn push_any(&mut self, groups: Vec<Conditions>) {
+        self.conditions.push(ConditionNode::Any(groups));
+    }
+
+    /// Adds a full-text search for the quoted `terms` in the `columns`.
+    pub fn push_match(&mut self, columns: Vec<String>, terms: String, mode: MatchMode) {
+        self.conditions.push(ConditionNode::Match {
+            columns,
+            terms,
+            mode,
+        });
+    }
+
+    /// Returns `true` if the conditions match every row, e.g. if there are no conditions.
+    pub fn always_matches(&self) -> bool {
+        self.conditions.iter().all(ConditionNode::always_matches)
+    }
+
+    /// Appends all conditions of `other`.
+    pub fn extend(&mut self, other: Conditions) {
+        self.conditions.extend(other.conditions);
+    }
+
+    /// Writes the conditions joined with `AND`, without the leading `WHERE`.
+    fn fmt_inner<D>(&self, dialect: &D, f: &mut Formatter) -> fmt::Result
+    where
+        D: Dialect,
+    {
+        // An empty list of conditions matches everything.
+        if self.conditions.is_empty() {
+            return write!(f, "TRUE");
+        }
+
+        write!(f, "{}", WithDialect(&self.conditions[0], dialect))?;
+
+        for condition in self.conditions.iter().skip(1) {
+            write!(f, " AND {}", WithDialect(condition, dialect))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FmtWith for Conditions {
+    fn fmt_with<D>(&self, dialect: &D, f: &mut Formatter) -> fmt::Result
+    where
+        D: Dialect,
+    {
+        if self.conditions.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, " WHERE ")?;
+        self.fmt_inner(dialect, f)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum ConditionNode {
+    Condition(Condition),
+    /// Matches if any of the groups matches.
+    Any(Vec<Conditions>),
+    /// A full-text search. `terms` is a quoted string literal.
+    Match {
+        columns: Vec<String>,
+        terms: String,
+        mode: MatchMode,
+    },
+}
+
+impl ConditionNode {
+    /// Returns `true` if the node matches every row.
+    fn always_matches(&self) -> bool {
+        match self {
+            Self::Condition(condition) => {
+                matches!(condition.comparator, Comparator::NotIn) && condition.values.is_empty()
+            }
+            Self::Any(groups) => groups.iter().any(Conditions::always_matches),
+            Self::Match { .. } => false,
+        }
+    }
+}
+
+impl FmtWith for ConditionNode {
+    fn fmt_with<D>(&self, dialect: &D, f: &mut Formatter) -> fmt::Result
+    where
+        D: Dialect,
+    {
+        match self {
+            Self::Condition(condition) => condition.fmt_with(dialect, f),
+            // `OR` without any operands never matches.
+            Self::Any(groups) if groups.is_empty() => write!(f, "FALSE"),
+            Self::Any(groups) => {
+                write!(f, "(")?;
+
+                for (index, group) in groups.iter().enumerate() {
+                    if index != 0 {
+                        write!(f, " OR ")?;
+                    }
+
+                    write!(f, "(")?;
+                    group.fmt_inner(dialect, f)?;
+                    write!(f, ")")?;
+                }
+
+                write!(f, ")")
+            }
+            Self::Match {
+                columns,
+                terms,
+                mode,
+            } => write!(
+                f,
+                "MATCH ({}) AGAINST ({} {})",
+                dialect::join_idents(dialect, columns),
+                dialect.value(terms),
+                mode
+            ),
+        }
+    }
+}
+
+/// A single sql condition. (e.g. id = 1)
+#[derive(Clone, Debug)]
+struct Condition {
+    column: String,
+    comparator: Comparator,
+    /// The values compared against. Contains a single value for all comparators except
+    /// [`Comparator::In`], [`Comparator::NotIn`], [`Comparator::Between`], [`Comparator::IsNull`]
+    /// and [`Comparator::IsNotNull`].
+    values: Vec<String>,
+    /// The collation used to compare a single value.
+    collation: Option<String>,
+    /// The single value is the name of another column instead of a literal.
+    value_is_column: bool,
+    /// The JSON path within the column that is compared instead of the column.
+    json_path: Option<String>,
+}
+
+impl Condition {
+    pub fn new(column: String, value: String, comparator: Comparator) -> Self {
+        Self {
+            column,
+            comparator,
+            values: vec![value],
+            collation: None,
+            value_is_column: false,
+            json_path: None,
+        }
+    }
+
+    /// Creates a new condition comparing the column with the column `other`.
+    pub fn columns(column: String, other: String, comparator: Comparator) -> Self {
+        Self {
+            column,
+            comparator,
+            values: vec![other],
+            collation: None,
+            value_is_column: true,
+            json_path: None,
+        }
+    }
+
+    /// Compares the value using `collation` instead of the collation of the column.
+    pub fn collate(mut self, collation: String) -> Self {
+        self.collation = Some(collation);
+        self
+    }
+
+    /// Compares the unquoted value at the JSON `path` within the column instead of the column.
+    pub fn json_path(mut self, path: String) -> Self {
+        self.json_path = Some(path);
+        self
+    }
+
+    /// Creates a new condition matching if the column is equal to any of the `values`.
+    pub fn in_list(column: String, values: Vec<String>) -> Self {
+        Self {
+            column,
+            comparator: Comparator::In,
+            values,
+            collation: None,
+            value_is_column: false,
+            json_path: None,
+        }
+    }
+
+    /// Creates a new condition matching if the column is not equal to any of the `values`.
+    pub fn not_in_list(column: String, values: Vec<String>) -> Self {
+        Self {
+            column,
+            comparator: Comparator::NotIn,
+            values,
+            collation: None,
+            value_is_column: false,
+            json_path: None,
+        }
+    }
+
+    /// Creates a new condition matching if the column is `NULL`.
+    pub fn is_null(column: String) -> Self {
+        Self {
+            column,
+            comparator: Comparator::IsNull,
+            values: Vec::new(),
+            collation: None,
+            value_is_column: false,
+            json_path: None,
+        }
+    }
+
+    /// Creates a new condition matching if the column is not `NULL`.
+    pub fn is_not_null(column: String) -> Self {
+        Self {
+            column,
+            comparator: Comparator::IsNotNull,
+            values: Vec::new(),
+            collation: None,
+            value_is_column: false,
+            json_path: None,
+        }
+    }
+
+    /// Creates a new condition matching if the column is between `low` and `high`, inclusive.
+    pub fn between(column: String, low: String, high: String) -> Self {
+        Self {
+            column,
+            comparator: Comparator::Between,
+            values: vec![low, high],
+            collation: None,
+            value_is_column: false,
+            json_path: None,
+        }
+    }
+}
+
+impl FmtWith for Condition {
+    fn fmt_with<D>(&self, dialect: &D, f: &mut Formatter) -> fmt::Result
+    where
+        D: Dialect,
+    {
+        let mut column = dialect.quote_ident(&self.column);
+        if let Some(path) = &self.json_path {
+            // The path is written as an escaped string literal, it can't end the expression.
+            column = format!("{}->>{}", column, dialect.quote_string_literal(path));
+        }
+
+        match self.comparator {
+            // `IN ()` is not valid sql. An empty list never matches.
+            Comparator::In if self.values.is_empty() => write!(f, "FALSE"),
+            // `NOT IN ()` is not valid sql either. Excluding no values always matches.
+            Comparator::NotIn if self.values.is_empty() => write!(f, "TRUE"),
+            Comparator::In | Comparator::NotIn => write!(
+                f,
+                "{} {} ({})",
+                column,
+                self.comparator,
+                self.values
+                    .iter()
+                    .map(|value| dialect.value(value))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            Comparator::Between => write!(
+                f,
+                "{} {} {} AND {}",
+                column,
+                self.comparator,
+                dialect.value(&self.values[0]),
+                dialect.value(&self.values[1])
+            ),
+            Comparator::IsNull | Comparator::IsNotNull => {
+                write!(f, "{} {}", column, self.comparator)
+            }
+            _ if self.value_is_column => write!(
+                f,
+                "{} {} {}",
+                column,
+                self.comparator,
+                dialect.quote_ident(&self.values[0])
+            ),
+            _ => {
+                write!(
+                    f,
+                    "{} {} {}",
+                    column,
+                    self.comparator,
+                    dialect.value(&self.values[0])
+                )?;
+
+                if let Some(collation) = &self.collation {
+                    write!(f, " COLLATE {}", collation)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Comparator {
+    Between,
+    Eq,
+    Ge,
+    Gt,
+    In,
+    IsNull,
+    IsNotNull,
+    Le,
+    Like,
+    Lt,
+    Ne,
+    NotIn,
+}
+
+impl Display for Comparator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let string = match self {
+            Self::Between => "BETWEEN",
+            Self::Eq => "=",
+            Self::Ge => ">=",
+            Self::Gt => ">",
+            Self::In => "IN",
+            Self::IsNull => "IS NULL",
+            Self::IsNotNull => "IS NOT NULL",
+            Self::Le => "<=",
+            Self::Like => "LIKE",
+            Self::Lt => "<",
+            Self::Ne => "<>",
+            Self::NotIn => "NOT IN",
+        };
+
+        write!(f, "{}", string)
+    }
+}
+
+/// The direction of an `ORDER BY` column, used by [`MySqlStore::get_top`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// Smallest values first (`ASC`).
+    Asc,
+    /// Largest values first (`DESC`).
+    Desc,
+}
+
+impl Display for Order {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Asc => write!(f, "ASC"),
+            Self::Desc => write!(f, "DESC"),
+        }
+    }
+}
+
+/// Where `NULL` values are sorted by an [`OrderBy`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NullsOrder {
+    /// The order of MySQL: `NULL`s first for [`Order::Asc`] and last for [`Order::Desc`].
+    #[default]
+    Default,
+    /// `NULL`s before all other values.
+    First,
+    /// `NULL`s after all other values.
+    Last,
+}
+
+/// A column of an `ORDER BY`, used by [`MySqlStore::get_ordered`].
+///
+/// MySQL has no `NULLS FIRST` or `NULLS LAST`. If [`nulls`] is set the rows are ordered by
+/// `(column IS NULL)` first, which is `1` for `NULL`s and `0` for all other values.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore_mysql::OrderBy;
+///
+/// // ORDER BY (due_at IS NULL),due_at DESC,id ASC
+/// let order_by = [OrderBy::desc("due_at").nulls_last(), OrderBy::asc("id")];
+/// ```
+///
+/// [`nulls`]: Self::nulls
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OrderBy {
+    /// The name of the column.
+    pub column: String,
+    /// The direction of the column.
+    pub order: Order,
+    /// Where `NULL` values of the column are sorted.
+    pub nulls: NullsOrder,
+}
+
+impl OrderBy {
+    /// Creates a new `OrderBy` ordering `column` ascending.
+    pub fn asc(column: &str) -> Self {
+        Self {
+            column: column.to_owned(),
+            order: Order::Asc,
+            nulls: NullsOrder::Default,
+        }
+    }
+
+    /// Creates a new `OrderBy` ordering `column` descending.
+    pub fn desc(column: &str) -> Self {
+        Self {
+            column: column.to_owned(),
+            order: Order::Desc,
+            nulls: NullsOrder::Default,
+        }
+    }
+
+    /// Sorts `NULL` values before all other values.
+    pub fn nulls_first(mut self) -> Self {
+        self.nulls = NullsOrder::First;
+        self
+    }
+
+    /// Sorts `NULL` values after all other values.
+    pub fn nulls_last(mut self) -> Self {
+        self.nulls = NullsOrder::Last;
+        self
+    }
+
+    /// Formats the `ORDER BY` expressions of the column.
+    fn to_sql<D>(&self, dialect: &D) -> String
+    where
+        D: Dialect,
+    {
+        let column = dialect.quote_ident(&self.column);
+
+        match self.nulls {
+            NullsOrder::Default => format!("{} {}", column, self.order),
+            NullsOrder::First => format!("({} IS NULL) DESC,{} {}", column, column, self.order),
+            NullsOrder::Last => format!("({} IS NULL),{} {}", column, column, self.order),
+        }
+    }
+}
+
+/// A `FOREIGN KEY` of a table, used by [`MySqlStore::create_with_foreign_keys`].
+///
+/// # Examples
+///
+/// ```
+/// use datastore_mysql::{ForeignKey, ReferentialAction};
+///
+/// // FOREIGN KEY (parent_id) REFERENCES parent (id) ON DELETE CASCADE
+/// let foreign_key =
+///     ForeignKey::new(&["parent_id"], "parent", &["id"]).on_delete(ReferentialAction::Cascade);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ForeignKey {
+    /// The referencing columns of the created table.
+    pub columns: Vec<String>,
+    /// The name of the referenced table.
+    pub table: String,
+    /// The referenced columns of `table`, in the order of `columns`.
+    pub references: Vec<String>,
+    /// What happens to the rows when the referenced row is deleted. `None` uses the default of
+    /// MySQL, which rejects deleting a referenced row.
+    pub on_delete: Option<ReferentialAction>,
+}
+
+impl ForeignKey {
+    /// Creates a new `ForeignKey` on `columns` referencing the `references` columns of `table`.
+    pub fn new(columns: &[&str], table: &str, references: &[&str]) -> Self {
+        Self {
+            columns: columns.iter().map(|column| (*column).to_owned()).collect(),
+            table: table.to_owned(),
+            references: references
+                .iter()
+                .map(|column| (*column).to_owned())
+                .collect(),
+            on_delete: None,
+        }
+    }
+
+    /// Applies `action` to the rows when the referenced row is deleted (`ON DELETE`).
+    pub fn on_delete(mut self, action: ReferentialAction) -> Self {
+        self.on_delete = Some(action);
+        self
+    }
+}
+
+/// The action of a [`ForeignKey`] when the referenced row is deleted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ReferentialAction {
+    /// Deletes the referencing rows (`CASCADE`).
+    Cascade,
+    /// Sets the referencing columns to `NULL` (`SET NULL`). The columns must be nullable.
+    SetNull,
+    /// Rejects deleting the referenced row (`RESTRICT`).
+    Restrict,
+}
+
+impl Display for ReferentialAction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Cascade => write!(f, "CASCADE"),
+            Self::SetNull => write!(f, "SET NULL"),
+            Self::Restrict => write!(f, "RESTRICT"),
+        }
+    }
+}
+
+/// An existing column of a table as reported by MySQL, returned by
+/// [`MySqlStore::describe_table`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColumnInfo {
+    /// The name of the column.
+    pub name: String,
+    /// The full sql type of the column, e.g. `int unsigned` or `varchar(255)`.
+    pub column_type: String,
+    /// Whether the column accepts `NULL`.
+    pub nullable: bool,
+    /// The index of the column: `PRI` for the primary key, `UNI` for the first column of a
+    /// unique key and `MUL` for the first column of any other index. `None` if the column isn't
+    /// the first column of an index.
+    pub key: Option<String>,
+    /// The default value of the column, e.g. `0` or `CURRENT_TIMESTAMP`. `None` if the column
+    /// has no default or defaults to `NULL`.
+    pub default: Option<String>,
+}
+
+impl ColumnInfo {
+    /// Creates a `ColumnInfo` from a row of the `Describe` query.
+    pub(crate) fn from_row(
+        (name, column_type, nullable, key, default): (
+            String,
+            String,
+            String,
+            String,
+            Option<String>,
+        ),
+    ) -> Self {
+        Self {
+            name,
+            column_type,
+            nullable: nullable == "YES",
+            key: if key.is_empty() { None } else { Some(key) },
+            default,
+        }
     }
 }
 
 #[derive(Debug)]
 pub(crate) enum QueryKind {
+    Alter,
+    Count,
     Create,
     Delete,
+    Describe,
+    Drop,
+    Exists,
     Insert,
     Select,
+    Truncate,
+    Update,
 }
@@ -1,633 +1,8590 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::convert::Infallible;
 use std::fmt::{Debug, Write as _};
-
-use crate::{Comparator, Condition, Error, ErrorKind, Query, QueryKind};
+use std::future::Future;
+use std::time::Duration;
+
+use crate::dialect::{self, Dialect, MySqlDialect};
+use crate::session::SessionGuard;
+use crate::table;
+use crate::types::{UnknownVariant, BLOB_MAX_LEN};
+use crate::{
+    hints, AggFunc, ColumnInfo, Comparator, Condition, Error, Filter, ForeignKey,
+    MySqlStoreBuilder, MySqlStoreConfig, Order, OrderBy, Query, QueryInner, QueryKind,
+    ReferentialAction, Session, Transaction, UpdateExpr, Value,
+};
 
 use async_trait::async_trait;
-use datastore::{DataDescriptor, DataQuery, Reader, Store, StoreData, TypeWriter, Write, Writer};
-use futures::TryStreamExt;
-use sqlx::{mysql::MySqlRow, MySql, Pool, Row};
+use datastore::{
+    DataDescriptor, DataQuery, Read, Reader, Store, StoreData, TypeWriter, Write, Writer,
+};
+use futures::future::BoxFuture;
+use futures::{Stream, TryStreamExt};
+use sqlx::mysql::{
+    MySqlArguments, MySqlConnectOptions, MySqlConnection, MySqlDatabaseError, MySqlQueryResult,
+    MySqlRow,
+};
+use sqlx::{Column, Connection, Decode, Executor, MySql, Pool, Row, TypeInfo, ValueRef};
+
+/// The maximum number of ids in the `IN` list of a single query executed by
+/// [`MySqlStore::get_by_ids`].
+pub(crate) const IDS_PER_QUERY: usize = 1000;
+
+/// The delay before the first retry of a query that failed because of a lock error. The delay
+/// doubles with every retry.
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// The column of a child table storing the index of the value in the [`Children`].
+///
+/// [`Children`]: crate::Children
+const CHILD_POSITION: &str = "position";
+
+/// The column of a child table storing the value.
+const CHILD_VALUE: &str = "value";
+
+thread_local! {
+    /// The child rows of all [`Children`] fields of the row that is currently read, by column.
+    ///
+    /// [`Children`]: crate::Children
+    static CHILD_ROWS: RefCell<HashMap<String, Vec<MySqlRow>>> = RefCell::new(HashMap::new());
+}
+
+/// The options of a [`MySqlStore`] used for every executed query.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct QueryOptions {
+    pub timeout: Option<Duration>,
+    pub log_level: Option<log::Level>,
+    pub redact_sql: bool,
+    pub max_retries: u32,
+    pub fetch_size: usize,
+}
+
+impl QueryOptions {
+    /// Returns the `sql` as it's logged and included in errors, redacted if
+    /// [`MySqlStoreConfig::redact_sql`] is set.
+    fn visible_sql<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        if self.redact_sql {
+            Cow::Owned(redact(sql))
+        } else {
+            Cow::Borrowed(sql)
+        }
+    }
+}
+
+/// A snapshot of the connections of a pool, returned by [`MySqlStore::pool_status`].
+///
+/// The numbers are read from the pool independently and may be slightly inconsistent while
+/// connections are being acquired or released.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PoolStatus {
+    /// The number of open connections, both idle and in use. This never exceeds
+    /// [`max_connections`].
+    ///
+    /// [`max_connections`]: MySqlStoreConfig::max_connections
+    pub size: u32,
+    /// The number of open connections that are waiting to be acquired.
+    pub idle: u32,
+    /// The number of connections that are currently acquired by an operation or a
+    /// [`Transaction`]. If this equals [`max_connections`] the pool is exhausted and new
+    /// operations wait for a connection to be released.
+    ///
+    /// [`max_connections`]: MySqlStoreConfig::max_connections
+    pub in_use: u32,
+}
+
+impl PoolStatus {
+    fn new(pool: &Pool<MySql>) -> Self {
+        let size = pool.size();
+        let idle = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX).min(size);
+
+        Self {
+            size,
+            idle,
+            in_use: size - idle,
+        }
+    }
+}
 
 /// A pooled [`Store`] for the MySQL database.
+///
+/// All operations that only read items (e.g. [`get`] or [`count`]) are executed on the read
+/// pool, all other operations and [`Transaction`]s on the primary pool. Both pools are the same
+/// unless the store was created using [`connect_split`].
+///
+/// [`get`]: Store::get
+/// [`count`]: Self::count
+/// [`connect_split`]: Self::connect_split
 #[derive(Clone, Debug)]
 pub struct MySqlStore {
     pool: Pool<MySql>,
+    read_pool: Pool<MySql>,
+    options: QueryOptions,
+    max_rows: Option<u64>,
+    insert_batch_size: usize,
+    order_by_primary_key: bool,
+    charset: Option<String>,
+    collation: Option<String>,
+    engine: Option<String>,
 }
 
-#[async_trait]
-impl Store for MySqlStore {
-    type DataStore = Self;
-    type Error = Error;
+impl MySqlStore {
+    /// Returns a [`MySqlStoreBuilder`] to connect to the store without building a uri.
+    #[inline]
+    pub fn builder() -> MySqlStoreBuilder {
+        MySqlStoreBuilder::new()
+    }
 
-    async fn connect(uri: &str) -> Result<Self, Self::Error> {
-        let pool = Pool::connect(uri)
-            .await
-            .map_err(|err| Error(ErrorKind::Sqlx(err)))?;
+    /// Connects to the store using the given uri and [`MySqlStoreConfig`].
+    pub async fn connect_with(uri: &str, config: MySqlStoreConfig) -> Result<Self, Error> {
+        let options = config.connect_options(uri)?;
+        Self::connect_options(options, config).await
+    }
 
-        Ok(Self { pool })
+    pub(crate) async fn connect_options(
+        options: MySqlConnectOptions,
+        config: MySqlStoreConfig,
+    ) -> Result<Self, Error> {
+        let pool = config.pool_options().connect_with(options).await?;
+
+        Ok(Self {
+            read_pool: pool.clone(),
+            pool,
+            options: config.query_options(),
+            max_rows: config.max_rows,
+            insert_batch_size: config.insert_batch_size,
+            order_by_primary_key: config.order_by_primary_key,
+            charset: config.charset,
+            collation: config.collation,
+            engine: config.engine,
+        })
     }
 
-    async fn create<T, D>(&self, descriptor: D) -> Result<(), Self::Error>
-    where
-        T: StoreData<Self> + Send + Sync + 'static,
-        D: DataDescriptor<T, Self> + Send + Sync,
-    {
-        let table = descriptor.ident();
-        let mut writer = MySqlTypeWriter::new(table, QueryKind::Create);
-        descriptor.write(&mut writer).unwrap();
+    /// Connects to the store using a primary at `write_uri` and a read replica at `read_uri`.
+    ///
+    /// All operations that only read items are executed on the replica, all other operations
+    /// on the primary. If `read_uri` is `None` all operations are executed on the primary. Both
+    /// pools use the given [`MySqlStoreConfig`].
+    ///
+    /// Note: Reads on the replica may not see the latest writes to the primary because of
+    /// replication lag. Use a [`Transaction`], which always runs on the primary, to read items
+    /// directly after writing them.
+    pub async fn connect_split(
+        write_uri: &str,
+        read_uri: Option<&str>,
+        config: MySqlStoreConfig,
+    ) -> Result<Self, Error> {
+        let pool = config
+            .pool_options()
+            .connect_with(config.connect_options(write_uri)?)
+            .await?;
+
+        let read_pool = match read_uri {
+            Some(read_uri) => {
+                config
+                    .pool_options()
+                    .connect_with(config.connect_options(read_uri)?)
+                    .await?
+            }
+            None => pool.clone(),
+        };
 
-        let sql = writer.sql();
-        log::debug!("Executing sql CREATE query: \"{}\"", sql);
+        Ok(Self {
+            pool,
+            read_pool,
+            options: config.query_options(),
+            max_rows: config.max_rows,
+            insert_batch_size: config.insert_batch_size,
+            order_by_primary_key: config.order_by_primary_key,
+            charset: config.charset,
+            collation: config.collation,
+            engine: config.engine,
+        })
+    }
 
-        sqlx::query(&sql)
-            .execute(&self.pool)
-            .await
-            .map_err(|err| Error(ErrorKind::Sqlx(err)))?;
-        Ok(())
+    /// Connects to the store using the given uri, retrying up to `max_attempts` times if the
+    /// connection fails with a transient error.
+    ///
+    /// The delay between attempts starts at `base_delay` and doubles after every failed
+    /// attempt. Only I/O errors and pool timeouts are retried, all other errors (e.g. an
+    /// authentication failure) are returned immediately. After `max_attempts` failed attempts
+    /// the last error is returned. A `max_attempts` of `0` is treated as `1`.
+    pub async fn connect_with_retry(
+        uri: &str,
+        max_attempts: u32,
+        base_delay: Duration,
+    ) -> Result<Self, Error> {
+        retry(max_attempts, base_delay, is_connection_error, || {
+            Self::connect_with(uri, MySqlStoreConfig::default())
+        })
+        .await
     }
 
-    async fn delete<T, D, Q>(&self, descriptor: D, query: Q) -> Result<(), Self::Error>
-    where
-        T: StoreData<Self::DataStore> + Send + Sync + 'static,
-        D: DataDescriptor<T, Self::DataStore> + Send,
-        Q: DataQuery<T, Self::DataStore> + Send,
-    {
-        let table = descriptor.ident();
-        let mut writer = MySqlWriter::new(table, QueryKind::Delete);
-        writer.write_conditions = true;
-        query.write(&mut writer).unwrap();
+    /// Creates a new `MySqlStore` using an existing [`Pool`]. The store uses the options of the
+    /// default [`MySqlStoreConfig`].
+    #[inline]
+    pub fn from_pool(pool: Pool<MySql>) -> Self {
+        let config = MySqlStoreConfig::default();
 
-        let sql = writer.sql();
-        log::debug!("Executing sql DELETE query: \"{}\"", sql);
+        Self {
+            read_pool: pool.clone(),
+            pool,
+            options: config.query_options(),
+            max_rows: None,
+            insert_batch_size: config.insert_batch_size,
+            order_by_primary_key: config.order_by_primary_key,
+            charset: config.charset,
+            collation: config.collation,
+            engine: config.engine,
+        }
+    }
+
+    /// Creates a new `MySqlStore` using an existing primary [`Pool`] and read `Pool`. See
+    /// [`connect_split`].
+    ///
+    /// [`connect_split`]: Self::connect_split
+    #[inline]
+    pub fn from_pools(pool: Pool<MySql>, read_pool: Pool<MySql>) -> Self {
+        let config = MySqlStoreConfig::default();
+
+        Self {
+            pool,
+            read_pool,
+            options: config.query_options(),
+            max_rows: None,
+            insert_batch_size: config.insert_batch_size,
+            order_by_primary_key: config.order_by_primary_key,
+            charset: config.charset,
+            collation: config.collation,
+            engine: config.engine,
+        }
+    }
+
+    /// Sets the charset, collation and engine of the tables created by the `Create` `query`.
+    fn table_options(&self, query: &mut Query<'_>) {
+        query.table_options(self.charset.clone(), self.collation.clone());
+        query.engine(self.engine.clone());
+    }
+
+    /// Executes the `Create` query and creates the child tables of all [`Children`] fields.
+    /// Child tables are always created with `IF NOT EXISTS`.
+    ///
+    /// [`Children`]: crate::Children
+    async fn execute_create(&self, query: &mut Query<'_>) -> Result<(), Error> {
+        let tables = child_tables(query);
+        let children = create_children(query, &tables)?;
+
+        execute(&self.pool, self.options, query).await?;
+        for mut child in children {
+            self.table_options(&mut child);
+            execute(&self.pool, self.options, &mut child).await?;
+        }
 
-        sqlx::query(&sql)
-            .execute(&self.pool)
-            .await
-            .map_err(|err| Error(ErrorKind::Sqlx(err)))?;
         Ok(())
     }
 
-    async fn get<T, D, Q>(&self, descriptor: D, query: Q) -> Result<Vec<T>, Self::Error>
+    /// Executes the `Select` query on the read pool and reads all returned rows into `T`,
+    /// loading the [`Children`] fields of `T`. See [`fetch_all_with_children`].
+    ///
+    /// [`Children`]: crate::Children
+    async fn fetch_all_with_children<T>(
+        &self,
+        primary_key: &[String],
+        query: Query<'_>,
+        max_rows: Option<u64>,
+    ) -> Result<Vec<T>, Error>
     where
-        T: StoreData<Self::DataStore> + Send + Sync + 'static,
-        D: DataDescriptor<T, Self::DataStore> + Send,
-        Q: DataQuery<T, Self::DataStore> + Send,
+        T: StoreData<Self>,
     {
-        let table = descriptor.ident();
+        let mut conn = self
+            .read_pool
+            .acquire()
+            .await
+            .map_err(|err| acquire_error(err, query.operation(), query.error_table()))?;
+        fetch_all_with_children(&mut conn, self.options, primary_key, query, max_rows).await
+    }
+
+    /// Executes the `Insert` query on the primary and inserts the child rows of its
+    /// [`Children`] fields in a transaction. Returns the number of affected rows of the parent.
+    ///
+    /// [`Children`]: crate::Children
+    async fn execute_insert_with_children(
+        &self,
+        primary_key: &[String],
+        query: &mut Query<'_>,
+    ) -> Result<u64, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| acquire_error(err, query.operation(), query.error_table()))?;
+        let rows = insert_with_children(&mut tx, self.options, primary_key, query).await?;
+        tx.commit().await?;
+        Ok(rows)
+    }
 
-        let mut writer = MySqlWriter::new(table, QueryKind::Select);
-        descriptor.write(&mut writer).unwrap();
+    /// Executes the writing `query` on the primary, retrying it up to
+    /// [`MySqlStoreConfig::max_retries`] times if it fails because of a deadlock or lock wait
+    /// timeout.
+    async fn execute_write(&self, query: &mut Query<'_>) -> Result<MySqlQueryResult, Error> {
+        query.validate()?;
+        let query = &*query;
+
+        retry(
+            self.options.max_retries.saturating_add(1),
+            LOCK_RETRY_DELAY,
+            is_lock_error,
+            || execute_validated(&self.pool, self.options, query),
+        )
+        .await
+    }
 
-        writer.write_conditions = true;
-        query.write(&mut writer).unwrap();
+    /// Returns a copy of the store logging the sql of queries at `level`, `None` disables
+    /// logging. The copy shares the connections of the store.
+    ///
+    /// This overrides [`MySqlStoreConfig::log_level`] for single operations, e.g. to not log
+    /// queries writing sensitive data.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// store.with_log_level(None).insert(descriptor, user).await?;
+    /// ```
+    pub fn with_log_level(&self, level: Option<log::Level>) -> Self {
+        let mut store = self.clone();
+        store.options.log_level = level;
+        store
+    }
 
-        let sql = writer.sql();
-        log::debug!("Executing sql SELECT query: \"{}\"", sql);
+    /// Returns a reference to the underlying primary [`Pool`]. This can be used to run queries
+    /// using sqlx directly on the same connections as the store.
+    #[inline]
+    pub fn pool(&self) -> &Pool<MySql> {
+        &self.pool
+    }
 
-        let mut rows = sqlx::query(&sql).fetch(&self.pool);
+    /// Returns a reference to the underlying [`Pool`] used for reads. This is the same pool as
+    /// [`pool`] unless the store has a separate read pool.
+    ///
+    /// [`pool`]: Self::pool
+    #[inline]
+    pub fn read_pool(&self) -> &Pool<MySql> {
+        &self.read_pool
+    }
 
-        let mut entries = Vec::new();
-        while let Some(row) = rows
-            .try_next()
+    /// Returns the current [`PoolStatus`] of the primary pool.
+    #[inline]
+    pub fn pool_status(&self) -> PoolStatus {
+        PoolStatus::new(&self.pool)
+    }
+
+    /// Returns the current [`PoolStatus`] of the read pool. This is the same as
+    /// [`pool_status`] unless the store has a separate read pool.
+    ///
+    /// [`pool_status`]: Self::pool_status
+    #[inline]
+    pub fn read_pool_status(&self) -> PoolStatus {
+        PoolStatus::new(&self.read_pool)
+    }
+
+    /// Begins a new [`Transaction`] on a connection of the store.
+    pub async fn begin(&self) -> Result<Transaction, Error> {
+        let inner = self
+            .pool
+            .begin()
             .await
-            .map_err(|err| Error(ErrorKind::Sqlx(err)))?
-        {
-            let mut reader = MySqlReader::new(row);
-            let data = T::read(&mut reader).unwrap();
+            .map_err(|err| acquire_error(err, "begin", None))?;
+
+        Ok(Transaction::new(
+            inner,
+            self.options,
+            self.max_rows,
+            self.order_by_primary_key,
+        ))
+    }
 
-            entries.push(data);
+    /// Acquires a single connection of the store and calls `f` with a [`Session`] on it.
+    ///
+    /// Operations on the store may run on different connections of the pool. All operations
+    /// of the `Session` run on the same connection and share the state of the session, e.g.
+    /// temporary tables, user variables and `LAST_INSERT_ID()`. The operations are not atomic,
+    /// use a [`Transaction`] for that. The connection is returned to the pool after `f`
+    /// completes, table locks acquired by [`Session::lock_tables`] are released before.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let id: u64 = store
+    ///     .with_connection(|session| {
+    ///         Box::pin(async move {
+    ///             session.insert(store.descriptor::<Person>(), person).await?;
+    ///             session.query_raw_scalar("SELECT LAST_INSERT_ID()", &[]).await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn with_connection<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: for<'c> FnOnce(&'c mut Session) -> BoxFuture<'c, Result<R, Error>>,
+    {
+        let inner = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|err| acquire_error(err, "with_connection", None))?;
+
+        let mut guard =
+            SessionGuard::new(Session::new(inner, self.options, self.order_by_primary_key));
+        let res = f(guard.session()).await;
+
+        // Table locks are not released when the connection is returned to the pool.
+        if guard.session().tables_locked() {
+            guard.session().unlock_tables().await?;
         }
 
-        Ok(entries)
+        res
     }
 
-    async fn get_all<T, D>(&self, descriptor: D) -> Result<Vec<T>, Self::Error>
+    /// Calls `f` with a [`Session`] on a single connection on which foreign key and unique
+    /// checks are disabled, e.g. to load large amounts of data.
+    ///
+    /// Inserting without `FOREIGN_KEY_CHECKS` and `UNIQUE_CHECKS` avoids a lookup per row and
+    /// is much faster, but rows violating a constraint are not rejected. Only import data that
+    /// is known to be consistent. The checks are enabled again after `f` completes, even if it
+    /// returns an error. If enabling them fails or the returned future is dropped the
+    /// connection is closed instead of returned to the pool.
+    ///
+    /// The checks are session variables, other connections are not affected. If the process is
+    /// killed during the import the server ends the session, but the rows imported so far are
+    /// not checked later.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// store
+    ///     .import(|session| {
+    ///         Box::pin(async move {
+    ///             for order in orders {
+    ///                 session.insert(store.descriptor::<Order>(), order).await?;
+    ///             }
+    ///             Ok(())
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn import<F, R>(&self, f: F) -> Result<R, Error>
     where
-        T: StoreData<Self::DataStore> + Send + Sync + 'static,
-        D: DataDescriptor<T, Self::DataStore> + Send + Sync,
+        F: for<'c> FnOnce(&'c mut Session) -> BoxFuture<'c, Result<R, Error>>,
     {
-        let table = descriptor.ident();
-        let mut writer = MySqlTypeWriter::new(table, QueryKind::Select);
-        descriptor.write(&mut writer).unwrap();
+        let inner = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|err| acquire_error(err, "import", None))?;
 
-        let sql = writer.sql();
-        log::debug!("Executing sql SELECT query: \"{}\"", sql);
+        let mut guard =
+            SessionGuard::new(Session::new(inner, self.options, self.order_by_primary_key));
+        guard.session().set_checks(false).await?;
 
-        let mut rows = sqlx::query(&sql).fetch(&self.pool);
+        let res = f(guard.session()).await;
 
-        let mut entries = Vec::new();
-        while let Some(row) = rows
-            .try_next()
+        guard.session().set_checks(true).await?;
+        if guard.session().tables_locked() {
+            guard.session().unlock_tables().await?;
+        }
+
+        res
+    }
+
+    /// Checks whether the database can be reached by acquiring a connection from the pool and
+    /// pinging the server.
+    ///
+    /// Acquiring the connection fails after [`acquire_timeout`] if no connection becomes
+    /// available.
+    ///
+    /// [`acquire_timeout`]: MySqlStoreConfig::acquire_timeout
+    pub async fn ping(&self) -> Result<(), Error> {
+        let mut conn = self
+            .pool
+            .acquire()
             .await
-            .map_err(|err| Error(ErrorKind::Sqlx(err)))?
-        {
-            let mut reader = MySqlReader::new(row);
-            let data = T::read(&mut reader).map_err(|err| Error(ErrorKind::Sqlx(err)))?;
+            .map_err(|err| acquire_error(err, "ping", None))?;
+        conn.ping().await?;
+        Ok(())
+    }
 
-            entries.push(data);
-        }
+    /// Closes all connections of the store.
+    ///
+    /// Idle connections are closed immediately. The returned future completes once all
+    /// connections that are currently in use, including those of open [`Transaction`]s, are
+    /// returned to the pool and closed. Call `close` before the process exits to avoid leaving
+    /// sessions open on the server.
+    ///
+    /// The store can't be used after calling `close`. All subsequent operations on the store and
+    /// its clones fail with [`Error::StoreClosed`].
+    pub async fn close(&self) {
+        self.pool.close().await;
+        self.read_pool.close().await;
+    }
 
-        Ok(entries)
+    /// Returns `true` if [`close`] was called on the store or any of its clones.
+    ///
+    /// [`close`]: Self::close
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.pool.is_closed()
     }
 
-    async fn get_one<T, D, Q>(&self, descriptor: D, query: Q) -> Result<Option<T>, Self::Error>
+    /// Creates the table of `T`. Unlike [`create`] this fails if the table already exists,
+    /// even if it has the same schema.
+    ///
+    /// [`create`]: Store::create
+    pub async fn create_strict<T, D>(&self, descriptor: D) -> Result<(), Error>
     where
-        T: StoreData<Self::DataStore> + Send + Sync + 'static,
-        D: DataDescriptor<T, Self::DataStore> + Send,
-        Q: DataQuery<T, Self::DataStore> + Send,
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
     {
-        let table = descriptor.ident();
-
-        let mut writer = MySqlWriter::new(table, QueryKind::Select);
-        descriptor.write(&mut writer).unwrap();
+        let mut query = create(&descriptor);
+        self.table_options(&mut query);
+        query.strict();
 
-        writer.write_conditions = true;
-        query.write(&mut writer).unwrap();
+        self.execute_create(&mut query).await
+    }
 
-        let sql = writer.sql();
-        log::debug!("Executing sql SELECT query: \"{}\"", sql);
+    /// Creates the table of `T` like [`create`], with the counter of the `AUTO_INCREMENT`
+    /// column starting at `start`, e.g. to give the tables of shards disjoint id ranges.
+    ///
+    /// Nothing is changed if the table already exists. MySQL never sets the counter below the
+    /// largest stored id.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// store
+    ///     .create_with_auto_increment(store.descriptor::<Order>(), 100_000)
+    ///     .await?;
+    /// ```
+    ///
+    /// [`create`]: Store::create
+    pub async fn create_with_auto_increment<T, D>(
+        &self,
+        descriptor: D,
+        start: u64,
+    ) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let mut query = create(&descriptor);
+        self.table_options(&mut query);
+        query.auto_increment_start(start);
 
-        let row = match sqlx::query(&sql).fetch_one(&self.pool).await {
-            Ok(row) => row,
-            Err(sqlx::Error::RowNotFound) => return Ok(None),
-            Err(err) => return Err(Error(ErrorKind::Sqlx(err))),
-        };
+        self.execute_create(&mut query).await
+    }
 
-        let mut reader = MySqlReader::new(row);
-        let data = T::read(&mut reader).map_err(|err| Error(ErrorKind::Sqlx(err)))?;
+    /// Creates the table of `T` like [`create`], with a `UNIQUE KEY` for each pair of a name
+    /// and columns in `unique_keys`.
+    ///
+    /// The unique keys are part of the `CREATE TABLE` statement. Nothing is changed if the
+    /// table already exists, use [`create_index`] to add a unique index to an existing table.
+    /// Upserts update the existing row if any unique key matches, not only the primary key.
+    /// MySQL can't index `TEXT` columns without a prefix length, so the columns of a unique key
+    /// must have a bounded type like [`Varchar`].
+    /// Returns [`Error::InvalidUniqueKey`] without creating the table if a unique key has no
+    /// columns.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// store
+    ///     .create_with_unique_keys(
+    ///         store.descriptor::<User>(),
+    ///         &[("uq_tenant_email", &["tenant_id", "email"])],
+    ///     )
+    ///     .await?;
+    /// ```
+    ///
+    /// [`create`]: Store::create
+    /// [`create_index`]: Self::create_index
+    /// [`Varchar`]: crate::Varchar
+    pub async fn create_with_unique_keys<T, D>(
+        &self,
+        descriptor: D,
+        unique_keys: &[(&str, &[&str])],
+    ) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let mut query = create_with_unique_keys(&descriptor, unique_keys);
+        self.table_options(&mut query);
 
-        Ok(Some(data))
+        self.execute_create(&mut query).await
     }
 
-    async fn insert<T, D>(&self, descriptor: D, data: T) -> Result<(), Self::Error>
+    /// Creates the table of `T` like [`create`], with the `foreign_keys` referencing other
+    /// tables.
+    ///
+    /// The foreign keys are part of the `CREATE TABLE` statement, nothing is changed if the
+    /// table already exists. The referenced tables must exist and the referenced columns must
+    /// be indexed, e.g. by the primary key of the referenced table. Returns
+    /// [`Error::InvalidForeignKey`] without creating the table if a foreign key references a
+    /// column that is not a column of `T` or has a different number of columns and referenced
+    /// columns.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{ForeignKey, ReferentialAction};
+    ///
+    /// store.create(store.descriptor::<Order>()).await?;
+    /// store
+    ///     .create_with_foreign_keys(
+    ///         store.descriptor::<OrderLine>(),
+    ///         &[ForeignKey::new(&["order_id"], "order", &["id"]).on_delete(ReferentialAction::Cascade)],
+    ///     )
+    ///     .await?;
+    /// ```
+    ///
+    /// [`create`]: Store::create
+    pub async fn create_with_foreign_keys<T, D>(
+        &self,
+        descriptor: D,
+        foreign_keys: &[ForeignKey],
+    ) -> Result<(), Error>
     where
-        T: StoreData<Self::DataStore> + Send + Sync + 'static,
-        D: DataDescriptor<T, Self::DataStore> + Send,
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
     {
-        let table = descriptor.ident();
+        let mut query = create_with_foreign_keys(&descriptor, foreign_keys)?;
+        self.table_options(&mut query);
 
-        let mut writer = MySqlWriter::new(table, QueryKind::Insert);
-        data.write(&mut writer).unwrap();
+        self.execute_create(&mut query).await
+    }
 
-        let sql = writer.sql();
-        log::debug!("Executing sql INSERT query: \"{}\"", sql);
+    /// Creates the index `name` on the `columns` of the table of `T`. If `unique` is `true` a
+    /// `UNIQUE` index is created. Does nothing if an index named `name` already exists on the
+    /// table, even if it covers different columns. Use [`create_prefix_index`] to index `TEXT`
+    /// or `BLOB` columns.
+    ///
+    /// MySQL has no `CREATE INDEX IF NOT EXISTS`, the existing indexes are read from
+    /// `information_schema.statistics` instead. Creating the same index from multiple
+    /// connections at the same time may still fail with a duplicate key name error.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// store.create(store.descriptor::<Person>()).await?;
+    /// store
+    ///     .create_index(store.descriptor::<Person>(), "idx_name", &["name"], false)
+    ///     .await?;
+    /// ```
+    ///
+    /// [`create_prefix_index`]: Self::create_prefix_index
+    pub async fn create_index<T, D>(
+        &self,
+        descriptor: D,
+        name: &str,
+        columns: &[&str],
+        unique: bool,
+    ) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let columns: Vec<(&str, Option<u16>)> =
+            columns.iter().map(|column| (*column, None)).collect();
 
-        sqlx::query(&sql)
-            .execute(&self.pool)
+        self.create_prefix_index(descriptor, name, &columns, unique)
             .await
-            .map_err(|err| Error(ErrorKind::Sqlx(err)))?;
-        Ok(())
     }
-}
 
-#[derive(Debug)]
-struct MySqlWriter<'a> {
-    query: Query<'a>,
-    key: &'static str,
-    write_conditions: bool,
-}
+    /// Creates the index `name` on the `columns` of the table of `T` like [`create_index`].
+    /// Columns with a prefix length only index the first characters (or bytes for binary
+    /// columns) of the value.
+    ///
+    /// MySQL can only index `TEXT` and `BLOB` columns with a prefix length. A `UNIQUE` prefix
+    /// index only requires the prefixes to be unique.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// store
+    ///     .create_prefix_index(
+    ///         store.descriptor::<Post>(),
+    ///         "idx_author_body",
+    ///         &[("author", None), ("body", Some(255))],
+    ///         false,
+    ///     )
+    ///     .await?;
+    /// ```
+    ///
+    /// [`create_index`]: Self::create_index
+    pub async fn create_prefix_index<T, D>(
+        &self,
+        descriptor: D,
+        name: &str,
+        columns: &[(&str, Option<u16>)],
+        unique: bool,
+    ) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let mut query = Query::index_exists(descriptor.ident(), name.to_owned());
 
-impl<'a> MySqlWriter<'a> {
-    fn new(table: &'a str, kind: QueryKind) -> Self {
-        Self {
-            query: Query::new(table, kind),
-            key: "",
-            write_conditions: false,
+        let exists: i64 = fetch_scalar(&self.pool, self.options, &mut query).await?;
+        if exists != 0 {
+            return Ok(());
         }
-    }
 
-    fn sql(&self) -> String {
-        self.query.to_string()
+        let mut query = Query::create_index(
+            descriptor.ident(),
+            name.to_owned(),
+            columns
+                .iter()
+                .map(|(column, prefix_len)| (column.to_string(), *prefix_len))
+                .collect(),
+            unique,
+        );
+
+        execute(&self.pool, self.options, &mut query).await?;
+        Ok(())
     }
 
-    fn write<T>(&mut self, val: T) -> Result<(), <Self as Writer<MySqlStore>>::Error>
+    /// Drops the table of `T` and all its items. Does nothing if the table doesn't exist.
+    ///
+    /// This is the inverse of [`create`]. `DROP TABLE` implicitly commits any active
+    /// transaction of the connection, which is why `drop_table` is not available on a
+    /// [`Transaction`].
+    ///
+    /// [`create`]: Store::create
+    pub async fn drop_table<T, D>(&self, descriptor: D) -> Result<(), Error>
     where
-        T: ToString,
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
     {
-        if self.write_conditions {
-            self.query.push_condition(Condition::new(
-                self.key.to_owned(),
-                val.to_string(),
-                Comparator::Eq,
-            ));
-        } else {
-            self.query.push(self.key.to_owned(), val.to_string());
-        }
+        let mut query = Query::new(descriptor.ident(), QueryKind::Drop);
+
+        execute(&self.pool, self.options, &mut query).await?;
         Ok(())
     }
-}
 
-impl<'a> Writer<MySqlStore> for MySqlWriter<'a> {
-    type Error = Infallible;
+    /// Adds all columns of `T` that are missing from the existing table of `T`.
+    ///
+    /// This allows adding new fields to `T` after the table was created by [`create`]. The
+    /// existing columns are read from `information_schema.columns` and every field of `T`
+    /// without a column is added using `ALTER TABLE ... ADD COLUMN`. Does nothing if no column is
+    /// missing. Columns are never dropped, renamed or changed and the primary key is not
+    /// updated.
+    ///
+    /// Existing rows get the implicit default value of an added `NOT NULL` column (e.g. `0` or
+    /// an empty string) and `NULL` for an added `Option<T>` column.
+    ///
+    /// Note: `ALTER TABLE` implicitly commits any active transaction of the connection, which is
+    /// why `migrate` is not available on a [`Transaction`].
+    ///
+    /// [`create`]: Store::create
+    pub async fn migrate<T, D>(&self, descriptor: D) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let existing: Vec<String> = describe(&self.pool, self.options, descriptor.ident())
+            .await?
+            .into_iter()
+            .map(|column| column.name)
+            .collect();
+
+        let mut query = alter(&descriptor, &existing);
+        if !query.has_columns() {
+            return Ok(());
+        }
 
-    fn write_bool(&mut self, v: bool) -> Result<(), Self::Error> {
-        self.write(match v {
-            false => "FALSE",
-            true => "TRUE",
-        })
+        execute(&self.pool, self.options, &mut query).await?;
+        Ok(())
     }
 
-    fn write_i8(&mut self, v: i8) -> Result<(), Self::Error> {
-        self.write(v)
-    }
+    /// Checks that the existing table of `T` has a column with a compatible type for every
+    /// field of `T`.
+    ///
+    /// The existing columns are read from `information_schema.columns` and compared against
+    /// the column types [`create`] would use. Integer display widths, the collation, the
+    /// nullability and the letter case are ignored. `BOOLEAN` is compatible with
+    /// `TINYINT(1)`. Columns that don't belong to a field of `T` are ignored.
+    ///
+    /// Returns [`Error::SchemaMismatch`] for the first missing or incompatible column. This is
+    /// useful to fail fast at startup instead of on the first query after a failed migration.
+    ///
+    /// [`create`]: Store::create
+    pub async fn validate_schema<T, D>(&self, descriptor: D) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let existing = describe(&self.pool, self.options, descriptor.ident()).await?;
 
-    fn write_i16(&mut self, v: i16) -> Result<(), Self::Error> {
-        self.write(v)
+        validate(&descriptor, &existing)
     }
 
-    fn write_i32(&mut self, v: i32) -> Result<(), Self::Error> {
-        self.write(v)
+    /// Returns the existing columns of the table `table` as reported by MySQL, in the order of
+    /// the table.
+    ///
+    /// The columns are read from `information_schema.columns` of the current database. Unlike
+    /// [`validate_schema`] this doesn't need a type for the table, which is useful to inspect
+    /// a deployed schema from diagnostic tooling. Returns an empty `Vec` if the table doesn't
+    /// exist.
+    ///
+    /// [`validate_schema`]: Self::validate_schema
+    pub async fn describe_table(&self, table: &str) -> Result<Vec<ColumnInfo>, Error> {
+        describe(&self.read_pool, self.options, table).await
     }
 
-    fn write_i64(&mut self, v: i64) -> Result<(), Self::Error> {
-        self.write(v)
-    }
+    /// Deletes all items `T` from the store.
+    ///
+    /// Note: [`delete`] refuses to run with a query without any conditions. `delete_all` must be
+    /// used to delete all items instead.
+    ///
+    /// [`delete`]: Store::delete
+    pub async fn delete_all<T, D>(&self, descriptor: D) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let mut query = Query::new(descriptor.ident(), QueryKind::Delete);
 
-    fn write_u8(&mut self, v: u8) -> Result<(), Self::Error> {
-        self.write(v)
+        self.execute_write(&mut query).await?;
+        Ok(())
     }
 
-    fn write_u16(&mut self, v: u16) -> Result<(), Self::Error> {
-        self.write(v)
-    }
+    /// Deletes all items `T` matching the query `Q` and returns the number of deleted items.
+    /// Returns an error if the query has no conditions, use [`delete_all`] to delete all items.
+    ///
+    /// This takes precedence over [`Store::delete`], which can't return the number of deleted
+    /// items, like [`Transaction::delete`] and [`Session::delete`]. Code generic over [`Store`]
+    /// still calls [`Store::delete`].
+    ///
+    /// [`delete_all`]: Self::delete_all
+    pub async fn delete<T, D, Q>(&self, descriptor: D, query: Q) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = delete(&descriptor, &query);
 
-    fn write_u32(&mut self, v: u32) -> Result<(), Self::Error> {
-        self.write(v)
-    }
+        if !query.has_conditions() {
+            return Err(Error::EmptyDeleteConditions);
+        }
 
-    fn write_u64(&mut self, v: u64) -> Result<(), Self::Error> {
-        self.write(v)
+        let res = self.execute_write(&mut query).await?;
+        Ok(res.rows_affected())
     }
 
-    fn write_f32(&mut self, v: f32) -> Result<(), Self::Error> {
-        self.write(v)
-    }
+    /// Deletes all items `T` whose `key_column` is equal to any of the `ids` and returns the
+    /// number of deleted items. Returns `0` without querying the database if `ids` is empty.
+    ///
+    /// Like [`get_by_ids`] large lists of ids are split into multiple queries of at most 1000
+    /// ids each. The queries are not executed atomically, use a [`Transaction`] to delete either
+    /// all or none of the items.
+    ///
+    /// [`get_by_ids`]: Self::get_by_ids
+    pub async fn delete_by_ids<T, D, K>(
+        &self,
+        descriptor: D,
+        key_column: &str,
+        ids: &[K],
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+        K: Write<Self> + Sync,
+    {
+        let mut deleted = 0;
 
-    fn write_f64(&mut self, v: f64) -> Result<(), Self::Error> {
-        self.write(v)
-    }
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let mut query = delete_in(&descriptor, key_column, ids);
 
-    fn write_bytes(&mut self, v: &[u8]) -> Result<(), Self::Error> {
-        let mut string = String::with_capacity(2 * v.len() + "0x".len());
-        string.push_str("0x");
-        for byte in v {
-            let _ = write!(string, "{:02x}", byte);
+            deleted += self.execute_write(&mut query).await?.rows_affected();
         }
 
-        self.write(string)
+        Ok(deleted)
     }
 
-    fn write_str(&mut self, v: &str) -> Result<(), Self::Error> {
-        self.write(format!("'{}'", v.replace('\'', "\'")))
+    /// Deletes at most `limit` items `T` matching the query `Q` and returns the number of
+    /// deleted items. Returns an error if the query has no conditions.
+    ///
+    /// This allows deleting many items in batches without locking the table for a long time.
+    /// Call `delete_limited` until fewer than `limit` items are deleted.
+    ///
+    /// Note: The items deleted by each call are not ordered. Any `limit` matching items may be
+    /// deleted.
+    pub async fn delete_limited<T, D, Q>(
+        &self,
+        descriptor: D,
+        query: Q,
+        limit: u64,
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = delete_limited(&descriptor, &query, limit);
+
+        if !query.has_conditions() {
+            return Err(Error::EmptyDeleteConditions);
+        }
+
+        let res = self.execute_write(&mut query).await?;
+        Ok(res.rows_affected())
     }
 
-    fn write_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    /// Deletes the only item `T` matching the query `Q`. Returns an error if the query has no
+    /// conditions.
+    ///
+    /// Like [`get_exactly_one`] this fails with [`Error::NotFound`] if no item matches and with
+    /// [`Error::TooManyRows`] if more than one item matches, in which case nothing is deleted.
+    /// This guards against a mistyped condition deleting many items.
+    ///
+    /// The matching items are counted before the item is deleted within a single transaction.
+    /// The delete is limited to two rows and rolled back if more than one row was deleted, so
+    /// items matching concurrently are never deleted either. For tables of a non-transactional
+    /// engine, like MyISAM, only the first check applies.
+    ///
+    /// Note that the additional count makes `delete_one` slower than [`delete`].
+    ///
+    /// [`delete`]: Store::delete
+    /// [`get_exactly_one`]: Self::get_exactly_one
+    pub async fn delete_one<T, D, Q>(&self, descriptor: D, query: Q) -> Result<(), Error>
     where
-        T: ?Sized + Write<MySqlStore>,
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
     {
-        self.key = key;
-        value.write(self)
-    }
-}
+        let mut count = count(&descriptor, &query);
+        let mut query = delete_limited(&descriptor, &query, 2);
 
-impl<'a> TypeWriter<MySqlStore> for MySqlWriter<'a> {
-    type Error = Infallible;
+        if !query.has_conditions() {
+            return Err(Error::EmptyDeleteConditions);
+        }
 
-    fn write_bool(&mut self) -> Result<(), Self::Error> {
-        self.write("BOOLEAN")
-    }
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| acquire_error(err, "delete", Some(descriptor.ident())))?;
 
-    fn write_i8(&mut self) -> Result<(), Self::Error> {
-        self.write("TINYINT")
-    }
+        let matched: i64 = fetch_scalar(&mut tx, self.options, &mut count).await?;
+        check_exactly_one(matched as u64)?;
 
-    fn write_i16(&mut self) -> Result<(), Self::Error> {
-        self.write("SMALLINT")
+        let res = execute(&mut tx, self.options, &mut query).await?;
+        // Dropping the transaction rolls back the delete.
+        check_exactly_one(res.rows_affected())?;
+
+        tx.commit().await?;
+        Ok(())
     }
 
-    fn write_i32(&mut self) -> Result<(), Self::Error> {
-        self.write("INT")
+    /// Deletes all items `T` using `TRUNCATE TABLE`.
+    ///
+    /// Unlike [`delete_all`] this is faster for large tables and resets the `AUTO_INCREMENT`
+    /// counter of the table. `TRUNCATE TABLE` implicitly commits any active transaction of the
+    /// connection and can't be rolled back, which is why `truncate` is not available on a
+    /// [`Transaction`].
+    ///
+    /// [`delete_all`]: Self::delete_all
+    pub async fn truncate<T, D>(&self, descriptor: D) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let mut query = Query::new(descriptor.ident(), QueryKind::Truncate);
+
+        execute(&self.pool, self.options, &mut query).await?;
+        Ok(())
     }
 
-    fn write_i64(&mut self) -> Result<(), Self::Error> {
-        self.write("BIGINT")
+    /// Returns at most `limit` items `T` from the store, skipping the first `offset` items. If
+    /// no `limit` is given all remaining items after `offset` are returned.
+    ///
+    /// Note: There is no guarantee on the item order. Calling `get_page` for consecutive pages
+    /// might return overlapping items if the table has no defined order.
+    pub async fn get_page<T, D>(
+        &self,
+        descriptor: D,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let mut query = select_all(&descriptor);
+
+        if let Some(limit) = limit {
+            query.limit(limit);
+        }
+
+        if let Some(offset) = offset {
+            query.offset(offset);
+        }
+
+        if !query.children().is_empty() {
+            return self
+                .fetch_all_with_children(&primary_key(&descriptor), query, None)
+                .await;
+        }
+
+        fetch_all(&self.read_pool, self.options, &mut query).await
     }
 
-    fn write_u8(&mut self) -> Result<(), Self::Error> {
-        self.write("TINYINT UNSIGNED")
+    /// Returns at most `limit` items `T` whose `key_column` is greater than `last_value`,
+    /// ordered by `key_column`. If `last_value` is `None` the first items are returned.
+    ///
+    /// Unlike [`get_page`] this pages using the value of `key_column` instead of an offset, so
+    /// deep pages are as cheap as the first one and items inserted between calls don't shift
+    /// the pages. Pass the `key_column` value of the last returned item to fetch the next page.
+    /// Fewer than `limit` items are returned on the last page, an empty `Vec` once no more
+    /// items follow. `key_column` should be unique, items sharing a key with the last item of
+    /// a page are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut last_id = None;
+    /// loop {
+    ///     let persons: Vec<Person> = store
+    ///         .get_after(store.descriptor::<Person>(), "id", last_id.as_ref(), 100)
+    ///         .await?;
+    ///
+    ///     match persons.last() {
+    ///         Some(person) => last_id = Some(person.id),
+    ///         None => break,
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`get_page`]: Self::get_page
+    pub async fn get_after<T, D, V>(
+        &self,
+        descriptor: D,
+        key_column: &str,
+        last_value: Option<&V>,
+        limit: u64,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+        V: ?Sized + Write<Self> + Sync,
+    {
+        let mut query = select_after(&descriptor, key_column, last_value, limit);
+
+        if !query.children().is_empty() {
+            return self
+                .fetch_all_with_children(&primary_key(&descriptor), query, None)
+                .await;
+        }
+
+        fetch_all(&self.read_pool, self.options, &mut query).await
     }
 
-    fn write_u16(&mut self) -> Result<(), Self::Error> {
-        self.write("SMALLINT UNSIGNED")
+    /// Returns the first `limit` items `T` matching the query `Q`, ordered by `order_column`.
+    /// The items are returned in the requested order. Returns an empty `Vec` without querying
+    /// the database if `limit` is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{Filter, Order};
+    ///
+    /// // The 10 most recent posts of the user.
+    /// let posts: Vec<Post> = store
+    ///     .get_top(
+    ///         store.descriptor::<Post>(),
+    ///         Filter::new().eq("user_id", &user_id),
+    ///         "created_at",
+    ///         Order::Desc,
+    ///         10,
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn get_top<T, D, Q>(
+        &self,
+        descriptor: D,
+        query: Q,
+        order_column: &str,
+        order: Order,
+        limit: u64,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut query = select_top(&descriptor, &query, order_column, order, limit);
+
+        if !query.children().is_empty() {
+            return self
+                .fetch_all_with_children(&primary_key(&descriptor), query, None)
+                .await;
+        }
+
+        fetch_all(&self.read_pool, self.options, &mut query).await
     }
 
-    fn write_u32(&mut self) -> Result<(), Self::Error> {
-        self.write("INT UNSIGNED")
+    /// Returns the items `T` matching the query `Q`, ordered by all columns of `order_by`. Only
+    /// the first `limit` items are returned if `limit` is not `None`.
+    ///
+    /// Items that are equal in all columns of `order_by` are returned in an undefined order.
+    /// Add a unique column, e.g. the primary key, as the last column for a stable order.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{Filter, OrderBy};
+    ///
+    /// // Tasks with the latest due date first, tasks without a due date last.
+    /// let tasks: Vec<Task> = store
+    ///     .get_ordered(
+    ///         store.descriptor::<Task>(),
+    ///         Filter::new(),
+    ///         &[OrderBy::desc("due_at").nulls_last(), OrderBy::asc("id")],
+    ///         Some(50),
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn get_ordered<T, D, Q>(
+        &self,
+        descriptor: D,
+        query: Q,
+        order_by: &[OrderBy],
+        limit: Option<u64>,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        if limit == Some(0) {
+            return Ok(Vec::new());
+        }
+
+        let mut query = select_ordered(&descriptor, &query, order_by, limit);
+
+        if !query.children().is_empty() {
+            return self
+                .fetch_all_with_children(&primary_key(&descriptor), query, None)
+                .await;
+        }
+
+        fetch_all(&self.read_pool, self.options, &mut query).await
     }
 
-    fn write_u64(&mut self) -> Result<(), Self::Error> {
-        self.write("BIGINT UNSIGNED")
+    /// Returns all items `T` whose `key_column` is equal to any of the `ids`. Returns an empty
+    /// `Vec` without querying the database if `ids` is empty.
+    ///
+    /// Large lists of ids are split into multiple queries of at most 1000 ids each. The
+    /// returned items are not ordered and ids without a matching item are skipped.
+    pub async fn get_by_ids<T, D, K>(
+        &self,
+        descriptor: D,
+        key_column: &str,
+        ids: &[K],
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+        K: Write<Self> + Sync,
+    {
+        let mut entries = Vec::new();
+
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let mut query = select_in(&descriptor, key_column, ids);
+
+            if !query.children().is_empty() {
+                entries.extend(
+                    self.fetch_all_with_children::<T>(&primary_key(&descriptor), query, None)
+                        .await?,
+                );
+                continue;
+            }
+
+            entries.extend(fetch_all::<_, T>(&self.read_pool, self.options, &mut query).await?);
+        }
+
+        Ok(entries)
     }
 
-    fn write_f32(&mut self) -> Result<(), Self::Error> {
-        self.write("FLOAT")
+    /// Returns all items `T`, reading every row independently of the others.
+    ///
+    /// Unlike [`get_all`], which fails if any row can't be read, a row that fails to decode,
+    /// e.g. because of a corrupt value, is returned as an `Err` in place of its item and the
+    /// remaining rows are still read. Errors executing the query itself are returned as the
+    /// outer `Err`. Limits like [`max_rows`] apply like for [`get_all`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let rows = store.get_all_results(store.descriptor::<Person>()).await?;
+    ///
+    /// let persons: Vec<Person> = rows
+    ///     .into_iter()
+    ///     .filter_map(|row| match row {
+    ///         Ok(person) => Some(person),
+    ///         Err(err) => {
+    ///             tracing::warn!("Skipping corrupt row: {}", err);
+    ///             None
+    ///         }
+    ///     })
+    ///     .collect();
+    /// ```
+    ///
+    /// [`get_all`]: Store::get_all
+    /// [`max_rows`]: MySqlStoreConfig::max_rows
+    pub async fn get_all_results<T, D>(&self, descriptor: D) -> Result<Vec<Result<T, Error>>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let mut query = select_all(&descriptor);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&primary_key(&descriptor));
+        }
+
+        if !query.children().is_empty() {
+            let mut conn = self
+                .read_pool
+                .acquire()
+                .await
+                .map_err(|err| acquire_error(err, query.operation(), query.error_table()))?;
+            return fetch_all_with_children_results(
+                &mut conn,
+                self.options,
+                &primary_key(&descriptor),
+                query,
+                self.max_rows,
+            )
+            .await;
+        }
+
+        if let Some(max_rows) = self.max_rows {
+            query.limit(max_rows.saturating_add(1));
+        }
+
+        let rows = fetch_all_results(&self.read_pool, self.options, &mut query).await?;
+        if self
+            .max_rows
+            .is_some_and(|max_rows| rows.len() as u64 > max_rows)
+        {
+            return Err(Error::TooManyRows);
+        }
+
+        Ok(rows)
     }
 
-    fn write_f64(&mut self) -> Result<(), Self::Error> {
-        self.write("DOUBLE")
+    /// Returns all items `T`, only selecting the given `columns`.
+    ///
+    /// Fields of `T` whose column is not selected are read as `NULL`. These fields must be
+    /// `Option`s, reading any other field that is not selected returns an error. Names in
+    /// `columns` that are not a field of `T` are ignored.
+    pub async fn get_columns<T, D>(&self, descriptor: D, columns: &[&str]) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let mut query = select_columns(&descriptor, columns);
+
+        fetch_all(&self.read_pool, self.options, &mut query).await
     }
 
-    fn write_bytes(&mut self) -> Result<(), Self::Error> {
-        self.write("BLOB")
+    /// Returns all distinct items `T`, only selecting the given `columns`.
+    ///
+    /// Items are distinct if they differ in any of the selected `columns`. Fields of `T` whose
+    /// column is not selected are read as `NULL`, like in [`get_columns`].
+    ///
+    /// [`get_columns`]: Self::get_columns
+    pub async fn get_distinct<T, D>(&self, descriptor: D, columns: &[&str]) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let mut query = select_distinct(&descriptor, columns);
+
+        fetch_all(&self.read_pool, self.options, &mut query).await
     }
 
-    fn write_str(&mut self) -> Result<(), Self::Error> {
-        self.write("TEXT")
+    /// Returns the item `T` whose primary key is `key`, or `None` if no item has the key.
+    ///
+    /// The primary key is the single [`PrimaryKey`] field of `T`. Types without a primary key
+    /// or with a primary key of multiple columns return [`Error::NoPrimaryKey`], use
+    /// [`get_one`] with a [`Filter`] on all key columns instead.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let person: Option<Person> = store.get_by_key(store.descriptor::<Person>(), &3).await?;
+    /// ```
+    ///
+    /// [`PrimaryKey`]: crate::PrimaryKey
+    /// [`get_one`]: Store::get_one
+    pub async fn get_by_key<T, D, K>(&self, descriptor: D, key: &K) -> Result<Option<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        K: ?Sized + Write<Self>,
+    {
+        let filter = key_filter(&descriptor, key)?;
+
+        self.get_one(descriptor, filter).await
     }
 
-    fn write_field<T>(&mut self, key: &'static str) -> Result<(), Self::Error>
+    /// Returns all items `T` where `column` is equal to `value`. If `value` is `None` items where
+    /// `column` is `NULL` are returned.
+    ///
+    /// This is a shorthand for [`get`] with a [`Filter`] of a single [`Filter::eq`] condition.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let persons: Vec<Person> = store
+    ///     .get_where(store.descriptor::<Person>(), "name", "Robb")
+    ///     .await?;
+    /// ```
+    ///
+    /// [`get`]: Store::get
+    pub async fn get_where<T, D, V>(
+        &self,
+        descriptor: D,
+        column: &str,
+        value: &V,
+    ) -> Result<Vec<T>, Error>
     where
-        T: ?Sized + Write<MySqlStore>,
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        V: ?Sized + Write<Self>,
     {
-        self.key = key;
-        T::write_type(self)
+        self.get(descriptor, Filter::new().eq(column, value)).await
     }
-}
 
-struct MySqlTypeWriter<'a> {
-    query: Query<'a>,
-    key: &'static str,
-    write_conditions: bool,
-}
+    /// Returns an item `T` where `column` is equal to `value`, or `None` if no item matches.
+    /// See [`get_where`].
+    ///
+    /// [`get_where`]: Self::get_where
+    pub async fn get_one_where<T, D, V>(
+        &self,
+        descriptor: D,
+        column: &str,
+        value: &V,
+    ) -> Result<Option<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        V: ?Sized + Write<Self>,
+    {
+        self.get_one(descriptor, Filter::new().eq(column, value))
+            .await
+    }
 
-impl<'a> MySqlTypeWriter<'a> {
-    fn new(table: &'a str, kind: QueryKind) -> Self {
-        Self {
-            query: Query::new(table, kind),
-            key: "",
-            write_conditions: false,
+    /// Returns the only item `T` matching the query `Q`.
+    ///
+    /// Unlike [`get_one`] this fails with [`Error::NotFound`] if no item matches and with
+    /// [`Error::TooManyRows`] if more than one item matches.
+    ///
+    /// [`get_one`]: Store::get_one
+    pub async fn get_exactly_one<T, D, Q>(&self, descriptor: D, query: Q) -> Result<T, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let query = select(&descriptor, &query);
+
+        if !query.children().is_empty() {
+            let mut conn = self
+                .read_pool
+                .acquire()
+                .await
+                .map_err(|err| acquire_error(err, query.operation(), query.error_table()))?;
+            return fetch_exactly_one_with_children(
+                &mut conn,
+                self.options,
+                &primary_key(&descriptor),
+                query,
+            )
+            .await;
         }
+
+        fetch_exactly_one(&self.read_pool, self.options, query).await
     }
 
-    fn sql(&self) -> String {
-        self.query.to_string()
+    /// Calls `f` with the bytes of the binary `column` of all items `T` matching the query `Q`.
+    ///
+    /// Unlike reading a `Vec<u8>` field the bytes are borrowed from the returned row and not
+    /// copied, which avoids an allocation per row when the bytes are only hashed or streamed
+    /// elsewhere.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut hasher = Sha256::new();
+    /// store
+    ///     .get_bytes_with(store.descriptor::<File>(), query, "content", |bytes| {
+    ///         hasher.update(bytes);
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn get_bytes_with<T, D, Q, F>(
+        &self,
+        descriptor: D,
+        query: Q,
+        column: &str,
+        f: F,
+    ) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+        F: FnMut(&[u8]) + Send,
+    {
+        let mut query = select_bytes(&descriptor, &query, column);
+
+        fetch_bytes_with(&self.read_pool, self.options, &mut query, column, f).await
     }
 
-    fn write<T>(&mut self, value: T) -> Result<(), <Self as TypeWriter<MySqlStore>>::Error>
+    /// Calls `f` with the string of the text `column` of all items `T` matching the query `Q`.
+    ///
+    /// Like [`get_bytes_with`] the string is borrowed from the returned row and not copied into
+    /// a new `String` per row. Use this to scan large text columns, e.g. to count words.
+    ///
+    /// [`get_bytes_with`]: Self::get_bytes_with
+    pub async fn get_str_with<T, D, Q, F>(
+        &self,
+        descriptor: D,
+        query: Q,
+        column: &str,
+        f: F,
+    ) -> Result<(), Error>
     where
-        T: ToString,
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+        F: FnMut(&str) + Send,
     {
-        if !self.write_conditions {
-            self.query.push(self.key.to_owned(), value.to_string());
-        } else {
-            self.query.push_condition(Condition::new(
-                self.key.to_owned(),
-                value.to_string(),
-                Comparator::Eq,
-            ));
+        let mut query = select_bytes(&descriptor, &query, column);
+
+        fetch_str_with(&self.read_pool, self.options, &mut query, column, f).await
+    }
+
+    /// Returns a [`Stream`] of all items `T` matching the query `Q`.
+    ///
+    /// Unlike [`get`] the items are not collected into a `Vec`. Each item is read lazily when
+    /// the stream is polled.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut persons = Box::pin(store.get_stream(store.descriptor::<Person>(), query));
+    /// while let Some(person) = persons.try_next().await? {
+    ///     println!("{:?}", person);
+    /// }
+    /// ```
+    ///
+    /// [`get`]: Store::get
+    pub fn get_stream<T, D, Q>(
+        &self,
+        descriptor: D,
+        query: Q,
+    ) -> impl Stream<Item = Result<T, Error>> + Send + '_
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = select(&descriptor, &query);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&primary_key(&descriptor));
+        }
+        // The child rows are loaded for all returned items at once, which a stream can't do.
+        unsupported_children(&mut query, "Children field into a stream");
+
+        fetch_stream(&self.read_pool, self.options, &mut query)
+    }
+
+    /// Returns the number of items `T` matching the query `Q`. Returns `0` if no items match.
+    pub async fn count<T, D, Q>(&self, descriptor: D, query: Q) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = count(&descriptor, &query);
+
+        let count: i64 = fetch_scalar(&self.read_pool, self.options, &mut query).await?;
+        Ok(count as u64)
+    }
+
+    /// Returns the number of distinct values of `column` of all items `T` matching the query
+    /// `Q`, e.g. the number of unique users. `NULL` values are not counted. Returns `0` if no
+    /// items match.
+    pub async fn count_distinct<T, D, Q>(
+        &self,
+        descriptor: D,
+        column: &str,
+        query: Q,
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = count(&descriptor, &query);
+        query.count_distinct(column.to_owned());
+
+        let count: i64 = fetch_scalar(&self.read_pool, self.options, &mut query).await?;
+        Ok(count as u64)
+    }
+
+    /// Returns the number of all items `T`. Returns `0` if the store contains no items.
+    pub async fn count_all<T, D>(&self, descriptor: D) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let mut query = Query::new(descriptor.ident(), QueryKind::Count);
+
+        let count: i64 = fetch_scalar(&self.read_pool, self.options, &mut query).await?;
+        Ok(count as u64)
+    }
+
+    /// Returns the aggregate `func` of `column` for each distinct value of the `group_by`
+    /// column, over all items `T` matching the query `Q`.
+    ///
+    /// Each returned pair contains a value of `group_by` and the aggregate of that group. The
+    /// aggregate is `None` if `column` is `NULL` for all items in the group, except for
+    /// [`AggFunc::Count`] which returns `0`. The aggregate is calculated as a `DOUBLE`, which
+    /// requires MySQL 8.0.17 or newer.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{AggFunc, Filter};
+    ///
+    /// // SELECT day,CAST(SUM(amount) AS DOUBLE) FROM orders WHERE amount > 0 GROUP BY day
+    /// let totals: Vec<(String, Option<f64>)> = store
+    ///     .aggregate(
+    ///         store.descriptor::<Order>(),
+    ///         Filter::new().gt("amount", &0),
+    ///         AggFunc::Sum,
+    ///         "amount",
+    ///         "day",
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn aggregate<T, D, Q, K>(
+        &self,
+        descriptor: D,
+        query: Q,
+        func: AggFunc,
+        column: &str,
+        group_by: &str,
+    ) -> Result<Vec<(K, Option<f64>)>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+        K: Send + Unpin + for<'r> sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
+    {
+        let mut query = aggregate(&descriptor, &query, func, column, group_by);
+
+        fetch_as(&self.read_pool, self.options, &mut query).await
+    }
+
+    /// Returns `true` if any item `T` matches the query `Q`.
+    ///
+    /// This is cheaper than [`get_one`] because no item is read.
+    ///
+    /// [`get_one`]: Store::get_one
+    pub async fn exists<T, D, Q>(&self, descriptor: D, query: Q) -> Result<bool, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = exists(&descriptor, &query);
+
+        let exists: i64 = fetch_scalar(&self.read_pool, self.options, &mut query).await?;
+        Ok(exists != 0)
+    }
+
+    /// Inserts all items `T` into the store using a single multi-row `INSERT` query. Does
+    /// nothing if `data` is empty.
+    ///
+    /// Returns an error if the items don't write the same columns in the same order.
+    pub async fn insert_many<T, D>(&self, descriptor: D, data: Vec<T>) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let Some(mut query) = insert_rows(&descriptor, &data)? else {
+            return Ok(());
+        };
+
+        self.execute_write(&mut query).await?;
+        Ok(())
+    }
+
+    /// Inserts all items `T` into the store using one multi-row `INSERT` query per batch of
+    /// [`insert_batch_size`] items. All batches are inserted in a single transaction, if any
+    /// batch fails no items are inserted. Does nothing if `data` is empty.
+    ///
+    /// Unlike [`insert_many`] this can insert more items than fit into a single query, e.g.
+    /// when importing large files.
+    ///
+    /// Returns an error if the items don't write the same columns in the same order.
+    ///
+    /// [`insert_batch_size`]: MySqlStoreConfig::insert_batch_size
+    /// [`insert_many`]: Self::insert_many
+    pub async fn insert_batched<T, D>(&self, descriptor: D, data: Vec<T>) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        // Build and validate all queries first, an invalid item fails before anything is
+        // executed.
+        let mut queries = data
+            .chunks(self.insert_batch_size.max(1))
+            .map(|batch| insert_rows(&descriptor, batch))
+            .collect::<Result<Vec<_>, Error>>()?;
+        for query in queries.iter_mut().flatten() {
+            query.validate()?;
+        }
+
+        if queries.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| acquire_error(err, "insert", Some(descriptor.ident())))?;
+
+        for query in queries.iter_mut().flatten() {
+            execute(&mut tx, self.options, query).await?;
         }
+
+        // Dropping the transaction after an error rolls back all batches.
+        tx.commit().await?;
         Ok(())
     }
-}
 
-impl<'a> TypeWriter<MySqlStore> for MySqlTypeWriter<'a> {
-    type Error = Infallible;
+    /// Inserts a new item `T` and returns the id generated for the `AUTO_INCREMENT` column of
+    /// the table.
+    ///
+    /// The returned id is only meaningful if the table has an `AUTO_INCREMENT` column, `0` is
+    /// returned otherwise.
+    pub async fn insert_returning_id<T, D>(&self, descriptor: D, data: T) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let mut query = insert(&descriptor, &data);
+        unsupported_children(&mut query, "Children field into insert_returning_id");
+
+        let res = self.execute_write(&mut query).await?;
+        Ok(res.last_insert_id())
+    }
+
+    /// Inserts a new item `T`, or updates all columns of the existing item if an item with the
+    /// same primary or unique key already exists.
+    pub async fn upsert<T, D>(&self, descriptor: D, data: T) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let mut query = upsert(&descriptor, &data);
+
+        self.execute_write(&mut query).await?;
+        Ok(())
+    }
+
+    /// Inserts a new item `T`, replacing the existing item if an item with the same primary or
+    /// unique key already exists.
+    ///
+    /// Unlike [`upsert`], which updates the existing row, `REPLACE` deletes the existing row
+    /// before inserting the new one. All columns not written by `T` are reset to their
+    /// defaults, delete triggers fire and a new `AUTO_INCREMENT` id is generated.
+    ///
+    /// [`upsert`]: Self::upsert
+    pub async fn replace<T, D>(&self, descriptor: D, data: T) -> Result<(), Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let mut query = replace(&descriptor, &data);
+
+        if !query.children().is_empty() {
+            self.execute_insert_with_children(&primary_key(&descriptor), &mut query)
+                .await?;
+            return Ok(());
+        }
+
+        self.execute_write(&mut query).await?;
+        Ok(())
+    }
+
+    /// Saves the item `T`: Inserts it if no item with the same primary or unique key exists and
+    /// updates all columns of the existing item otherwise. Returns the id of the inserted or
+    /// updated item if the table has an `AUTO_INCREMENT` column, `0` otherwise.
+    ///
+    /// A new item is saved with an `AUTO_INCREMENT` key of `0`, for which the server generates
+    /// a new id. The existing item is found by the server within a single
+    /// `INSERT ... ON DUPLICATE KEY UPDATE` query like [`upsert`], without a separate
+    /// `SELECT`. Unlike [`upsert`] the `AUTO_INCREMENT` column of an existing item is never
+    /// updated, also if the item is found by a unique key.
+    ///
+    /// ```ignore
+    /// let mut user = User {
+    ///     id: PrimaryKey(AutoIncrement(0)),
+    ///     name: String::from("alice"),
+    /// };
+    ///
+    /// // Inserts the user.
+    /// user.id = PrimaryKey(AutoIncrement(store.save(UserDescriptor, user.clone()).await?));
+    ///
+    /// // Updates the user.
+    /// user.name = String::from("bob");
+    /// store.save(UserDescriptor, user).await?;
+    /// ```
+    ///
+    /// Note that an `AUTO_INCREMENT` key of `0` is stored as is if the `sql_mode` contains
+    /// `NO_AUTO_VALUE_ON_ZERO`.
+    ///
+    /// [`upsert`]: Self::upsert
+    pub async fn save<T, D>(&self, descriptor: D, data: T) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let mut query = save(&descriptor, &data);
+
+        let res = self.execute_write(&mut query).await?;
+        Ok(res.last_insert_id())
+    }
+
+    /// Inserts a new item `T` unless an item with the same primary or unique key already
+    /// exists and returns the number of inserted items, i.e. `0` if the item was skipped.
+    ///
+    /// Note that `INSERT IGNORE` not only skips duplicate keys: Other errors, like an out of
+    /// range number or a too long string, are downgraded to warnings and the truncated value
+    /// is stored, even if [`strict_sql_mode`] is enabled. Rows violating a foreign key are
+    /// skipped as well.
+    ///
+    /// [`strict_sql_mode`]: MySqlStoreConfig::strict_sql_mode
+    pub async fn insert_ignore<T, D>(&self, descriptor: D, data: T) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+    {
+        let mut query = insert_ignore(&descriptor, &data);
+
+        if !query.children().is_empty() {
+            return self
+                .execute_insert_with_children(&primary_key(&descriptor), &mut query)
+                .await;
+        }
+
+        let res = self.execute_write(&mut query).await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Updates all items `T` matching the query `Q` to `data` and returns the number of
+    /// matched items.
+    ///
+    /// Matched items are counted even if `data` equals their current values, so an optimistic
+    /// update succeeded iff `1` is returned.
+    pub async fn update<T, D, Q>(&self, descriptor: D, data: T, query: Q) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = update(&descriptor, &data, &query);
+
+        let res = self.execute_write(&mut query).await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Updates only the given `fields` of all items `T` matching the query `Q` and returns the
+    /// number of matched items. Each field is a pair of a column and its new value. All other
+    /// columns are left unchanged.
+    ///
+    /// Unlike [`update`] this doesn't require reading the item first, so concurrent updates of
+    /// other columns are not overwritten. Returns an error if `fields` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{Filter, Value};
+    ///
+    /// store
+    ///     .update_fields(
+    ///         store.descriptor::<Order>(),
+    ///         &[("status", Value::from("shipped"))],
+    ///         Filter::new().eq("id", &3),
+    ///     )
+    ///     .await?;
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    pub async fn update_fields<T, D, Q>(
+        &self,
+        descriptor: D,
+        fields: &[(&str, Value)],
+        query: Q,
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = update_fields(&descriptor, fields, &query);
+
+        let res = self.execute_write(&mut query).await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Updates the columns of all items `T` matching the query `Q` to the result of an
+    /// [`UpdateExpr`] and returns the number of matched items. Each expression is a pair of a
+    /// column and the expression calculating its new value. All other columns are left
+    /// unchanged. Returns an error if `exprs` is empty.
+    ///
+    /// The new values are calculated by the database, concurrent updates are never lost.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{Filter, UpdateExpr, Value};
+    ///
+    /// store
+    ///     .update_exprs(
+    ///         store.descriptor::<Post>(),
+    ///         &[("views", UpdateExpr::Increment(Value::from(1)))],
+    ///         Filter::new().eq("id", &3),
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn update_exprs<T, D, Q>(
+        &self,
+        descriptor: D,
+        exprs: &[(&str, UpdateExpr)],
+        query: Q,
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send,
+        Q: DataQuery<T, Self> + Send,
+    {
+        let mut query = update_exprs(&descriptor, exprs, &query);
+
+        let res = self.execute_write(&mut query).await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Returns all rows of `table` matching `filter` as maps from the name to the [`Value`] of
+    /// each column, for tables whose columns are not known at compile time, e.g. in an admin
+    /// panel.
+    ///
+    /// All columns of the table are selected. See [`Value`] for how the column types are
+    /// decoded. The table name must be a plain identifier, otherwise
+    /// [`Error::InvalidTableName`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::{Filter, Value};
+    ///
+    /// let rows = store.get_dynamic("person", Filter::new().gt("age", &30)).await?;
+    /// for row in rows {
+    ///     println!("{:?}", row.get("name"));
+    /// }
+    /// ```
+    pub async fn get_dynamic(
+        &self,
+        table: &str,
+        filter: Filter,
+    ) -> Result<Vec<HashMap<String, Value>>, Error> {
+        let mut query = select_dynamic(table, &filter)?;
+
+        fetch_dynamic(&self.read_pool, self.options, &mut query).await
+    }
+
+    /// Executes the raw `sql` and reads all returned rows into `T`.
+    ///
+    /// This allows queries that can't be expressed otherwise, e.g. using window functions or
+    /// common table expressions. The rows are read like the rows returned by [`get`], every
+    /// field of `T` is read from the column with the same name. Computed columns are read by
+    /// their alias, e.g. a field `total` from `COUNT(*) AS total`. Returns
+    /// [`Error::AmbiguousColumn`] if multiple returned columns have the same name.
+    ///
+    /// Every `?` in `sql` is replaced by one of the `binds`, in order. The query is executed on
+    /// the primary pool.
+    ///
+    /// # Security
+    ///
+    /// `sql` is sent to the database unchanged. Never build it by interpolating untrusted
+    /// input, this allows sql injection. Pass all values as `binds` instead, they are sent
+    /// separately from the sql.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::Value;
+    ///
+    /// let persons: Vec<Person> = store
+    ///     .query_raw(
+    ///         "WITH t AS (SELECT * FROM person WHERE age > ?) SELECT id, name FROM t",
+    ///         &[Value::from(30)],
+    ///     )
+    ///     .await?;
+    ///
+    /// #[derive(Debug, StoreData)]
+    /// pub struct StatusCount {
+    ///     status: String,
+    ///     total: i64,
+    /// }
+    ///
+    /// let counts: Vec<StatusCount> = store
+    ///     .query_raw("SELECT COUNT(*) AS total, status FROM t GROUP BY status", &[])
+    ///     .await?;
+    /// ```
+    ///
+    /// [`get`]: Store::get
+    pub async fn query_raw<T>(&self, sql: &str, binds: &[Value]) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+    {
+        let mut query = Query::raw(sql.to_owned(), binds.to_vec());
+
+        fetch_all(&self.pool, self.options, &mut query).await
+    }
+
+    /// Executes the raw `sql` and reads the columns of all returned rows into `T` in order.
+    ///
+    /// This is meant for tuples, e.g. the results of aggregates or joins that don't match a
+    /// [`StoreData`] type. Unlike [`query_raw`] the columns are read by position, the column
+    /// names are ignored. `binds` are bound like in [`query_raw`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let counts: Vec<(String, i64)> = store
+    ///     .query_raw_as("SELECT name, COUNT(*) FROM person GROUP BY name", &[])
+    ///     .await?;
+    /// ```
+    ///
+    /// [`query_raw`]: Self::query_raw
+    pub async fn query_raw_as<T>(&self, sql: &str, binds: &[Value]) -> Result<Vec<T>, Error>
+    where
+        T: Read<MySqlStore> + Send,
+    {
+        let mut query = Query::raw(sql.to_owned(), binds.to_vec());
+
+        fetch_all_as(&self.pool, self.options, &mut query).await
+    }
+
+    /// Executes the raw `sql` and returns the first column of the first returned row, e.g. for
+    /// `SELECT MAX(id) FROM person`. `binds` are bound like in [`query_raw`].
+    ///
+    /// Returns an error if the query returns no rows. Use an `Option<O>` for values that may be
+    /// `NULL`, e.g. the maximum of an empty table.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let max_id: Option<i64> = store
+    ///     .query_raw_scalar("SELECT MAX(id) FROM person WHERE age > ?", &[Value::from(30)])
+    ///     .await?;
+    /// ```
+    ///
+    /// [`query_raw`]: Self::query_raw
+    pub async fn query_raw_scalar<O>(&self, sql: &str, binds: &[Value]) -> Result<O, Error>
+    where
+        O: Send + Unpin + for<'r> sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
+    {
+        let mut query = Query::raw(sql.to_owned(), binds.to_vec());
+
+        fetch_scalar(&self.pool, self.options, &mut query).await
+    }
+
+    /// Executes the raw `sql` and returns the number of affected rows. See [`query_raw`].
+    ///
+    /// [`query_raw`]: Self::query_raw
+    pub async fn execute_raw(&self, sql: &str, binds: &[Value]) -> Result<u64, Error> {
+        let mut query = Query::raw(sql.to_owned(), binds.to_vec());
+
+        let res = execute(&self.pool, self.options, &mut query).await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Executes all statements of the sql `script`, e.g. a migration creating views or seeding
+    /// data that [`create`] can't express.
+    ///
+    /// The script is split into statements separated by `;`, ignoring `;` inside string
+    /// literals, quoted identifiers and comments. Use a `DELIMITER` line like in the `mysql`
+    /// client for statements containing `;`, e.g. stored procedures. The statements are
+    /// executed in order in a single transaction, which is rolled back if a statement fails.
+    ///
+    /// Note: Statements like `CREATE TABLE` or `ALTER TABLE` implicitly commit the transaction,
+    /// these and all statements before them are not rolled back.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// store
+    ///     .run_migration(include_str!("../migrations/0001_init.sql"))
+    ///     .await?;
+    /// ```
+    ///
+    /// [`create`]: Store::create
+    pub async fn run_migration(&self, script: &str) -> Result<(), Error> {
+        let mut tx = self.begin().await?;
+        tx.run_migration(script).await?;
+        tx.commit().await
+    }
+
+    /// Returns the statements executed by [`create`] without executing them, starting with the
+    /// `CREATE TABLE` of the parent table followed by the child tables of all [`Children`]
+    /// fields. Returns the error the operation would fail with if the item can't be created,
+    /// e.g. [`Error::NoPrimaryKey`] for `Children` fields of an item without a primary key.
+    ///
+    /// The `*_sql` methods build the same statements as the corresponding operations, which
+    /// makes them useful for debugging and snapshot tests. They don't access the database. The
+    /// values are written into the sql as escaped literals like in the log, the operations bind
+    /// them as parameters of prepared statements instead.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let person = Person {
+    ///     id: 1,
+    ///     name: String::from("Robb"),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     store.insert_sql(store.descriptor::<Person>(), &person)?,
+    ///     ["INSERT INTO person (id,name) VALUES (1,'Robb')"],
+    /// );
+    /// ```
+    ///
+    /// [`create`]: Store::create
+    /// [`Children`]: crate::Children
+    pub fn create_sql<T, D>(&self, descriptor: D) -> Result<Vec<String>, Error>
+    where
+        T: StoreData<Self>,
+        D: DataDescriptor<T, Self>,
+    {
+        let mut query = create(&descriptor);
+        self.table_options(&mut query);
+
+        let tables = child_tables(&query);
+        let mut queries = vec![query];
+        for mut child in create_children(&queries[0], &tables)? {
+            self.table_options(&mut child);
+            queries.push(child);
+        }
+
+        statements(queries)
+    }
+
+    /// Returns the statement executed by [`delete`] without executing it. See [`create_sql`].
+    ///
+    /// [`delete`]: Self::delete
+    /// [`create_sql`]: Self::create_sql
+    pub fn delete_sql<T, D, Q>(&self, descriptor: D, query: Q) -> Result<Vec<String>, Error>
+    where
+        T: StoreData<Self>,
+        D: DataDescriptor<T, Self>,
+        Q: DataQuery<T, Self>,
+    {
+        let query = delete(&descriptor, &query);
+        if !query.has_conditions() {
+            return Err(Error::EmptyDeleteConditions);
+        }
+
+        statements(vec![query])
+    }
+
+    /// Returns the statements executed by [`get`] without executing them, starting with the
+    /// `SELECT` of the items followed by the selects of the child rows of all [`Children`]
+    /// fields. The child rows are only selected if any items are returned. See [`create_sql`].
+    ///
+    /// [`get`]: Store::get
+    /// [`Children`]: crate::Children
+    /// [`create_sql`]: Self::create_sql
+    pub fn get_sql<T, D, Q>(&self, descriptor: D, query: Q) -> Result<Vec<String>, Error>
+    where
+        T: StoreData<Self>,
+        D: DataDescriptor<T, Self>,
+        Q: DataQuery<T, Self>,
+    {
+        let primary_key = primary_key(&descriptor);
+        let mut query = select(&descriptor, &query);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&primary_key);
+        }
+
+        if query.children().is_empty() {
+            return statements(vec![query]);
+        }
+
+        if primary_key.is_empty() {
+            return Err(Error::NoPrimaryKey);
+        }
+
+        let children = child_tables(&query)
+            .iter()
+            .map(|table| select_children(&query, &primary_key, table))
+            .collect::<Vec<Query<'_>>>();
+
+        statements(std::iter::once(query).chain(children).collect())
+    }
+
+    /// Returns the statements executed by [`insert`] without executing them, starting with the
+    /// `INSERT` of the item followed by the inserts of the child rows of all [`Children`]
+    /// fields. See [`create_sql`].
+    ///
+    /// [`insert`]: Store::insert
+    /// [`Children`]: crate::Children
+    /// [`create_sql`]: Self::create_sql
+    pub fn insert_sql<T, D>(&self, descriptor: D, data: &T) -> Result<Vec<String>, Error>
+    where
+        T: StoreData<Self>,
+        D: DataDescriptor<T, Self>,
+    {
+        let query = insert(&descriptor, data);
+
+        let tables = child_tables(&query);
+        let children = insert_children(&query, &primary_key(&descriptor), &tables)?;
+
+        statements(std::iter::once(query).chain(children).collect())
+    }
+
+    /// Returns the statement executed by [`update`] without executing it. See [`create_sql`].
+    ///
+    /// [`update`]: Self::update
+    /// [`create_sql`]: Self::create_sql
+    pub fn update_sql<T, D, Q>(
+        &self,
+        descriptor: D,
+        data: &T,
+        query: Q,
+    ) -> Result<Vec<String>, Error>
+    where
+        T: StoreData<Self>,
+        D: DataDescriptor<T, Self>,
+        Q: DataQuery<T, Self>,
+    {
+        statements(vec![update(&descriptor, data, &query)])
+    }
+}
+
+/// Validates the `queries` and returns their sql, see [`MySqlStore::create_sql`].
+fn statements(queries: Vec<Query<'_>>) -> Result<Vec<String>, Error> {
+    queries
+        .into_iter()
+        .map(|mut query| {
+            query.validate()?;
+            Ok(query.to_string())
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Store for MySqlStore {
+    type DataStore = Self;
+    type Error = Error;
+
+    async fn connect(uri: &str) -> Result<Self, Self::Error> {
+        Self::connect_with(uri, MySqlStoreConfig::default()).await
+    }
+
+    async fn create<T, D>(&self, descriptor: D) -> Result<(), Self::Error>
+    where
+        T: StoreData<Self> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self> + Send + Sync,
+    {
+        let mut query = create(&descriptor);
+        self.table_options(&mut query);
+
+        self.execute_create(&mut query).await
+    }
+
+    async fn delete<T, D, Q>(&self, descriptor: D, query: Q) -> Result<(), Self::Error>
+    where
+        T: StoreData<Self::DataStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self::DataStore> + Send,
+        Q: DataQuery<T, Self::DataStore> + Send,
+    {
+        MySqlStore::delete(self, descriptor, query).await?;
+        Ok(())
+    }
+
+    async fn get<T, D, Q>(&self, descriptor: D, query: Q) -> Result<Vec<T>, Self::Error>
+    where
+        T: StoreData<Self::DataStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self::DataStore> + Send,
+        Q: DataQuery<T, Self::DataStore> + Send,
+    {
+        let mut query = select(&descriptor, &query);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&primary_key(&descriptor));
+        }
+
+        if !query.children().is_empty() {
+            return self
+                .fetch_all_with_children(&primary_key(&descriptor), query, None)
+                .await;
+        }
+
+        fetch_all(&self.read_pool, self.options, &mut query).await
+    }
+
+    async fn get_all<T, D>(&self, descriptor: D) -> Result<Vec<T>, Self::Error>
+    where
+        T: StoreData<Self::DataStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self::DataStore> + Send + Sync,
+    {
+        let mut query = select_all(&descriptor);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&primary_key(&descriptor));
+        }
+
+        if !query.children().is_empty() {
+            return self
+                .fetch_all_with_children(&primary_key(&descriptor), query, self.max_rows)
+                .await;
+        }
+
+        fetch_all_max(&self.read_pool, self.options, query, self.max_rows).await
+    }
+
+    async fn get_one<T, D, Q>(&self, descriptor: D, query: Q) -> Result<Option<T>, Self::Error>
+    where
+        T: StoreData<Self::DataStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self::DataStore> + Send,
+        Q: DataQuery<T, Self::DataStore> + Send,
+    {
+        let mut query = select_one(&descriptor, &query);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&primary_key(&descriptor));
+        }
+
+        if !query.children().is_empty() {
+            let entries = self
+                .fetch_all_with_children(&primary_key(&descriptor), query, None)
+                .await?;
+            return Ok(entries.into_iter().next());
+        }
+
+        fetch_optional(&self.read_pool, self.options, &mut query).await
+    }
+
+    async fn insert<T, D>(&self, descriptor: D, data: T) -> Result<(), Self::Error>
+    where
+        T: StoreData<Self::DataStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, Self::DataStore> + Send,
+    {
+        let mut query = insert(&descriptor, &data);
+
+        if !query.children().is_empty() {
+            self.execute_insert_with_children(&primary_key(&descriptor), &mut query)
+                .await?;
+            return Ok(());
+        }
+
+        self.execute_write(&mut query).await?;
+        Ok(())
+    }
+}
+
+/// Builds the `ALTER TABLE` query adding all columns of `descriptor` that are not contained in
+/// `existing`.
+pub(crate) fn alter<'a, T, D>(descriptor: &'a D, existing: &[String]) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut writer = MySqlTypeWriter::new(descriptor.ident(), QueryKind::Alter);
+    descriptor.write(&mut writer).unwrap();
+    writer.query.remove_columns(existing);
+    writer.query
+}
+
+/// Checks that the `existing` columns contain a compatible column for every column of
+/// `descriptor`.
+pub(crate) fn validate<T, D>(descriptor: &D, existing: &[ColumnInfo]) -> Result<(), Error>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let query = create(descriptor);
+
+    for (column, expected) in query.column_types() {
+        let found = existing
+            .iter()
+            .find(|existing| existing.name.eq_ignore_ascii_case(column))
+            .map(|existing| &existing.column_type);
+
+        if found.map(|found| normalize_type(found)) != Some(normalize_type(expected)) {
+            return Err(Error::SchemaMismatch {
+                column: column.to_owned(),
+                expected: expected.to_owned(),
+                found: found.cloned(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Normalizes a column type for comparison, e.g. `INT NOT NULL` and `int(11)` both become
+/// `int`.
+fn normalize_type(column_type: &str) -> String {
+    let mut column_type = column_type.to_ascii_lowercase();
+
+    if let Some(index) = column_type.find(" check ") {
+        column_type.truncate(index);
+    }
+
+    if let Some(index) = column_type.find(" default ") {
+        column_type.truncate(index);
+    }
+
+    if let Some(stripped) = column_type.strip_suffix(" auto_increment") {
+        column_type.truncate(stripped.len());
+    }
+
+    for suffix in [" not null", " null"] {
+        if let Some(stripped) = column_type.strip_suffix(suffix) {
+            column_type.truncate(stripped.len());
+        }
+    }
+
+    if let Some(index) = column_type.find(" collate ") {
+        column_type.truncate(index);
+    }
+
+    // MySQL stores BOOLEAN as TINYINT(1).
+    if column_type == "boolean" || column_type == "bool" {
+        return String::from("tinyint");
+    }
+
+    // Integer types have an optional display width that doesn't affect the stored values.
+    for int in ["tinyint", "smallint", "mediumint", "bigint", "int", "year"] {
+        if let Some(rest) = column_type.strip_prefix(int) {
+            if let Some(rest) = rest.strip_prefix('(') {
+                if let Some(index) = rest.find(')') {
+                    return format!("{}{}", int, &rest[index + 1..]);
+                }
+            }
+        }
+    }
+
+    column_type
+}
+
+/// Builds the `GROUP BY` query selecting the aggregate `func` of `column` of all items matched
+/// by `query`.
+pub(crate) fn aggregate<'a, T, D, Q>(
+    descriptor: &'a D,
+    query: &Q,
+    func: AggFunc,
+    column: &str,
+    group_by: &str,
+) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut writer = MySqlWriter::from_query(Query::aggregate(
+        descriptor.ident(),
+        func,
+        column.to_owned(),
+        group_by.to_owned(),
+    ));
+    writer.write_conditions = true;
+    query.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the `SELECT COUNT(*)` query counting all items matched by `query`.
+pub(crate) fn count<'a, T, D, Q>(descriptor: &'a D, query: &Q) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut writer = MySqlWriter::new(descriptor.ident(), QueryKind::Count);
+    writer.write_conditions = true;
+    query.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the `SELECT EXISTS` query checking whether any item is matched by `query`.
+pub(crate) fn exists<'a, T, D, Q>(descriptor: &'a D, query: &Q) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut writer = MySqlWriter::new(descriptor.ident(), QueryKind::Exists);
+    writer.write_conditions = true;
+    query.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the `SELECT` query selecting the first item matched by `query`.
+pub(crate) fn select_one<'a, T, D, Q>(descriptor: &'a D, query: &Q) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut query = select(descriptor, query);
+    query.limit(1);
+    query
+}
+
+/// Builds the `CREATE` query for the table of `descriptor`.
+pub(crate) fn create<T, D>(descriptor: &D) -> Query<'_>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut writer = MySqlTypeWriter::new(descriptor.ident(), QueryKind::Create);
+    descriptor.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the `CREATE` query for the table of `descriptor` with the `unique_keys`.
+pub(crate) fn create_with_unique_keys<'a, T, D>(
+    descriptor: &'a D,
+    unique_keys: &[(&str, &[&str])],
+) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut query = create(descriptor);
+    for (name, columns) in unique_keys {
+        query.push_unique_key(
+            (*name).to_owned(),
+            columns.iter().map(|column| (*column).to_owned()).collect(),
+        );
+    }
+    query
+}
+
+/// Builds the `Create` query of `descriptor` with the `foreign_keys`. Returns
+/// [`Error::InvalidForeignKey`] if any foreign key is invalid.
+pub(crate) fn create_with_foreign_keys<'a, T, D>(
+    descriptor: &'a D,
+    foreign_keys: &[ForeignKey],
+) -> Result<Query<'a>, Error>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut query = create(descriptor);
+    for foreign_key in foreign_keys {
+        query.push_foreign_key(foreign_key.clone());
+    }
+
+    match query.invalid_foreign_key() {
+        Some(err) => Err(err),
+        None => Ok(query),
+    }
+}
+
+/// Returns the primary key columns of the table of `descriptor`.
+pub(crate) fn primary_key<T, D>(descriptor: &D) -> Vec<String>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    create(descriptor).primary_key().to_vec()
+}
+
+/// Returns the [`Filter`] matching the item of `descriptor` whose single primary key column is
+/// `key`. Returns [`Error::NoPrimaryKey`] if the table doesn't have a single primary key
+/// column.
+pub(crate) fn key_filter<T, D, K>(descriptor: &D, key: &K) -> Result<Filter, Error>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    K: ?Sized + Write<MySqlStore>,
+{
+    match primary_key(descriptor).as_slice() {
+        [column] => Ok(Filter::new().eq(column, key)),
+        _ => Err(Error::NoPrimaryKey),
+    }
+}
+
+/// Builds the `DELETE` query deleting all items matched by `query`.
+pub(crate) fn delete<'a, T, D, Q>(descriptor: &'a D, query: &Q) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut writer = MySqlWriter::new(descriptor.ident(), QueryKind::Delete);
+    writer.write_conditions = true;
+    query.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the `DELETE` query deleting at most `limit` items matched by `query`.
+pub(crate) fn delete_limited<'a, T, D, Q>(descriptor: &'a D, query: &Q, limit: u64) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut query = delete(descriptor, query);
+    query.limit(limit);
+    query
+}
+
+/// Builds the `INSERT` query inserting `data`.
+pub(crate) fn insert<'a, T, D>(descriptor: &'a D, data: &T) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut writer = MySqlWriter::new(descriptor.ident(), QueryKind::Insert);
+    data.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the multi-row `INSERT` query inserting all items of `data`. Returns `None` if `data`
+/// is empty and [`Error::ColumnMismatch`] if the items write different columns.
+pub(crate) fn insert_rows<'a, T, D>(
+    descriptor: &'a D,
+    data: &[T],
+) -> Result<Option<Query<'a>>, Error>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    // The child rows reference the parent by the key of the first row.
+    let mut rows = data.iter().map(|data| {
+        let mut row = insert(descriptor, data);
+        unsupported_children(&mut row, "Children field into a multi-row insert");
+        row
+    });
+
+    let Some(mut query) = rows.next() else {
+        return Ok(None);
+    };
+
+    for row in rows {
+        if !query.append(row) {
+            return Err(Error::ColumnMismatch);
+        }
+    }
+
+    Ok(Some(query))
+}
+
+/// Builds the `INSERT ... ON DUPLICATE KEY UPDATE` query upserting `data`.
+pub(crate) fn upsert<'a, T, D>(descriptor: &'a D, data: &T) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut query = insert(descriptor, data);
+    query.upsert();
+    // The existing child rows of an updated item would have to be replaced.
+    unsupported_children(&mut query, "Children field into an upsert");
+    query
+}
+
+/// Builds the `REPLACE` query replacing `data`.
+pub(crate) fn replace<'a, T, D>(descriptor: &'a D, data: &T) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut query = insert(descriptor, data);
+    query.replace();
+    query
+}
+
+/// Builds the upsert query saving `data` and returning the id of the inserted or updated item.
+pub(crate) fn save<'a, T, D>(descriptor: &'a D, data: &T) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut query = insert(descriptor, data);
+    query.upsert_returning_id();
+    // The existing child rows of an updated item would have to be replaced.
+    unsupported_children(&mut query, "Children field into an upsert");
+    query
+}
+
+/// Builds the `INSERT IGNORE` query inserting `data` unless its key already exists.
+pub(crate) fn insert_ignore<'a, T, D>(descriptor: &'a D, data: &T) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut query = insert(descriptor, data);
+    query.ignore();
+    query
+}
+
+/// Records the unsupported `operation` if `query` writes or reads [`Children`] fields, for
+/// operations that can't write or load the child rows.
+///
+/// [`Children`]: crate::Children
+pub(crate) fn unsupported_children(query: &mut Query<'_>, operation: &'static str) {
+    if !query.children().is_empty() {
+        query.set_unsupported(operation);
+    }
+}
+
+/// Returns the name of the child table storing the [`Children`] field `column` of `table`.
+///
+/// [`Children`]: crate::Children
+fn child_table(table: &str, column: &str) -> String {
+    format!("{}_{}", table, column)
+}
+
+/// Returns the names of the child tables of all [`Children`] fields written by `query`.
+///
+/// [`Children`]: crate::Children
+fn child_tables(query: &Query<'_>) -> Vec<String> {
+    query
+        .children()
+        .iter()
+        .map(|(column, _)| child_table(query.table, column))
+        .collect()
+}
+
+/// Returns the columns of a child table of `table` referencing the `primary_key` columns.
+fn child_keys(table: &str, primary_key: &[String]) -> Vec<String> {
+    primary_key
+        .iter()
+        .map(|column| format!("{}_{}", table, column))
+        .collect()
+}
+
+/// Builds the `CREATE` queries of the child tables `tables` of all [`Children`] fields of the
+/// `Create` query `parent`.
+///
+/// [`Children`]: crate::Children
+fn create_children<'a>(parent: &Query<'_>, tables: &'a [String]) -> Result<Vec<Query<'a>>, Error> {
+    if parent.children().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let primary_key = parent.primary_key();
+    if primary_key.is_empty() {
+        return Err(Error::NoPrimaryKey);
+    }
+
+    let keys = child_keys(parent.table, primary_key);
+
+    let mut queries = Vec::new();
+    for (table, (_, values)) in tables.iter().zip(parent.children()) {
+        let mut query = Query::new(table, QueryKind::Create);
+
+        for (key, column) in keys.iter().zip(primary_key) {
+            let column_type = parent
+                .column_types()
+                .find(|(name, _)| name == column)
+                .map(|(_, column_type)| column_type)
+                .unwrap_or_default();
+
+            query.push(key.clone(), column_type.to_owned());
+            query.push_primary_key(key.clone());
+        }
+
+        query.push(
+            CHILD_POSITION.to_owned(),
+            String::from("INT UNSIGNED NOT NULL"),
+        );
+        query.push_primary_key(CHILD_POSITION.to_owned());
+        query.push(CHILD_VALUE.to_owned(), values[0].clone());
+        query.push_foreign_key(ForeignKey {
+            columns: keys.clone(),
+            table: parent.table.to_owned(),
+            references: primary_key.to_vec(),
+            on_delete: Some(ReferentialAction::Cascade),
+        });
+
+        queries.push(query);
+    }
+
+    Ok(queries)
+}
+
+/// Builds the `INSERT` queries inserting the child rows of all [`Children`] fields of the
+/// `Insert` query `parent` into `tables`. `primary_key` contains the primary key columns of the
+/// parent table. Primary key columns that are not written by `parent` are assumed to be
+/// generated by the database and use `LAST_INSERT_ID()`.
+///
+/// [`Children`]: crate::Children
+fn insert_children<'a>(
+    parent: &Query<'_>,
+    primary_key: &[String],
+    tables: &'a [String],
+) -> Result<Vec<Query<'a>>, Error> {
+    if parent.children().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if primary_key.is_empty() {
+        return Err(Error::NoPrimaryKey);
+    }
+
+    let keys = child_keys(parent.table, primary_key);
+
+    let mut queries = Vec::new();
+    for (table, (_, values)) in tables.iter().zip(parent.children()) {
+        let mut rows = values.iter().enumerate().map(|(position, value)| {
+            let mut row = Query::new(table, QueryKind::Insert);
+
+            for (key, column) in keys.iter().zip(primary_key) {
+                let key_value = parent.value(column).unwrap_or("LAST_INSERT_ID()");
+                row.push(key.clone(), key_value.to_owned());
+            }
+
+            row.push(CHILD_POSITION.to_owned(), position.to_string());
+            row.push(CHILD_VALUE.to_owned(), value.clone());
+            row
+        });
+
+        // Don't insert anything for an empty `Children`.
+        let Some(mut query) = rows.next() else {
+            continue;
+        };
+
+        for row in rows {
+            query.append(row);
+        }
+
+        queries.push(query);
+    }
+
+    Ok(queries)
+}
+
+/// Builds the query selecting the rows of the child table `table` of all items selected by
+/// the `Select` query `parent`, ordered by the parent row and position. `primary_key` contains
+/// the primary key columns of the parent table.
+fn select_children(parent: &Query<'_>, primary_key: &[String], table: &str) -> Query<'static> {
+    let dialect = MySqlDialect;
+    let keys = dialect::join_idents(&dialect, &child_keys(parent.table, primary_key));
+
+    // MySQL doesn't support `LIMIT` in an `IN` subquery, but in a derived table within it.
+    let sql = format!(
+        "SELECT {},{} FROM {} WHERE ({}) IN (SELECT {} FROM ({}) AS parent) ORDER BY {},{}",
+        keys,
+        CHILD_VALUE,
+        dialect.quote_ident(table),
+        keys,
+        dialect::join_idents(&dialect, primary_key),
+        parent,
+        keys,
+        CHILD_POSITION,
+    );
+
+    Query::raw(sql, Vec::new())
+}
+
+/// Builds the `SELECT` query selecting all items matched by `query`.
+pub(crate) fn select<'a, T, D, Q>(descriptor: &'a D, query: &Q) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    // The column list is written by the `TypeWriter`, the `Writer` would check the range hints
+    // of `Bounded` columns against the type names.
+    let mut writer = MySqlWriter::from_query(select_all(descriptor));
+    writer.write_conditions = true;
+    query.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the `SELECT` query selecting all items.
+pub(crate) fn select_all<T, D>(descriptor: &D) -> Query<'_>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut writer = MySqlTypeWriter::new(descriptor.ident(), QueryKind::Select);
+    descriptor.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the `SELECT` query selecting at most `limit` items ordered by `key_column`, starting
+/// after `last_value`.
+pub(crate) fn select_after<'a, T, D, V>(
+    descriptor: &'a D,
+    key_column: &str,
+    last_value: Option<&V>,
+    limit: u64,
+) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    V: ?Sized + Write<MySqlStore>,
+{
+    let mut query = select_all(descriptor);
+
+    if let Some(value) = last_value {
+        query.push_condition(Condition::new(
+            key_column.to_owned(),
+            format_value(value),
+            Comparator::Gt,
+        ));
+    }
+
+    query.order_by(key_column.to_owned(), Order::Asc);
+    query.limit(limit);
+    query
+}
+
+/// Builds the `SELECT` query selecting the first `limit` items matched by `query`, ordered by
+/// `order_column`.
+pub(crate) fn select_top<'a, T, D, Q>(
+    descriptor: &'a D,
+    query: &Q,
+    order_column: &str,
+    order: Order,
+    limit: u64,
+) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut query = select(descriptor, query);
+    query.order_by(order_column.to_owned(), order);
+    query.limit(limit);
+    query
+}
+
+/// Builds the `SELECT` query selecting the items matched by `query`, ordered by all columns of
+/// `order_by`.
+pub(crate) fn select_ordered<'a, T, D, Q>(
+    descriptor: &'a D,
+    query: &Q,
+    order_by: &[OrderBy],
+    limit: Option<u64>,
+) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut query = select(descriptor, query);
+    for order_by in order_by {
+        query.push_order_by(order_by.clone());
+    }
+
+    if let Some(limit) = limit {
+        query.limit(limit);
+    }
+
+    query
+}
+
+/// Builds the `SELECT` query selecting all items whose `key_column` is any of the `ids`.
+pub(crate) fn select_in<'a, T, D, K>(descriptor: &'a D, key_column: &str, ids: &[K]) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    K: Write<MySqlStore>,
+{
+    let mut query = select_all(descriptor);
+    query.push_condition(Condition::in_list(
+        key_column.to_owned(),
+        ids.iter().map(format_value).collect(),
+    ));
+    query
+}
+
+/// Builds the `DELETE` query deleting all items whose `key_column` is any of the `ids`.
+pub(crate) fn delete_in<'a, T, D, K>(descriptor: &'a D, key_column: &str, ids: &[K]) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    K: Write<MySqlStore>,
+{
+    let mut query = Query::new(descriptor.ident(), QueryKind::Delete);
+    query.push_condition(Condition::in_list(
+        key_column.to_owned(),
+        ids.iter().map(format_value).collect(),
+    ));
+    query
+}
+
+/// Builds the `SELECT` query selecting the `columns` of all items.
+pub(crate) fn select_columns<'a, T, D>(descriptor: &'a D, columns: &[&str]) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut query = select_all(descriptor);
+    query.retain_columns(columns);
+    // The child rows are matched to their parent by the primary key, which may not be selected.
+    unsupported_children(&mut query, "Children field into a column selection");
+    query
+}
+
+/// Builds the `SELECT` query selecting all columns of the rows of `table` matching `filter`.
+pub(crate) fn select_dynamic<'a>(table: &'a str, filter: &Filter) -> Result<Query<'a>, Error> {
+    table::validate_table_name(table)?;
+
+    let mut query = Query::new(table, QueryKind::Select);
+    query.push_conditions(filter.conditions().clone());
+    Ok(query)
+}
+
+/// Builds the `SELECT` query selecting only `column` of all items matched by `query`, used to
+/// read the borrowed bytes or string of the column.
+pub(crate) fn select_bytes<'a, T, D, Q>(descriptor: &'a D, query: &Q, column: &str) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut query = select(descriptor, query);
+    query.retain_columns(&[column]);
+    query
+}
+
+/// Builds the `SELECT DISTINCT` query selecting the distinct values of `columns` of all items.
+pub(crate) fn select_distinct<'a, T, D>(descriptor: &'a D, columns: &[&str]) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    let mut query = select_columns(descriptor, columns);
+    query.distinct();
+    query
+}
+
+/// Builds the `UPDATE` query setting all items matched by `query` to `data`.
+pub(crate) fn update<'a, T, D, Q>(descriptor: &'a D, data: &T, query: &Q) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut writer = MySqlWriter::new(descriptor.ident(), QueryKind::Update);
+    data.write(&mut writer).unwrap();
+
+    writer.write_conditions = true;
+    query.write(&mut writer).unwrap();
+
+    // The child rows of all matched items would have to be replaced.
+    unsupported_children(&mut writer.query, "Children field into an update");
+    writer.query
+}
+
+/// Builds the `UPDATE` query setting only the `fields` of all items matched by `query`.
+pub(crate) fn update_fields<'a, T, D, Q>(
+    descriptor: &'a D,
+    fields: &[(&str, Value)],
+    query: &Q,
+) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut writer = MySqlWriter::new(descriptor.ident(), QueryKind::Update);
+
+    for (column, value) in fields {
+        writer.query.push((*column).to_owned(), value.to_literal());
+    }
+
+    writer.write_conditions = true;
+    query.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Builds the `UPDATE` query setting the columns of `exprs` of all items matched by `query`.
+pub(crate) fn update_exprs<'a, T, D, Q>(
+    descriptor: &'a D,
+    exprs: &[(&str, UpdateExpr)],
+    query: &Q,
+) -> Query<'a>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+    Q: DataQuery<T, MySqlStore>,
+{
+    let mut writer = MySqlWriter::new(descriptor.ident(), QueryKind::Update);
+
+    for (column, expr) in exprs {
+        writer.query.push((*column).to_owned(), expr.to_sql(column));
+    }
+
+    writer.write_conditions = true;
+    query.write(&mut writer).unwrap();
+    writer.query
+}
+
+/// Formats `value` as a sql literal.
+pub(crate) fn format_value<V>(value: &V) -> String
+where
+    V: ?Sized + Write<MySqlStore>,
+{
+    let mut writer = MySqlWriter::new("", QueryKind::Insert);
+    value.write(&mut writer).unwrap();
+    writer.query.into_values().join(",")
+}
+
+/// Calls `f` until it succeeds, returns an error that is not `is_transient` or failed
+/// `max_attempts` times.
+async fn retry<F, Fut, T>(
+    max_attempts: u32,
+    base_delay: Duration,
+    is_transient: fn(&Error) -> bool,
+    mut f: F,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        let err = match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+
+        if !is_transient(&err) || attempt >= max_attempts {
+            return Err(err);
+        }
+
+        let delay = base_delay.saturating_mul(2_u32.saturating_pow(attempt - 1));
+        log::warn!(
+            "Attempt {}/{} failed: {}, retrying in {:?}",
+            attempt,
+            max_attempts,
+            err,
+            delay
+        );
+
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Returns `true` if connecting failed because of an I/O error or pool timeout.
+fn is_connection_error(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::Sqlx(sqlx::Error::Io(_))
+            | Error::Sqlx(sqlx::Error::PoolTimedOut)
+            | Error::PoolExhausted { .. }
+    )
+}
+
+/// Returns `true` if a query failed because of a deadlock (`1213`) or lock wait timeout
+/// (`1205`).
+fn is_lock_error(err: &Error) -> bool {
+    match err {
+        Error::Query {
+            source: sqlx::Error::Database(err),
+            ..
+        } => err
+            .try_downcast_ref::<MySqlDatabaseError>()
+            .is_some_and(|err| matches!(err.number(), 1205 | 1213)),
+        _ => false,
+    }
+}
+
+/// Awaits `f` executing the `sql` of `query`, logging the executed sql. Errors returned by
+/// sqlx are wrapped in [`Error::Query`] containing the `sql`. The caller validates `query`
+/// before, see [`Query::validate`].
+///
+/// With the `tracing` feature `f` runs in a span named after the operation and the elapsed
+/// time is recorded once `f` completes.
+async fn instrument<F, T>(
+    options: QueryOptions,
+    query: &Query<'_>,
+    sql: &str,
+    f: F,
+) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let f = async {
+        f.await.map_err(|err| {
+            with_sql(
+                err,
+                query.operation(),
+                query.error_table(),
+                &options.visible_sql(sql),
+            )
+        })
+    };
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        log_sql(options, query, sql);
+        f.await
+    }
+
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        async move {
+            log_sql(options, query, sql);
+
+            let start = std::time::Instant::now();
+            let res = f.await;
+
+            tracing::debug!(elapsed = ?start.elapsed(), "Finished sql query");
+            res
+        }
+        .instrument(span(query))
+        .await
+    }
+}
+
+/// Logs the `sql` of `query` at the log level of `options`.
+fn log_sql(options: QueryOptions, query: &Query<'_>, sql: &str) {
+    let Some(level) = options.log_level else {
+        return;
+    };
+
+    let sql = options.visible_sql(sql);
+    let sql = sql.as_ref();
+
+    #[cfg(not(feature = "tracing"))]
+    log::log!(level, "Executing sql {} query: \"{}\"", query.kind(), sql);
+
+    #[cfg(feature = "tracing")]
+    {
+        // The level of tracing events must be a constant.
+        let kind = query.kind();
+        match level {
+            log::Level::Error => tracing::error!(kind, sql, "Executing sql query"),
+            log::Level::Warn => tracing::warn!(kind, sql, "Executing sql query"),
+            log::Level::Info => tracing::info!(kind, sql, "Executing sql query"),
+            log::Level::Debug => tracing::debug!(kind, sql, "Executing sql query"),
+            log::Level::Trace => tracing::trace!(kind, sql, "Executing sql query"),
+        }
+    }
+}
+
+/// Splits the sql `script` into its statements. Statements are separated by `;`, except inside
+/// string literals, quoted identifiers and comments. Like in the `mysql` client a `DELIMITER`
+/// line changes the separator, e.g. to define a stored procedure whose body contains `;`.
+/// Statements that are empty or only contain comments are skipped.
+// `Option::is_none_or` requires Rust 1.82.
+#[allow(clippy::unnecessary_map_or)]
+pub(crate) fn split_statements(script: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut delimiter = ";";
+    // The start of the current statement and whether it contains more than comments.
+    let mut start = 0;
+    let mut has_content = false;
+    let mut chars = script.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            _ if script[index..].starts_with(delimiter) => {
+                if has_content {
+                    statements.push(script[start..index].trim());
+                }
+
+                for _ in 1..delimiter.chars().count() {
+                    chars.next();
+                }
+                start = index + delimiter.len();
+                has_content = false;
+            }
+            // `DELIMITER` is a command of the client, it is not sent to the server.
+            _ if !has_content
+                && script
+                    .get(index..index + "delimiter ".len())
+                    .is_some_and(|s| s.eq_ignore_ascii_case("delimiter ")) =>
+            {
+                let end = script[index..]
+                    .find('\n')
+                    .map_or(script.len(), |n| index + n);
+                let new = script[index + "delimiter ".len()..end].trim();
+                if !new.is_empty() {
+                    delimiter = new;
+                }
+
+                while chars.peek().is_some_and(|(index, _)| *index < end) {
+                    chars.next();
+                }
+                start = end;
+            }
+            '\'' | '"' | '`' => {
+                while let Some((_, next)) = chars.next() {
+                    match next {
+                        '\\' if c != '`' => {
+                            chars.next();
+                        }
+                        // A quote is escaped by doubling it.
+                        next if next == c && chars.peek().is_some_and(|(_, n)| *n == c) => {
+                            chars.next();
+                        }
+                        next if next == c => break,
+                        _ => (),
+                    }
+                }
+
+                has_content = true;
+            }
+            '#' => skip_line(&mut chars),
+            '-' if script[index..].starts_with("--")
+                && script[index + 2..]
+                    .chars()
+                    .next()
+                    .map_or(true, char::is_whitespace) =>
+            {
+                skip_line(&mut chars)
+            }
+            '/' if script[index..].starts_with("/*") => {
+                // `/*! ... */` comments are executed by MySQL.
+                if script[index..].starts_with("/*!") {
+                    has_content = true;
+                }
+
+                chars.next();
+                while let Some((_, c)) = chars.next() {
+                    if c == '*' && chars.peek().is_some_and(|(_, c)| *c == '/') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => (),
+            _ => has_content = true,
+        }
+    }
+
+    if has_content {
+        statements.push(script[start..].trim());
+    }
+
+    statements
+}
+
+/// Skips all characters until the end of the line.
+fn skip_line<I>(chars: &mut std::iter::Peekable<I>)
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    while chars.peek().is_some_and(|(_, c)| *c != '\n') {
+        chars.next();
+    }
+}
+
+/// Replaces all string, number and binary literals in `sql` with `?`.
+fn redact(sql: &str) -> String {
+    let mut redacted = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    // Whether the previous character is part of an identifier or keyword.
+    let mut in_word = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        // A quote is escaped by doubling it.
+                        '\'' if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                        }
+                        '\'' => break,
+                        _ => (),
+                    }
+                }
+
+                redacted.push('?');
+                in_word = false;
+            }
+            '`' => {
+                redacted.push(c);
+                for c in chars.by_ref() {
+                    redacted.push(c);
+                    if c == '`' {
+                        break;
+                    }
+                }
+
+                in_word = false;
+            }
+            // Numbers and hexadecimal `0x` literals.
+            c if c.is_ascii_digit() && !in_word => {
+                while chars
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '.')
+                {
+                    chars.next();
+                }
+
+                redacted.push('?');
+            }
+            // The prefix of binary `b'...'` and hexadecimal `x'...'` literals.
+            'b' | 'B' | 'x' | 'X' if !in_word && chars.peek() == Some(&'\'') => (),
+            c => {
+                redacted.push(c);
+                in_word = c.is_alphanumeric() || c == '_';
+            }
+        }
+    }
+
+    redacted
+}
+
+/// Wraps an [`Error::Sqlx`] returned while executing `sql` on `table` in an [`Error::Query`].
+/// A pool timeout is returned as [`Error::PoolExhausted`] of `operation` and a closed pool as
+/// [`Error::StoreClosed`] instead. Other errors are returned unchanged.
+fn with_sql(err: Error, operation: &'static str, table: Option<&str>, sql: &str) -> Error {
+    match err {
+        Error::Sqlx(sqlx::Error::PoolTimedOut) => pool_exhausted(operation, table),
+        Error::Sqlx(sqlx::Error::PoolClosed) => Error::StoreClosed,
+        Error::Sqlx(source) => Error::Query {
+            sql: sql.to_owned(),
+            table: table.map(str::to_owned),
+            source,
+        },
+        err => err,
+    }
+}
+
+/// Returns the [`Error::PoolExhausted`] of `operation` on `table`.
+fn pool_exhausted(operation: &'static str, table: Option<&str>) -> Error {
+    Error::PoolExhausted {
+        operation,
+        table: table.map(str::to_owned),
+    }
+}
+
+/// Maps an error acquiring a connection for `operation` on `table`. A pool timeout is
+/// returned as [`Error::PoolExhausted`].
+fn acquire_error(err: sqlx::Error, operation: &'static str, table: Option<&str>) -> Error {
+    match err {
+        sqlx::Error::PoolTimedOut => pool_exhausted(operation, table),
+        err => err.into(),
+    }
+}
+
+/// Returns the span of the operation executed by `query`.
+#[cfg(feature = "tracing")]
+fn span(query: &Query<'_>) -> tracing::Span {
+    let table = query.table;
+
+    match query.inner {
+        QueryInner::Aggregate { .. } => tracing::debug_span!("aggregate", table),
+        QueryInner::Alter { .. } => tracing::debug_span!("alter", table),
+        QueryInner::Count { .. } => tracing::debug_span!("count", table),
+        QueryInner::Create { .. } => tracing::debug_span!("create", table),
+        QueryInner::CreateIndex { .. } => tracing::debug_span!("create_index", table),
+        QueryInner::Delete { .. } => tracing::debug_span!("delete", table),
+        QueryInner::Describe => tracing::debug_span!("describe", table),
+        QueryInner::Drop => tracing::debug_span!("drop", table),
+        QueryInner::Exists { .. } => tracing::debug_span!("exists", table),
+        QueryInner::IndexExists { .. } => tracing::debug_span!("index_exists", table),
+        QueryInner::Insert { .. } => tracing::debug_span!("insert", table),
+        QueryInner::Raw { .. } => tracing::debug_span!("raw"),
+        QueryInner::Select { .. } => tracing::debug_span!("select", table),
+        QueryInner::Truncate => tracing::debug_span!("truncate", table),
+        QueryInner::Update { .. } => tracing::debug_span!("update", table),
+    }
+}
+
+/// Awaits `f`, returning [`Error::Timeout`] if `f` doesn't complete within `timeout`.
+async fn with_timeout<F, T>(timeout: Option<Duration>, f: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, f).await {
+            Ok(res) => res,
+            Err(_) => Err(Error::Timeout),
+        },
+        None => f.await,
+    }
+}
+
+/// Executes `query` on `executor` as a prepared statement, see [`bind`].
+pub(crate) async fn execute<'c, E>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<MySqlQueryResult, Error>
+where
+    E: Executor<'c, Database = MySql>,
+{
+    query.validate()?;
+    execute_validated(executor, options, query).await
+}
+
+/// Like [`execute`], but `query` was already validated. This allows executing the same query
+/// more than once, e.g. to retry it.
+async fn execute_validated<'c, E>(
+    executor: E,
+    options: QueryOptions,
+    query: &Query<'_>,
+) -> Result<MySqlQueryResult, Error>
+where
+    E: Executor<'c, Database = MySql>,
+{
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let f = instrument(options, query, &sql, async {
+        bind(&prepared, &binds)
+            .execute(executor)
+            .await
+            .map_err(Error::from)
+    });
+
+    with_timeout(options.timeout, f).await
+}
+
+/// Executes the `Select` query on `executor` and reads all returned rows into `T`. Returns
+/// [`Error::TooManyRows`] if the query returns more than `max_rows` rows.
+pub(crate) async fn fetch_all_max<'c, E, T>(
+    executor: E,
+    options: QueryOptions,
+    mut query: Query<'_>,
+    max_rows: Option<u64>,
+) -> Result<Vec<T>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+    T: StoreData<MySqlStore>,
+{
+    let Some(max_rows) = max_rows else {
+        return fetch_all(executor, options, &mut query).await;
+    };
+
+    // Fetch a single row more than allowed to detect that the limit is exceeded without
+    // reading the whole table.
+    query.limit(max_rows.saturating_add(1));
+
+    let rows = fetch_all(executor, options, &mut query).await?;
+    if rows.len() as u64 > max_rows {
+        return Err(Error::TooManyRows);
+    }
+
+    Ok(rows)
+}
+
+/// Executes the `Select` query on `executor` and reads the only returned row into `T`. Returns
+/// [`Error::NotFound`] if the query returns no rows and [`Error::TooManyRows`] if it returns
+/// more than one row.
+pub(crate) async fn fetch_exactly_one<'c, E, T>(
+    executor: E,
+    options: QueryOptions,
+    mut query: Query<'_>,
+) -> Result<T, Error>
+where
+    E: Executor<'c, Database = MySql>,
+    T: StoreData<MySqlStore>,
+{
+    // A second row is enough to know that the query doesn't match exactly one row.
+    query.limit(2);
+
+    let mut rows = fetch_all(executor, options, &mut query).await?;
+    check_exactly_one(rows.len() as u64)?;
+    Ok(rows.remove(0))
+}
+
+/// Like [`fetch_exactly_one`], but loads the [`Children`] fields of `T` like
+/// [`fetch_all_with_children`].
+///
+/// [`Children`]: crate::Children
+pub(crate) async fn fetch_exactly_one_with_children<T>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    primary_key: &[String],
+    mut query: Query<'_>,
+) -> Result<T, Error>
+where
+    T: StoreData<MySqlStore>,
+{
+    query.limit(2);
+
+    let mut entries = fetch_all_with_children(conn, options, primary_key, query, None).await?;
+    check_exactly_one(entries.len() as u64)?;
+    Ok(entries.remove(0))
+}
+
+/// Returns [`Error::NotFound`] if `rows` is `0` and [`Error::TooManyRows`] if it is greater
+/// than `1`.
+pub(crate) fn check_exactly_one(rows: u64) -> Result<(), Error> {
+    match rows {
+        0 => Err(Error::NotFound),
+        1 => Ok(()),
+        _ => Err(Error::TooManyRows),
+    }
+}
+
+/// Executes `query` on `executor` using the text protocol. This is required for statements
+/// that can't be prepared, like `SAVEPOINT`.
+pub(crate) async fn execute_unprepared<'c, E>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<MySqlQueryResult, Error>
+where
+    E: Executor<'c, Database = MySql>,
+{
+    query.validate()?;
+    let sql = query.to_string();
+
+    let f = instrument(options, query, &sql, async {
+        executor.execute(sql.as_str()).await.map_err(Error::from)
+    });
+
+    with_timeout(options.timeout, f).await
+}
+
+/// Creates the sqlx query for the `sql` and bound values returned by [`Query::prepare`]. sqlx
+/// prepares the query and caches the statement per connection, keyed by the sql.
+fn bind<'q>(sql: &'q str, binds: &[Value]) -> sqlx::query::Query<'q, MySql, MySqlArguments> {
+    sqlx::query_with(sql, arguments(binds))
+}
+
+/// Adds the `binds` to the arguments of a prepared statement.
+fn arguments(binds: &[Value]) -> MySqlArguments {
+    let mut arguments = MySqlArguments::default();
+    for value in binds {
+        value.bind(&mut arguments);
+    }
+
+    arguments
+}
+
+/// Executes `query` on `executor` and reads all returned rows into `T`.
+pub(crate) async fn fetch_all<'c, E, T>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<Vec<T>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+    T: StoreData<MySqlStore>,
+{
+    query.validate()?;
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let f = instrument(options, query, &sql, async {
+        let mut rows = bind(&prepared, &binds).fetch(executor);
+
+        let mut entries = Vec::new();
+        while let Some(row) = rows.try_next().await? {
+            // Generated selects never return a column twice. All rows of a result share their
+            // columns, checking the first row is enough.
+            if entries.is_empty() && matches!(query.inner, QueryInner::Raw { .. }) {
+                if let Some(name) = ambiguous_column(&row) {
+                    return Err(Error::AmbiguousColumn { name });
+                }
+            }
+
+            let mut reader = MySqlReader::new(row);
+            let data = T::read(&mut reader)?;
+
+            entries.push(data);
+        }
+
+        Ok(entries)
+    });
+
+    with_timeout(options.timeout, f).await
+}
+
+/// Executes `query` on `executor` and reads all returned rows into `T`. Unlike [`fetch_all`]
+/// a row that can't be read doesn't fail the query, its error is returned in place of the row.
+pub(crate) async fn fetch_all_results<'c, E, T>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<Vec<Result<T, Error>>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+    T: StoreData<MySqlStore>,
+{
+    query.validate()?;
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let f = instrument(options, query, &sql, async {
+        let mut rows = bind(&prepared, &binds).fetch(executor);
+
+        let mut entries = Vec::new();
+        while let Some(row) = rows.try_next().await? {
+            entries.push(T::read(&mut MySqlReader::new(row)));
+        }
+
+        Ok(entries)
+    });
+
+    with_timeout(options.timeout, f).await
+}
+
+/// Returns the name of the first column of `row` that has the same name as a previous column.
+///
+/// Columns are looked up by name, so only one of them could ever be read.
+fn ambiguous_column(row: &MySqlRow) -> Option<String> {
+    let mut names = HashSet::new();
+    row.columns()
+        .iter()
+        .map(|column| column.name())
+        .find(|name| !names.insert(*name))
+        .map(str::to_owned)
+}
+
+/// Executes `query` on `executor` and reads all returned rows into `T` using its [`Read`]
+/// implementation.
+pub(crate) async fn fetch_all_as<'c, E, T>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<Vec<T>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+    T: Read<MySqlStore>,
+{
+    let rows = fetch_rows(executor, options, query).await?;
+
+    rows.into_iter()
+        .map(|row| T::read(&mut MySqlReader::new(row)))
+        .collect()
+}
+
+/// Executes `query` on `executor` and returns each row as a map of the name and value of every
+/// column.
+pub(crate) async fn fetch_dynamic<'c, E>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<Vec<HashMap<String, Value>>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+{
+    let rows = fetch_rows(executor, options, query).await?;
+
+    rows.iter().map(read_dynamic).collect()
+}
+
+/// Reads all columns of `row` as [`Value`]s.
+fn read_dynamic(row: &MySqlRow) -> Result<HashMap<String, Value>, Error> {
+    row.columns()
+        .iter()
+        .map(|column| {
+            let value = row.try_get_raw(column.ordinal())?;
+            match Value::decode(value) {
+                Ok(value) => Ok((column.name().to_owned(), value)),
+                Err(source) => {
+                    let err = sqlx::Error::ColumnDecode {
+                        index: column.name().to_owned(),
+                        source,
+                    };
+                    Err(decode_error(err, column.name(), "Value"))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Executes `query` on `executor` and calls `f` with the borrowed bytes of `column` of each
+/// returned row.
+pub(crate) async fn fetch_bytes_with<'c, E, F>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+    column: &str,
+    mut f: F,
+) -> Result<(), Error>
+where
+    E: Executor<'c, Database = MySql>,
+    F: FnMut(&[u8]) + Send,
+{
+    query.validate()?;
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let fut = instrument(options, query, &sql, async {
+        let mut rows = bind(&prepared, &binds).fetch(executor);
+
+        while let Some(row) = rows.try_next().await? {
+            let reader = MySqlReader::new(row);
+            f(reader.read_bytes_ref(column)?);
+        }
+
+        Ok(())
+    });
+
+    with_timeout(options.timeout, fut).await
+}
+
+/// Executes `query` on `executor` and calls `f` with the borrowed string of `column` of each
+/// returned row.
+pub(crate) async fn fetch_str_with<'c, E, F>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+    column: &str,
+    mut f: F,
+) -> Result<(), Error>
+where
+    E: Executor<'c, Database = MySql>,
+    F: FnMut(&str) + Send,
+{
+    query.validate()?;
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let fut = instrument(options, query, &sql, async {
+        let mut rows = bind(&prepared, &binds).fetch(executor);
+
+        while let Some(row) = rows.try_next().await? {
+            let reader = MySqlReader::new(row);
+            f(reader.read_str_ref(column)?);
+        }
+
+        Ok(())
+    });
+
+    with_timeout(options.timeout, fut).await
+}
+
+/// Executes `query` on `executor` and returns a stream reading the returned rows into `T`. The
+/// rows are read in batches of [`MySqlStoreConfig::fetch_size`] rows.
+pub(crate) fn fetch_stream<'c, E, T>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> impl Stream<Item = Result<T, Error>> + Send + 'c
+where
+    E: Executor<'c, Database = MySql> + 'c,
+    T: StoreData<MySqlStore> + Send + 'c,
+{
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    #[cfg(not(feature = "tracing"))]
+    log_sql(options, query, &sql);
+
+    #[cfg(feature = "tracing")]
+    let span = span(query);
+    #[cfg(feature = "tracing")]
+    span.in_scope(|| log_sql(options, query, &sql));
+
+    let operation = query.operation();
+    let table = query.error_table().map(str::to_owned);
+    let invalid = query.validate().err();
+
+    async_stream::try_stream! {
+        if let Some(err) = invalid {
+            Err(err)?;
+        }
+
+        let rows = bind(&prepared, &binds).fetch(executor);
+        let mut batches = std::pin::pin!(batched(rows, options.fetch_size));
+
+        while let Some(batch) = batches
+            .try_next()
+            .await
+            .map_err(|err| with_sql(err.into(), operation, table.as_deref(), &options.visible_sql(&sql)))?
+        {
+            for row in batch {
+                let mut reader = MySqlReader::new(row);
+                yield T::read(&mut reader).map_err(|err| with_sql(err, operation, table.as_deref(), &options.visible_sql(&sql)))?;
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        span.in_scope(|| tracing::debug!("Finished sql query"));
+    }
+}
+
+/// Pulls up to `size` items at a time from `stream` and yields them as a single batch. The last
+/// batch holds the remaining items. If `stream` fails the items pulled before the error are
+/// yielded first, followed by the error.
+fn batched<S, T, E>(stream: S, size: usize) -> impl Stream<Item = Result<Vec<T>, E>>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    let size = size.max(1);
+
+    async_stream::stream! {
+        let mut stream = std::pin::pin!(stream);
+
+        loop {
+            let mut batch = Vec::with_capacity(size);
+            let mut error = None;
+
+            while batch.len() < size {
+                match futures::StreamExt::next(&mut stream).await {
+                    Some(Ok(item)) => batch.push(item),
+                    Some(Err(err)) => {
+                        error = Some(err);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+
+            // A short batch means that the stream ended or failed.
+            let done = batch.len() < size;
+            if !batch.is_empty() {
+                yield Ok(batch);
+            }
+
+            if let Some(err) = error {
+                yield Err(err);
+            }
+
+            if done {
+                break;
+            }
+        }
+    }
+}
+
+/// Executes `query` on `executor` and returns the first column of the first returned row.
+pub(crate) async fn fetch_scalar<'c, E, O>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<O, Error>
+where
+    E: Executor<'c, Database = MySql>,
+    O: Send + Unpin + for<'r> sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
+{
+    query.validate()?;
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let f = instrument(options, query, &sql, async {
+        let row = bind(&prepared, &binds).fetch_one(executor).await?;
+        row.try_get(0).map_err(Error::from)
+    });
+
+    with_timeout(options.timeout, f).await
+}
+
+/// Reads the [`ColumnInfo`] of the existing columns of `table` on `executor`.
+async fn describe<'c, E>(
+    executor: E,
+    options: QueryOptions,
+    table: &str,
+) -> Result<Vec<ColumnInfo>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+{
+    let mut query = Query::new(table, QueryKind::Describe);
+    let rows = fetch_as(executor, options, &mut query).await?;
+
+    Ok(rows.into_iter().map(ColumnInfo::from_row).collect())
+}
+
+/// Executes `query` on `executor` and reads all returned rows into the tuple `O`.
+pub(crate) async fn fetch_as<'c, E, O>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<Vec<O>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+    O: Send + Unpin + for<'r> sqlx::FromRow<'r, MySqlRow>,
+{
+    query.validate()?;
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let f = instrument(options, query, &sql, async {
+        sqlx::query_as_with(&prepared, arguments(&binds))
+            .fetch_all(executor)
+            .await
+            .map_err(Error::from)
+    });
+
+    with_timeout(options.timeout, f).await
+}
+
+/// Executes `query` on `executor` and reads the first returned row into `T`.
+pub(crate) async fn fetch_optional<'c, E, T>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<Option<T>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+    T: StoreData<MySqlStore>,
+{
+    query.validate()?;
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let f = instrument(options, query, &sql, async {
+        let row = match bind(&prepared, &binds).fetch_optional(executor).await? {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let mut reader = MySqlReader::new(row);
+        let data = T::read(&mut reader)?;
+
+        Ok(Some(data))
+    });
+
+    with_timeout(options.timeout, f).await
+}
+
+/// Executes `query` on `executor` and returns all rows without reading them.
+async fn fetch_rows<'c, E>(
+    executor: E,
+    options: QueryOptions,
+    query: &mut Query<'_>,
+) -> Result<Vec<MySqlRow>, Error>
+where
+    E: Executor<'c, Database = MySql>,
+{
+    query.validate()?;
+    let sql = query.to_string();
+    let (prepared, binds) = query.prepare();
+
+    let f = instrument(options, query, &sql, async {
+        bind(&prepared, &binds)
+            .fetch_all(executor)
+            .await
+            .map_err(Error::from)
+    });
+
+    with_timeout(options.timeout, f).await
+}
+
+/// Executes the `Select` query on `conn` and reads all returned rows into `T`, loading the
+/// [`Children`] fields of `T` from their child tables. `primary_key` contains the primary key
+/// columns of the table. Returns [`Error::TooManyRows`] if the
+/// query returns more than `max_rows` rows.
+///
+/// The child rows of each `Children` field are selected by a single query for all returned
+/// rows.
+///
+/// [`Children`]: crate::Children
+pub(crate) async fn fetch_all_with_children<T>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    primary_key: &[String],
+    query: Query<'_>,
+    max_rows: Option<u64>,
+) -> Result<Vec<T>, Error>
+where
+    T: StoreData<MySqlStore>,
+{
+    fetch_all_with_children_results(conn, options, primary_key, query, max_rows)
+        .await?
+        .into_iter()
+        .collect()
+}
+
+/// Like [`fetch_all_with_children`], but the error of a row that can't be read is returned in
+/// place of the row instead of failing the query.
+pub(crate) async fn fetch_all_with_children_results<T>(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    primary_key: &[String],
+    mut query: Query<'_>,
+    max_rows: Option<u64>,
+) -> Result<Vec<Result<T, Error>>, Error>
+where
+    T: StoreData<MySqlStore>,
+{
+    if primary_key.is_empty() {
+        return Err(Error::NoPrimaryKey);
+    }
+
+    if let Some(max_rows) = max_rows {
+        query.limit(max_rows.saturating_add(1));
+    }
+
+    let rows = fetch_rows(&mut *conn, options, &mut query).await?;
+    if max_rows.is_some_and(|max_rows| rows.len() as u64 > max_rows) {
+        return Err(Error::TooManyRows);
+    }
+
+    let keys = child_keys(query.table, primary_key);
+
+    let mut children = Vec::new();
+    if !rows.is_empty() {
+        for (table, (column, _)) in child_tables(&query).iter().zip(query.children()) {
+            let mut child_query = select_children(&query, primary_key, table);
+
+            let mut by_parent: HashMap<Vec<Vec<u8>>, Vec<MySqlRow>> = HashMap::new();
+            for row in fetch_rows(&mut *conn, options, &mut child_query).await? {
+                by_parent
+                    .entry(row_key(&row, &keys)?)
+                    .or_default()
+                    .push(row);
+            }
+
+            children.push((column.clone(), by_parent));
+        }
+    }
+
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        let key = row_key(&row, primary_key)?;
+
+        CHILD_ROWS.with(|rows| {
+            let mut rows = rows.borrow_mut();
+            for (column, by_parent) in &mut children {
+                rows.insert(column.clone(), by_parent.remove(&key).unwrap_or_default());
+            }
+        });
+
+        let res = T::read(&mut MySqlReader::new(row));
+        CHILD_ROWS.with(|rows| rows.borrow_mut().clear());
+        entries.push(res);
+    }
+
+    Ok(entries)
+}
+
+/// Executes the `Insert` query on `conn` and inserts the child rows of all [`Children`]
+/// fields. `primary_key` contains the primary key columns of the table. The caller makes sure
+/// that `conn` is in a transaction. Returns the number of affected rows of the parent.
+///
+/// No child rows are inserted if the parent row was skipped by `INSERT IGNORE`. `REPLACE`
+/// deletes the child rows of the replaced row with it.
+///
+/// [`Children`]: crate::Children
+pub(crate) async fn insert_with_children(
+    conn: &mut MySqlConnection,
+    options: QueryOptions,
+    primary_key: &[String],
+    query: &mut Query<'_>,
+) -> Result<u64, Error> {
+    let tables = child_tables(query);
+    let mut children = insert_children(query, primary_key, &tables)?;
+
+    let rows = execute(&mut *conn, options, query).await?.rows_affected();
+    if rows == 0 {
+        return Ok(0);
+    }
+
+    for child in &mut children {
+        execute(&mut *conn, options, child).await?;
+    }
+
+    Ok(rows)
+}
+
+#[derive(Debug)]
+struct MySqlWriter<'a> {
+    query: Query<'a>,
+    write_conditions: bool,
+}
+
+impl<'a> MySqlWriter<'a> {
+    fn new(table: &'a str, kind: QueryKind) -> Self {
+        Self::from_query(Query::new(table, kind))
+    }
+
+    fn from_query(query: Query<'a>) -> Self {
+        Self {
+            query,
+            write_conditions: false,
+        }
+    }
+
+    #[cfg(test)]
+    fn sql(&self) -> String {
+        self.query.to_string()
+    }
+
+    fn write<T>(&mut self, val: T) -> Result<(), <Self as Writer<MySqlStore>>::Error>
+    where
+        T: ToString,
+    {
+        let hints = hints::take();
+
+        if let Some(conditions) = hints.conditions {
+            self.query.push_conditions(conditions);
+            return Ok(());
+        }
+
+        if hints.skip {
+            return Ok(());
+        }
+
+        if let Some(child) = hints.child {
+            if !self.write_conditions {
+                self.query.push_child(hints::column(), child);
+            }
+            return Ok(());
+        }
+
+        // `= NULL` never matches, use `IS NULL` instead.
+        if self.write_conditions && hints.null {
+            self.query
+                .push_condition(Condition::is_null(hints::column()));
+            return Ok(());
+        }
+
+        let val = match (hints.null, hints.literal) {
+            (true, _) => String::from("NULL"),
+            (false, Some(literal)) => literal,
+            (false, None) => val.to_string(),
+        };
+
+        // Values compared in conditions are not stored and may be outside of the range.
+        if let Some((min, max)) = hints.range {
+            if !self.write_conditions {
+                self.query.set_error(Error::OutOfRange {
+                    column: hints::column(),
+                    value: val.clone(),
+                    min,
+                    max,
+                });
+            }
+        }
+
+        if self.write_conditions {
+            self.query
+                .push_condition(Condition::new(hints::column(), val, Comparator::Eq));
+        } else {
+            if hints.auto_increment {
+                self.query.set_auto_increment(hints::column());
+            }
+            self.query.push(hints::column(), val);
+        }
+        Ok(())
+    }
+
+    /// Writes a float as a `DOUBLE` literal with an exponent, e.g. `1.5E0`. Without the exponent
+    /// MySQL reads the literal as a `DECIMAL`. MySQL has no literals for `NaN` and the infinities,
+    /// non-finite values are written as `NULL` instead.
+    ///
+    /// Conditions compare floats for equality, which rarely matches values without an exact
+    /// float representation. This logs a debug message pointing to [`Filter::approx_eq`], it's
+    /// only a hint and intended comparisons would flood the log at a higher level.
+    ///
+    /// [`Filter::approx_eq`]: crate::Filter::approx_eq
+    fn write_float<T>(
+        &mut self,
+        val: T,
+        is_finite: bool,
+    ) -> Result<(), <Self as Writer<MySqlStore>>::Error>
+    where
+        T: ToString,
+    {
+        if self.write_conditions && is_finite {
+            log::debug!(
+                "Comparing the float column {} of table {} for equality, use Filter::approx_eq or a DECIMAL column instead",
+                hints::column(),
+                self.query.table
+            );
+        }
+
+        if is_finite {
+            self.write(format!("{}E0", val.to_string()))
+        } else {
+            hints::with(|hints| hints.null = true, || self.write(val))
+        }
+    }
+}
+
+impl<'a> Writer<MySqlStore> for MySqlWriter<'a> {
+    type Error = Infallible;
+
+    // `TRUE` and `FALSE` are the literals `1` and `0` in MySQL, in values and conditions alike.
+    // `active = TRUE` is the same comparison as `active = 1` and never matches other non-zero
+    // values, which are still read as `true`.
+    fn write_bool(&mut self, v: bool) -> Result<(), Self::Error> {
+        self.write(match v {
+            false => "FALSE",
+            true => "TRUE",
+        })
+    }
+
+    fn write_i8(&mut self, v: i8) -> Result<(), Self::Error> {
+        self.write(v)
+    }
+
+    fn write_i16(&mut self, v: i16) -> Result<(), Self::Error> {
+        self.write(v)
+    }
+
+    fn write_i32(&mut self, v: i32) -> Result<(), Self::Error> {
+        self.write(v)
+    }
+
+    fn write_i64(&mut self, v: i64) -> Result<(), Self::Error> {
+        self.write(v)
+    }
+
+    fn write_u8(&mut self, v: u8) -> Result<(), Self::Error> {
+        self.write(v)
+    }
+
+    fn write_u16(&mut self, v: u16) -> Result<(), Self::Error> {
+        self.write(v)
+    }
+
+    fn write_u32(&mut self, v: u32) -> Result<(), Self::Error> {
+        self.write(v)
+    }
+
+    // Integers are written as unquoted decimal literals. MySQL reads literals above `i64::MAX`
+    // as unsigned, so they are stored exactly in `BIGINT UNSIGNED` columns.
+    fn write_u64(&mut self, v: u64) -> Result<(), Self::Error> {
+        self.write(v)
+    }
+
+    fn write_f32(&mut self, v: f32) -> Result<(), Self::Error> {
+        self.write_float(v, v.is_finite())
+    }
+
+    fn write_f64(&mut self, v: f64) -> Result<(), Self::Error> {
+        self.write_float(v, v.is_finite())
+    }
+
+    fn write_bytes(&mut self, v: &[u8]) -> Result<(), Self::Error> {
+        // Values compared in conditions are not stored and can't be too large.
+        let max_len = hints::take_max_len().unwrap_or(BLOB_MAX_LEN);
+        if v.len() > max_len && !self.write_conditions {
+            self.query.set_error(Error::ValueTooLarge {
+                len: v.len(),
+                max: max_len,
+            });
+        }
+
+        let mut string = String::with_capacity(2 * v.len() + "0x".len());
+        string.push_str("0x");
+        for byte in v {
+            let _ = write!(string, "{:02x}", byte);
+        }
+
+        self.write(string)
+    }
+
+    fn write_str(&mut self, v: &str) -> Result<(), Self::Error> {
+        self.write(MySqlDialect.quote_string_literal(v))
+    }
+
+    fn write_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Write<MySqlStore>,
+    {
+        hints::with_column(key, || value.write(self))
+    }
+}
+
+impl<'a> TypeWriter<MySqlStore> for MySqlWriter<'a> {
+    type Error = Infallible;
+
+    fn write_bool(&mut self) -> Result<(), Self::Error> {
+        self.write("BOOLEAN")
+    }
+
+    fn write_i8(&mut self) -> Result<(), Self::Error> {
+        self.write("TINYINT")
+    }
+
+    fn write_i16(&mut self) -> Result<(), Self::Error> {
+        self.write("SMALLINT")
+    }
+
+    fn write_i32(&mut self) -> Result<(), Self::Error> {
+        self.write("INT")
+    }
+
+    fn write_i64(&mut self) -> Result<(), Self::Error> {
+        self.write("BIGINT")
+    }
+
+    fn write_u8(&mut self) -> Result<(), Self::Error> {
+        self.write("TINYINT UNSIGNED")
+    }
+
+    fn write_u16(&mut self) -> Result<(), Self::Error> {
+        self.write("SMALLINT UNSIGNED")
+    }
+
+    fn write_u32(&mut self) -> Result<(), Self::Error> {
+        self.write("INT UNSIGNED")
+    }
+
+    fn write_u64(&mut self) -> Result<(), Self::Error> {
+        self.write("BIGINT UNSIGNED")
+    }
+
+    fn write_f32(&mut self) -> Result<(), Self::Error> {
+        self.write("FLOAT")
+    }
+
+    fn write_f64(&mut self) -> Result<(), Self::Error> {
+        self.write("DOUBLE")
+    }
+
+    fn write_bytes(&mut self) -> Result<(), Self::Error> {
+        self.write("BLOB")
+    }
+
+    fn write_str(&mut self) -> Result<(), Self::Error> {
+        self.write("TEXT")
+    }
+
+    fn write_field<T>(&mut self, key: &'static str) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Write<MySqlStore>,
+    {
+        hints::with_column(key, || T::write_type(self))
+    }
+}
+
+struct MySqlTypeWriter<'a> {
+    query: Query<'a>,
+    write_conditions: bool,
+}
+
+impl<'a> MySqlTypeWriter<'a> {
+    fn new(table: &'a str, kind: QueryKind) -> Self {
+        Self {
+            query: Query::new(table, kind),
+            write_conditions: false,
+        }
+    }
+
+    #[cfg(test)]
+    fn sql(&self) -> String {
+        self.query.to_string()
+    }
+
+    fn write<T>(&mut self, value: T) -> Result<(), <Self as TypeWriter<MySqlStore>>::Error>
+    where
+        T: ToString,
+    {
+        self.write_column(value, false)
+    }
+
+    /// Writes the type of an integer column. Only integer columns can be `AUTO_INCREMENT`.
+    fn write_integer<T>(&mut self, value: T) -> Result<(), <Self as TypeWriter<MySqlStore>>::Error>
+    where
+        T: ToString,
+    {
+        self.write_column(value, true)
+    }
+
+    fn write_column<T>(
+        &mut self,
+        value: T,
+        integer: bool,
+    ) -> Result<(), <Self as TypeWriter<MySqlStore>>::Error>
+    where
+        T: ToString,
+    {
+        let hints = hints::take();
+        // Types with their own column type, e.g. `BIT`, are not integer columns.
+        let integer = integer && hints.column_type.is_none();
+
+        if let Some(child) = hints.child {
+            if !self.write_conditions {
+                self.query.push_child(hints::column(), child);
+            }
+            return Ok(());
+        }
+
+        let mut value = match hints.column_type {
+            Some(column_type) => column_type,
+            None => value.to_string(),
+        };
+
+        if let Some(collation) = hints.collation {
+            value.push_str(" COLLATE ");
+            value.push_str(collation);
+        }
+
+        // Primary key columns are always NOT NULL.
+        if !hints.nullable || hints.primary_key {
+            value.push_str(" NOT NULL");
+        }
+
+        if let Some(column_default) = hints.column_default {
+            value.push_str(" DEFAULT ");
+            value.push_str(column_default);
+        }
+
+        if hints.auto_increment && !self.write_conditions {
+            if integer {
+                value.push_str(" AUTO_INCREMENT");
+            } else {
+                self.query.set_error(Error::InvalidAutoIncrement {
+                    column: hints::column(),
+                });
+            }
+        }
+
+        if let Some((min, max)) = hints.range {
+            if !self.write_conditions {
+                let column = MySqlDialect.quote_ident(&hints::column());
+                let _ = write!(
+                    value,
+                    " CHECK ({} >= {} AND {} <= {})",
+                    column, min, column, max
+                );
+            }
+        }
+
+        if hints.primary_key {
+            self.query.push_primary_key(hints::column());
+        }
+
+        if !self.write_conditions {
+            self.query.push(hints::column(), value);
+        } else {
+            self.query
+                .push_condition(Condition::new(hints::column(), value, Comparator::Eq));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> TypeWriter<MySqlStore> for MySqlTypeWriter<'a> {
+    type Error = Infallible;
+
+    fn write_bool(&mut self) -> Result<(), Self::Error> {
+        self.write("BOOLEAN")
+    }
+
+    fn write_i8(&mut self) -> Result<(), Self::Error> {
+        self.write_integer("TINYINT")
+    }
+
+    fn write_i16(&mut self) -> Result<(), Self::Error> {
+        self.write_integer("SMALLINT")
+    }
+
+    fn write_i32(&mut self) -> Result<(), Self::Error> {
+        self.write_integer("INT")
+    }
+
+    fn write_i64(&mut self) -> Result<(), Self::Error> {
+        self.write_integer("BIGINT")
+    }
+
+    fn write_u8(&mut self) -> Result<(), Self::Error> {
+        self.write_integer("TINYINT UNSIGNED")
+    }
+
+    fn write_u16(&mut self) -> Result<(), Self::Error> {
+        self.write_integer("SMALLINT UNSIGNED")
+    }
+
+    fn write_u32(&mut self) -> Result<(), Self::Error> {
+        self.write_integer("INT UNSIGNED")
+    }
+
+    fn write_u64(&mut self) -> Result<(), Self::Error> {
+        self.write_integer("BIGINT UNSIGNED")
+    }
+
+    fn write_f32(&mut self) -> Result<(), Self::Error> {
+        self.write("FLOAT")
+    }
+
+    fn write_f64(&mut self) -> Result<(), Self::Error> {
+        self.write("DOUBLE")
+    }
+
+    fn write_bytes(&mut self) -> Result<(), Self::Error> {
+        self.write("BLOB")
+    }
+
+    fn write_str(&mut self) -> Result<(), Self::Error> {
+        self.write("TEXT")
+    }
+
+    fn write_field<T>(&mut self, key: &'static str) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Write<MySqlStore>,
+    {
+        hints::with_column(key, || T::write_type(self))
+    }
+}
+
+/// Writes `values` as the values of the rows of a child table.
+pub(crate) fn write_child_values<T>(values: &[T]) -> Vec<String>
+where
+    T: Write<MySqlStore>,
+{
+    hints::nested(|| {
+        values
+            .iter()
+            .filter_map(|value| {
+                let mut writer = MySqlWriter::new("", QueryKind::Insert);
+                hints::set_column(CHILD_VALUE);
+                value.write(&mut writer).unwrap();
+                writer.query.into_values().pop()
+            })
+            .collect()
+    })
+}
+
+/// Writes the type of the value column of a child table storing values of `T`.
+pub(crate) fn write_child_type<T>() -> String
+where
+    T: Write<MySqlStore>,
+{
+    hints::nested(|| {
+        let mut writer = MySqlTypeWriter::new("", QueryKind::Create);
+        hints::set_column(CHILD_VALUE);
+        T::write_type(&mut writer).unwrap();
+
+        let column_type = writer
+            .query
+            .column_types()
+            .next()
+            .map(|(_, column_type)| column_type.to_owned());
+        column_type.unwrap_or_default()
+    })
+}
+
+/// Reads the values of the child rows of the [`Children`] field `column` of the row that is
+/// currently read.
+///
+/// All generated queries reading `Children` fields either load the child rows or are rejected
+/// before being executed. Only the rows of raw queries are read without their child rows, which
+/// returns [`Error::UnsupportedOperation`] instead of an empty `Children`.
+///
+/// [`Children`]: crate::Children
+pub(crate) fn read_child_values<T>(column: &str) -> Result<Vec<T>, Error>
+where
+    T: Read<MySqlStore>,
+{
+    let Some(rows) = CHILD_ROWS.with(|rows| rows.borrow_mut().remove(column)) else {
+        return Err(Error::UnsupportedOperation {
+            operation: "Children field",
+            kind: "RAW",
+        });
+    };
+
+    hints::nested(|| {
+        rows.into_iter()
+            .map(|row| {
+                let mut reader = MySqlReader::new(row);
+                hints::set_column(CHILD_VALUE);
+                T::read(&mut reader)
+            })
+            .collect()
+    })
+}
+
+/// Returns the raw values of the `columns` of `row`, used to match child rows to their parent
+/// row.
+fn row_key(row: &MySqlRow, columns: &[String]) -> Result<Vec<Vec<u8>>, Error> {
+    columns
+        .iter()
+        .map(|column| {
+            let value = row.try_get_raw(column.as_str())?;
+            match <&[u8] as Decode<MySql>>::decode(value) {
+                Ok(bytes) => Ok(bytes.to_vec()),
+                Err(err) => Err(sqlx::Error::Decode(err).into()),
+            }
+        })
+        .collect()
+}
+
+/// Reads the current column of [`hints::column`] from a row.
+///
+/// Values read without a current column, e.g. by a [`Read`] implementation that doesn't read
+/// its fields by name, are read from the columns of the row in order. Positional reads use the
+/// name of the column at the position, a row with duplicate column names always reads the
+/// first of these columns.
+struct MySqlReader {
+    row: MySqlRow,
+    /// The position of the column read by the next positional read.
+    index: Cell<usize>,
+}
+
+impl MySqlReader {
+    /// Creates a new `MySqlReader` reading `row`. Unsets the current column, reads start at
+    /// the first column until a column is set.
+    fn new(row: MySqlRow) -> Self {
+        hints::clear_column();
+
+        Self {
+            row,
+            index: Cell::new(0),
+        }
+    }
+
+    /// Reads the binary `column` without copying it. The returned bytes borrow from the row.
+    fn read_bytes_ref(&self, column: &str) -> Result<&[u8], Error> {
+        self.row.try_get(column).map_err(Error::from)
+    }
+
+    /// Reads the text `column` without copying it. The returned string borrows from the row.
+    fn read_str_ref(&self, column: &str) -> Result<&str, Error> {
+        self.row
+            .try_get(column)
+            .map_err(|err| decode_error(err, column, "&str"))
+    }
+
+    /// Returns the column of the next read value: the current column, or the column at the
+    /// position of the next positional read if no column is set.
+    fn column(&self) -> Result<String, Error> {
+        let column = hints::column();
+        if !column.is_empty() {
+            return Ok(column);
+        }
+
+        let index = self.index.get();
+        match self.row.columns().get(index) {
+            Some(column) => Ok(column.name().to_owned()),
+            None => Err(sqlx::Error::ColumnIndexOutOfBounds {
+                index,
+                len: self.row.len(),
+            }
+            .into()),
+        }
+    }
+
+    /// Returns `true` if the row contains `column`.
+    fn has_column(&self, column: &str) -> bool {
+        self.row.columns().iter().any(|col| col.name() == column)
+    }
+
+    /// Moves to the next column if a value was read by position.
+    fn advance(&self) {
+        if hints::column().is_empty() {
+            self.index.set(self.index.get() + 1);
+        }
+    }
+
+    fn read<'r, T>(&'r mut self) -> Result<T, <Self as Reader<MySqlStore>>::Error>
+    where
+        T: sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
+    {
+        let column = self.column()?;
+        self.advance();
+        self.row
+            .try_get(column.as_str())
+            .map_err(|err| decode_error(err, &column, std::any::type_name::<T>()))
+    }
+}
+
+/// Returns an error decoding `column` as `rust_type` as [`Error::Decode`], or as
+/// [`Error::UnknownEnumVariant`] for unknown [`SqlEnum`] variants. Other errors, e.g. a missing
+/// column, are returned as [`Error::Sqlx`].
+///
+/// [`SqlEnum`]: crate::SqlEnum
+fn decode_error(err: sqlx::Error, column: &str, rust_type: &'static str) -> Error {
+    match err {
+        sqlx::Error::ColumnDecode { index, source } => match source.downcast::<UnknownVariant>() {
+            Ok(variant) => Error::UnknownEnumVariant {
+                column: column.to_owned(),
+                value: variant.0,
+            },
+            Err(source) => Error::Decode {
+                column: column.to_owned(),
+                rust_type,
+                source: sqlx::Error::ColumnDecode { index, source },
+            },
+        },
+        err => Error::Sqlx(err),
+    }
+}
+
+impl Reader<MySqlStore> for MySqlReader {
+    type Error = Error;
+
+    fn read_bool(&mut self) -> Result<bool, Self::Error> {
+        let hints = hints::take();
+        if hints.is_missing {
+            let is_missing = match self.column() {
+                Ok(column) => !self.has_column(&column),
+                Err(_) => true,
+            };
+            if is_missing {
+                // A missing column is never read, skip the column.
+                self.advance();
+            }
+            return Ok(is_missing);
+        }
+
+        if hints.is_null {
+            let column = self.column()?;
+            return match self.row.try_get_raw(column.as_str()) {
+                Ok(value) if value.is_null() => {
+                    // The value of a `NULL` column is never read, skip the column.
+                    self.advance();
+                    Ok(true)
+                }
+                Ok(_) => Ok(false),
+                // Columns that were not selected are read as `NULL`.
+                Err(sqlx::Error::ColumnNotFound(_)) => Ok(true),
+                Err(err) => Err(err.into()),
+            };
+        }
+
+        // `BOOLEAN` is an alias of `TINYINT(1)`, which stores any value in `-128..=127`. Like
+        // MySQL, treat every non-zero value as `TRUE` instead of rejecting values other than
+        // `0` and `1`.
+        let column = self.column()?;
+        self.advance();
+        let value = self.row.try_get_raw(column.as_str())?;
+        let ty = value.type_info();
+        if !<bool as sqlx::Type<MySql>>::compatible(&ty) {
+            let err = sqlx::Error::ColumnDecode {
+                index: column.clone(),
+                source: format!("cannot decode column of type {} as BOOLEAN", ty.name()).into(),
+            };
+            return Err(decode_error(err, &column, "bool"));
+        }
+
+        // All types compatible with `bool` are integers of at most 8 bytes.
+        let value: i64 = self
+            .row
+            .try_get_unchecked(column.as_str())
+            .map_err(|err| decode_error(err, &column, "bool"))?;
+        Ok(value != 0)
+    }
+
+    fn read_i8(&mut self) -> Result<i8, Self::Error> {
+        self.read()
+    }
+
+    fn read_i16(&mut self) -> Result<i16, Self::Error> {
+        self.read()
+    }
+
+    fn read_i32(&mut self) -> Result<i32, Self::Error> {
+        self.read()
+    }
+
+    fn read_i64(&mut self) -> Result<i64, Self::Error> {
+        self.read()
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Self::Error> {
+        self.read()
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Self::Error> {
+        self.read()
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Self::Error> {
+        self.read()
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Self::Error> {
+        // Expressions over a `BIGINT UNSIGNED` column, e.g. `CAST(id AS SIGNED)` or a `UNION`
+        // with a signed column, may have a signed type. sqlx only decodes `u64` from unsigned
+        // columns, read non-negative values of signed columns instead of rejecting the type.
+        let column = self.column()?;
+        let ty = self
+            .row
+            .try_get_raw(column.as_str())?
+            .type_info()
+            .into_owned();
+        if <u64 as sqlx::Type<MySql>>::compatible(&ty)
+            || !<i64 as sqlx::Type<MySql>>::compatible(&ty)
+        {
+            return self.read();
+        }
+
+        self.advance();
+        let value: i64 = self
+            .row
+            .try_get(column.as_str())
+            .map_err(|err| decode_error(err, &column, "u64"))?;
+        u64::try_from(value).map_err(|_| {
+            let err = sqlx::Error::ColumnDecode {
+                index: column.clone(),
+                source: format!("negative value {} is out of range for u64", value).into(),
+            };
+            decode_error(err, &column, "u64")
+        })
+    }
+
+    fn read_f32(&mut self) -> Result<f32, Self::Error> {
+        self.read()
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Self::Error> {
+        self.read()
+    }
+
+    fn read_byte_buf(&mut self) -> Result<Vec<u8>, Self::Error> {
+        if let Some((decode, rust_type)) = hints::take().decode {
+            let column = self.column()?;
+            self.advance();
+            let value =
+                decode(&self.row, &column).map_err(|err| decode_error(err, &column, rust_type))?;
+            hints::set_decoded(value);
+            return Ok(Vec::new());
+        }
+
+        self.read()
+    }
+
+    fn read_string(&mut self) -> Result<String, Self::Error> {
+        self.read()
+    }
+
+    fn read_field<T>(&mut self, key: &'static str) -> Result<T, Self::Error>
+    where
+        T: Sized + datastore::Read<MySqlStore>,
+    {
+        hints::with_column(key, || T::read(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{MySqlStore, MySqlWriter, PoolStatus, UnknownVariant};
+    use crate::{
+        mysql::MySqlTypeWriter, AggFunc, AutoIncrement, Bits, Bounded, CaseInsensitive,
+        CaseSensitive, Children, Collated, ColumnInfo, ColumnName, Comparison, Condition,
+        Defaulted, Enum, Error, Filter, FixedDecimal, Flatten, ForeignKey, LockMode, LongBytes,
+        LongText, MatchMode, MediumBytes, MediumText, MySqlStoreConfig, Nullable, Order, OrderBy,
+        Point, PrimaryKey, Query, QueryInner, QueryKind, RawTypeWriter, RawWriter,
+        ReferentialAction, Renamed, Set, SqlEnum, SqlSet, SslMode, UpdateExpr, Value, Varchar,
+        WithTable, Year,
+    };
+
+    use datastore::{DataQuery, Store, StoreData, TypeWriter, Write, Writer};
+
+    #[derive(Debug, StoreData)]
+    #[datastore(name = "test")]
+    struct Test {
+        id: i32,
+        name: String,
+    }
+
+    macro_rules! write {
+        ($writer:expr, $key:expr, $val:expr) => {
+            <MySqlWriter as Writer<MySqlStore>>::write_field(&mut $writer, $key, $val).unwrap();
+        };
+    }
+
+    macro_rules! write_type {
+        ($writer:expr, $key:expr, $val:ty) => {
+            <MySqlWriter as TypeWriter<MySqlStore>>::write_field::<$val>(&mut $writer, $key)
+                .unwrap();
+        };
+    }
+
+    #[tokio::test]
+    async fn test_retry_lock_error() {
+        // Only deadlocks and lock wait timeouts are retried, not failed connections.
+        let mut attempts = 0;
+        let res = super::retry(3, Duration::from_millis(1), super::is_lock_error, || {
+            attempts += 1;
+            async {
+                Err::<(), _>(Error::Query {
+                    sql: String::from("DELETE FROM test"),
+                    table: Some(String::from("test")),
+                    source: sqlx::Error::PoolTimedOut,
+                })
+            }
+        })
+        .await;
+
+        assert!(res.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let mut attempts = 0;
+        let res = super::retry(
+            3,
+            Duration::from_millis(1),
+            super::is_connection_error,
+            || {
+                attempts += 1;
+                async { Err::<(), _>(Error::Sqlx(sqlx::Error::PoolTimedOut)) }
+            },
+        )
+        .await;
+
+        assert!(matches!(res, Err(Error::Sqlx(sqlx::Error::PoolTimedOut))));
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let res = super::retry(
+            3,
+            Duration::from_millis(1),
+            super::is_connection_error,
+            || {
+                attempts += 1;
+                let attempt = attempts;
+                async move {
+                    match attempt {
+                        1 => Err(Error::Sqlx(sqlx::Error::Io(
+                            io::ErrorKind::ConnectionRefused.into(),
+                        ))),
+                        _ => Ok(attempt),
+                    }
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(res.unwrap(), 2);
+
+        let mut attempts = 0;
+        let res = super::retry(
+            3,
+            Duration::from_millis(1),
+            super::is_connection_error,
+            || {
+                attempts += 1;
+                async { Err::<(), _>(Error::Sqlx(sqlx::Error::Protocol(String::from("denied")))) }
+            },
+        )
+        .await;
+
+        assert!(res.is_err());
+        assert_eq!(attempts, 1);
+
+        let mut attempts = 0;
+        let res = super::retry(
+            0,
+            Duration::from_millis(1),
+            super::is_connection_error,
+            || {
+                attempts += 1;
+                async { Err::<(), _>(Error::Sqlx(sqlx::Error::PoolTimedOut)) }
+            },
+        )
+        .await;
+
+        assert!(res.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(
+            super::split_statements(
+                "CREATE TABLE a (id INT);\n\
+                 INSERT INTO a VALUES (1); INSERT INTO b VALUES ('a;b', \"c;\\\";d\", 'it''s;');\n\
+                 -- comment; not split\n\
+                 # another; comment\n\
+                 /* block; comment */ SELECT `semi;colon` FROM a;;\n\
+                 /*!40101 SET NAMES utf8mb4 */;\n\
+                 -- trailing comment"
+            ),
+            [
+                "CREATE TABLE a (id INT)",
+                "INSERT INTO a VALUES (1)",
+                "INSERT INTO b VALUES ('a;b', \"c;\\\";d\", 'it''s;')",
+                "-- comment; not split\n# another; comment\n/* block; comment */ SELECT `semi;colon` FROM a",
+                "/*!40101 SET NAMES utf8mb4 */",
+            ]
+        );
+
+        assert_eq!(
+            super::split_statements(
+                "DELIMITER //\n\
+                 CREATE PROCEDURE p() BEGIN SELECT 1; SELECT 2; END//\n\
+                 DELIMITER ;\n\
+                 CALL p();"
+            ),
+            [
+                "CREATE PROCEDURE p() BEGIN SELECT 1; SELECT 2; END",
+                "CALL p()"
+            ]
+        );
+
+        assert!(super::split_statements(" ;\n-- only a comment\n").is_empty());
+    }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            super::redact("INSERT INTO test (id,name,data) VALUES (3,'it''s \\' a',0x0a1f)"),
+            "INSERT INTO test (id,name,data) VALUES (?,?,?)"
+        );
+        assert_eq!(
+            super::redact("SELECT id1,`2 x` FROM test WHERE age >= -1.5 AND name IS NULL"),
+            "SELECT id1,`2 x` FROM test WHERE age >= -? AND name IS NULL"
+        );
+        assert_eq!(
+            super::redact("UPDATE test SET flags = b'0101' WHERE b = 'b'"),
+            "UPDATE test SET flags = ? WHERE b = ?"
+        );
+    }
+
+    #[test]
+    fn test_decode_error() {
+        let err = sqlx::Error::ColumnDecode {
+            index: String::from("age"),
+            source: "invalid value".into(),
+        };
+        assert!(matches!(
+            super::decode_error(err, "age", "i64"),
+            Error::Decode { column, rust_type: "i64", .. } if column == "age"
+        ));
+
+        let err = sqlx::Error::ColumnDecode {
+            index: String::from("status"),
+            source: Box::new(UnknownVariant(String::from("Archived"))),
+        };
+        assert!(matches!(
+            super::decode_error(err, "status", "Status"),
+            Error::UnknownEnumVariant { column, value } if column == "status" && value == "Archived"
+        ));
+
+        // Errors other than decode errors are returned unchanged.
+        let err = sqlx::Error::ColumnNotFound(String::from("age"));
+        assert!(matches!(
+            super::decode_error(err, "age", "i64"),
+            Error::Sqlx(sqlx::Error::ColumnNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_with_timeout() {
+        let res = super::with_timeout(Some(Duration::from_millis(1)), async {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Ok(())
+        })
+        .await;
+
+        assert!(matches!(res, Err(Error::Timeout)));
+
+        let res = super::with_timeout(None, async { Ok(3) }).await;
+        assert_eq!(res.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_select_bytes() {
+        #[derive(Clone, Debug, PartialEq, StoreData)]
+        #[datastore(name = "test_bytes_with")]
+        struct File {
+            id: i32,
+            content: Vec<u8>,
+        }
+
+        assert_eq!(
+            super::select_bytes(&FileDescriptor, &Filter::new().eq("id", &1_i32), "content")
+                .to_string(),
+            "SELECT content FROM test_bytes_with WHERE id = 1"
+        );
+    }
+
+    #[test]
+    fn test_lock_tables() {
+        assert_eq!(
+            crate::session::lock_tables(&[("a", LockMode::Write), ("order", LockMode::Read)])
+                .unwrap(),
+            "LOCK TABLES a WRITE,`order` READ"
+        );
+        assert!(matches!(
+            crate::session::lock_tables(&[]),
+            Err(Error::EmptyLockTables)
+        ));
+    }
+
+    #[test]
+    fn test_connect_options() {
+        let config = MySqlStoreConfig {
+            ssl_mode: Some(SslMode::VerifyCa),
+            ssl_ca: Some(PathBuf::from("/etc/ssl/ca.pem")),
+            ..Default::default()
+        };
+
+        let options = config
+            .connect_options("mysql://root@localhost/test")
+            .unwrap();
+        let options = format!("{:?}", options);
+
+        assert!(options.contains("ssl_mode: VerifyCa"));
+        assert!(options.contains("/etc/ssl/ca.pem"));
+
+        let options = MySqlStoreConfig::default()
+            .connect_options("mysql://root@localhost/test?ssl-mode=REQUIRED")
+            .unwrap();
+
+        assert!(format!("{:?}", options).contains("ssl_mode: Required"));
+    }
+
+    #[test]
+    fn test_builder() {
+        let options = MySqlStore::builder()
+            .host("db.example.com")
+            .port(3307)
+            .username("user")
+            .password("p@ss:w/rd")
+            .database("test")
+            .connect_options();
+        let options = format!("{:?}", options);
+
+        assert!(options.contains("host: \"db.example.com\""));
+        assert!(options.contains("port: 3307"));
+        assert!(options.contains("username: \"user\""));
+        assert!(options.contains("password: Some(\"p@ss:w/rd\")"));
+        assert!(options.contains("database: Some(\"test\")"));
+    }
+
+    #[tokio::test]
+    async fn test_read_pool() {
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+
+        assert!(!store.pool().is_closed());
+        store.read_pool().close().await;
+        assert!(store.pool().is_closed());
+
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://primary/test")
+            .unwrap();
+        let read_pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://replica/test")
+            .unwrap();
+        let store = MySqlStore::from_pools(pool, read_pool);
+
+        // Reads must fail on the closed read pool instead of falling back to the primary.
+        store.read_pool().close().await;
+        assert!(!store.pool().is_closed());
+        assert!(matches!(
+            store.get_all::<Test, _>(TestDescriptor).await,
+            Err(Error::StoreClosed)
+        ));
+        assert!(matches!(
+            store.count_all::<Test, _>(TestDescriptor).await,
+            Err(Error::StoreClosed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_query_error() {
+        let query = super::select(&TestDescriptor, &Filter::new());
+
+        match super::with_sql(
+            Error::Sqlx(sqlx::Error::RowNotFound),
+            query.operation(),
+            query.error_table(),
+            &query.to_string(),
+        ) {
+            Error::Query { sql, table, source } => {
+                assert_eq!(sql, "SELECT id,name FROM test");
+                assert_eq!(table.as_deref(), Some("test"));
+                assert!(matches!(source, sqlx::Error::RowNotFound));
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+
+        let query = crate::Query::raw(String::from("DELETE FROM test"), Vec::new());
+        match super::with_sql(
+            Error::Sqlx(sqlx::Error::RowNotFound),
+            query.operation(),
+            query.error_table(),
+            &query.to_string(),
+        ) {
+            Error::Query { sql, table, .. } => {
+                assert_eq!(sql, "DELETE FROM test");
+                assert_eq!(table, None);
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+
+        let query = super::select(&TestDescriptor, &Filter::new());
+        let err = super::with_sql(
+            Error::Sqlx(sqlx::Error::RowNotFound),
+            query.operation(),
+            query.error_table(),
+            &query.to_string(),
+        );
+        assert_eq!(
+            err.to_string(),
+            "no rows returned by a query that expected to return at least one row \
+             (table test, sql: \"SELECT id,name FROM test\")"
+        );
+
+        // Operations on a closed store fail with `StoreClosed` instead of a query error.
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+        store.close().await;
+
+        assert!(matches!(
+            store.get_all::<Test, _>(TestDescriptor).await,
+            Err(Error::StoreClosed)
+        ));
+        assert!(matches!(
+            store.execute_raw("DELETE FROM test", &[]).await,
+            Err(Error::StoreClosed)
+        ));
+        assert!(matches!(store.begin().await, Err(Error::StoreClosed)));
+        assert_eq!(Error::StoreClosed.to_string(), "the store is closed");
+    }
+
+    #[test]
+    fn test_pool_exhausted() {
+        let query = super::select(&TestDescriptor, &Filter::new());
+
+        let err = super::with_sql(
+            Error::Sqlx(sqlx::Error::PoolTimedOut),
+            query.operation(),
+            query.error_table(),
+            &query.to_string(),
+        );
+        match &err {
+            Error::PoolExhausted { operation, table } => {
+                assert_eq!(*operation, "select");
+                assert_eq!(table.as_deref(), Some("test"));
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+        assert_eq!(
+            err.to_string(),
+            "timed out waiting for a connection for select on table test"
+        );
+
+        let err = super::acquire_error(sqlx::Error::PoolTimedOut, "begin", None);
+        assert_eq!(
+            err.to_string(),
+            "timed out waiting for a connection for begin"
+        );
+        assert!(super::is_connection_error(&err));
+
+        // Other errors are still returned with the executed sql.
+        let err = super::with_sql(
+            Error::Sqlx(sqlx::Error::RowNotFound),
+            query.operation(),
+            query.error_table(),
+            &query.to_string(),
+        );
+        assert!(matches!(err, Error::Query { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_no_columns() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "empty")]
+        struct Empty {}
+
+        let query = super::create(&EmptyDescriptor);
+        assert!(query.missing_columns());
+
+        let query = super::insert(&EmptyDescriptor, &Empty {});
+        assert!(query.missing_columns());
+
+        assert!(!super::create(&TestDescriptor).missing_columns());
+
+        // The queries are rejected before a connection is acquired.
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+        store.close().await;
+
+        assert!(matches!(
+            store.create::<Empty, _>(EmptyDescriptor).await,
+            Err(Error::NoColumns)
+        ));
+        assert!(matches!(
+            store.insert(EmptyDescriptor, Empty {}).await,
+            Err(Error::NoColumns)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pool_status() {
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+
+        assert_eq!(
+            store.pool_status(),
+            PoolStatus {
+                size: 0,
+                idle: 0,
+                in_use: 0
+            }
+        );
+        assert_eq!(store.read_pool_status(), store.pool_status());
+    }
+
+    #[tokio::test]
+    async fn test_close() {
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+        assert!(!store.is_closed());
+
+        store.clone().close().await;
+
+        assert!(store.is_closed());
+        assert!(matches!(store.ping().await, Err(Error::StoreClosed)));
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let query = super::aggregate(&TestDescriptor, &Filter::new(), AggFunc::Sum, "id", "name");
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT name,CAST(SUM(id) AS DOUBLE) FROM test GROUP BY name"
+        );
+
+        let query = super::aggregate(
+            &TestDescriptor,
+            &Filter::new().gt("id", &3),
+            AggFunc::Avg,
+            "id",
+            "name",
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT name,CAST(AVG(id) AS DOUBLE) FROM test WHERE id > 3 GROUP BY name"
+        );
+    }
+
+    #[test]
+    fn test_writer_count() {
+        let writer = MySqlWriter::new("test", QueryKind::Count);
+
+        assert_eq!(writer.sql(), "SELECT COUNT(*) FROM test");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Count);
+        writer.write_conditions = true;
+        write!(writer, "id", &3_i32);
+
+        assert_eq!(writer.sql(), "SELECT COUNT(*) FROM test WHERE id = 3");
+
+        let mut query = super::count(&TestDescriptor, &Filter::new().eq("id", &3));
+        query.count_distinct(String::from("order"));
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT COUNT(DISTINCT `order`) FROM test WHERE id = 3"
+        );
+    }
+
+    #[test]
+    fn test_writer_create() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i32>("id").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL)"
+        );
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i32>("id").unwrap();
+        writer.write_field::<str>("name").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+
+        let data = Test {
+            id: 3,
+            name: String::from("hello"),
+        };
+        let query = TestQuery::default().name(String::from("hello"));
+
+        assert_eq!(
+            store.create_sql(TestDescriptor).unwrap(),
+            [
+                "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL) \
+                 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci"
+            ]
+        );
+        assert_eq!(
+            store.delete_sql(TestDescriptor, query.clone()).unwrap(),
+            ["DELETE FROM test WHERE name = 'hello'"]
+        );
+        assert_eq!(
+            store.get_sql(TestDescriptor, query.clone()).unwrap(),
+            ["SELECT id,name FROM test WHERE name = 'hello'"]
+        );
+        assert_eq!(
+            store.insert_sql(TestDescriptor, &data).unwrap(),
+            ["INSERT INTO test (id,name) VALUES (3,'hello')"]
+        );
+        assert_eq!(
+            store.update_sql(TestDescriptor, &data, query).unwrap(),
+            ["UPDATE test SET id = 3,name = 'hello' WHERE name = 'hello'"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_children() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "post")]
+        struct Post {
+            id: PrimaryKey<i64>,
+            tags: Children<String>,
+        }
+
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "note")]
+        struct Note {
+            tags: Children<String>,
+        }
+
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+
+        let post = Post {
+            id: PrimaryKey(3),
+            tags: Children(vec![String::from("a"), String::from("b")]),
+        };
+
+        assert_eq!(
+            store.create_sql(PostDescriptor).unwrap(),
+            [
+                "CREATE TABLE IF NOT EXISTS post (id BIGINT NOT NULL,PRIMARY KEY (id)) \
+                 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci",
+                "CREATE TABLE IF NOT EXISTS post_tags (post_id BIGINT NOT NULL,position INT UNSIGNED NOT NULL,value TEXT NOT NULL,PRIMARY KEY (post_id,position),FOREIGN KEY (post_id) REFERENCES post (id) ON DELETE CASCADE) \
+                 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci"
+            ]
+        );
+        assert_eq!(
+            store.insert_sql(PostDescriptor, &post).unwrap(),
+            [
+                "INSERT INTO post (id) VALUES (3)",
+                "INSERT INTO post_tags (post_id,position,value) VALUES (3,0,'a'),(3,1,'b')"
+            ]
+        );
+        assert_eq!(
+            store
+                .get_sql(PostDescriptor, Filter::new().eq("id", &3))
+                .unwrap(),
+            [
+                "SELECT id FROM post WHERE id = 3",
+                "SELECT post_id,value FROM post_tags WHERE (post_id) IN (SELECT id FROM (SELECT id FROM post WHERE id = 3) AS parent) ORDER BY post_id,position"
+            ]
+        );
+
+        // The errors of the operations are returned instead of the sql.
+        assert!(matches!(
+            store.update_sql(PostDescriptor, &post, Filter::new()),
+            Err(Error::UnsupportedOperation {
+                operation: "Children field into an update",
+                ..
+            })
+        ));
+        assert!(matches!(
+            store.delete_sql(PostDescriptor, Filter::new()),
+            Err(Error::EmptyDeleteConditions)
+        ));
+        assert!(matches!(
+            store.create_sql(NoteDescriptor),
+            Err(Error::NoPrimaryKey)
+        ));
+        assert!(matches!(
+            store.get_sql(NoteDescriptor, Filter::new()),
+            Err(Error::NoPrimaryKey)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_conditions_on_other_columns() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "test")]
+        struct Id {
+            id: i32,
+        }
+
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+
+        let filter = Filter::new().eq("name", "x");
+        assert_eq!(
+            store.get_sql(IdDescriptor, filter.clone()).unwrap(),
+            ["SELECT id FROM test WHERE name = 'x'"]
+        );
+        assert_eq!(
+            store.delete_sql(IdDescriptor, filter).unwrap(),
+            ["DELETE FROM test WHERE name = 'x'"]
+        );
+    }
+
+    #[test]
+    fn test_create_strict() {
+        let query = super::create(&TestDescriptor);
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL)"
+        );
+
+        let mut query = super::create(&TestDescriptor);
+        query.strict();
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE test (id INT NOT NULL,name TEXT NOT NULL)"
+        );
+    }
+
+    #[test]
+    fn test_with_table() {
+        let descriptor = WithTable::new(TestDescriptor, "test_2024").unwrap();
+
+        assert_eq!(
+            super::create(&descriptor).to_string(),
+            "CREATE TABLE IF NOT EXISTS test_2024 (id INT NOT NULL,name TEXT NOT NULL)"
+        );
+        assert_eq!(
+            super::select(&descriptor, &Filter::new().eq("id", &1)).to_string(),
+            "SELECT id,name FROM test_2024 WHERE id = 1"
+        );
+
+        for name in [
+            "",
+            "test; DROP TABLE x",
+            "te`st",
+            "123",
+            "select",
+            &"a".repeat(65),
+        ] {
+            assert!(matches!(
+                WithTable::new(TestDescriptor, name),
+                Err(Error::InvalidTableName { name: n }) if n == name
+            ));
+        }
+    }
+
+    #[test]
+    fn test_create_with_unique_keys() {
+        // MySQL can't index `TEXT` columns without a prefix length.
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "account")]
+        struct Account {
+            id: i32,
+            email: Varchar<64>,
+        }
+
+        let query = super::create_with_unique_keys(
+            &AccountDescriptor,
+            &[("uq_id_email", &["id", "email"]), ("uq_email", &["email"])],
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS account (id INT NOT NULL,email VARCHAR(64) NOT NULL,UNIQUE KEY uq_id_email (id,email),UNIQUE KEY uq_email (email))"
+        );
+
+        let mut query = super::create_with_unique_keys(&AccountDescriptor, &[("uq_none", &[])]);
+        assert!(matches!(
+            query.validate(),
+            Err(Error::InvalidUniqueKey { name }) if name == "uq_none"
+        ));
+    }
+
+    #[test]
+    fn test_create_with_foreign_keys() {
+        let query = super::create_with_foreign_keys(
+            &TestDescriptor,
+            &[
+                ForeignKey::new(&["id"], "parent", &["id"]).on_delete(ReferentialAction::Cascade),
+                ForeignKey::new(&["id", "name"], "order", &["a", "b"]),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL,FOREIGN KEY (id) REFERENCES parent (id) ON DELETE CASCADE,FOREIGN KEY (id,name) REFERENCES `order` (a,b))"
+        );
+
+        for foreign_key in [
+            ForeignKey::new(&["parent_id"], "parent", &["id"]),
+            ForeignKey::new(&["id"], "parent", &["id", "name"]),
+            ForeignKey::new(&[], "parent", &[]),
+        ] {
+            let err = super::create_with_foreign_keys(&TestDescriptor, &[foreign_key]).unwrap_err();
+            assert!(
+                matches!(&err, Error::InvalidForeignKey { table, .. } if table == "parent"),
+                "{:?}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_table_options() {
+        let mut query = super::create(&TestDescriptor);
+        query.table_options(
+            Some(String::from("utf8mb4")),
+            Some(String::from("utf8mb4_unicode_ci")),
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL) DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci"
+        );
+
+        let mut query = super::create(&TestDescriptor);
+        query.table_options(None, Some(String::from("utf8mb4_bin")));
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL) COLLATE=utf8mb4_bin"
+        );
+    }
+
+    #[test]
+    fn test_create_engine_auto_increment() {
+        let mut query = super::create(&TestDescriptor);
+        query.table_options(Some(String::from("utf8mb4")), None);
+        query.engine(Some(String::from("InnoDB")));
+        query.auto_increment_start(100_000);
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL) ENGINE=InnoDB AUTO_INCREMENT=100000 DEFAULT CHARSET=utf8mb4"
+        );
+
+        let mut query = super::create(&TestDescriptor);
+        query.engine(Some(String::from("MEMORY")));
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL) ENGINE=MEMORY"
+        );
+    }
+
+    #[test]
+    fn test_alter() {
+        let query = super::alter(&TestDescriptor, &[String::from("ID")]);
+
+        assert_eq!(
+            query.to_string(),
+            "ALTER TABLE test ADD COLUMN name TEXT NOT NULL"
+        );
+
+        let query = super::alter(&TestDescriptor, &[]);
+
+        assert_eq!(
+            query.to_string(),
+            "ALTER TABLE test ADD COLUMN id INT NOT NULL,ADD COLUMN name TEXT NOT NULL"
+        );
+
+        let query = super::alter(&TestDescriptor, &[String::from("id"), String::from("name")]);
+        assert!(!query.has_columns());
+    }
+
+    #[test]
+    fn test_describe() {
+        let query = crate::Query::new("test", QueryKind::Describe);
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT COLUMN_NAME,COLUMN_TYPE,IS_NULLABLE,COLUMN_KEY,COLUMN_DEFAULT FROM information_schema.columns WHERE table_schema = DATABASE() AND table_name = 'test' ORDER BY ORDINAL_POSITION"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let existing = |columns: &[(&str, &str)]| -> Vec<ColumnInfo> {
+            columns
+                .iter()
+                .map(|(name, column_type)| ColumnInfo {
+                    name: name.to_string(),
+                    column_type: column_type.to_string(),
+                    nullable: false,
+                    key: None,
+                    default: None,
+                })
+                .collect()
+        };
+
+        super::validate(
+            &TestDescriptor,
+            &existing(&[("id", "int"), ("name", "text")]),
+        )
+        .unwrap();
+        super::validate(
+            &TestDescriptor,
+            &existing(&[("ID", "int(11)"), ("name", "TEXT"), ("other", "blob")]),
+        )
+        .unwrap();
+
+        let err = super::validate(
+            &TestDescriptor,
+            &existing(&[("id", "bigint"), ("name", "text")]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::SchemaMismatch { column, expected, found: Some(found) }
+                if column == "id" && expected == "INT NOT NULL" && found == "bigint"
+        ));
+
+        let err = super::validate(&TestDescriptor, &existing(&[("id", "int")])).unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::SchemaMismatch { column, found: None, .. } if column == "name"
+        ));
+
+        assert_eq!(super::normalize_type("BOOLEAN NOT NULL"), "tinyint");
+        assert_eq!(
+            super::normalize_type("DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)"),
+            "datetime(6)"
+        );
+        assert_eq!(super::normalize_type("tinyint(1)"), "tinyint");
+        assert_eq!(super::normalize_type("int(10) unsigned"), "int unsigned");
+        assert_eq!(
+            super::normalize_type("VARCHAR(32) COLLATE utf8mb4_bin NOT NULL"),
+            "varchar(32)"
+        );
+        assert_eq!(
+            super::normalize_type("DECIMAL(39,0) UNSIGNED"),
+            "decimal(39,0) unsigned"
+        );
+    }
+
+    #[test]
+    fn test_writer_char() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<char>("c").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (c CHAR(1) NOT NULL)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &'a');
+        write!(writer, "b", &'\'');
+        write!(writer, "c", &'€');
+        write!(writer, "d", &'🦀');
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c,d) VALUES ('a','''','€','🦀')"
+        );
+    }
+
+    #[test]
+    fn test_writer_str_types() {
+        use std::borrow::Cow;
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Cow<'_, str>>("a").unwrap();
+        writer.write_field::<Box<str>>("b").unwrap();
+        writer.write_field::<Arc<str>>("c").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a TEXT NOT NULL,b TEXT NOT NULL,c TEXT NOT NULL)"
+        );
+
+        let string = String::from("it's");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Cow::Borrowed("a"));
+        write!(writer, "b", &Box::<str>::from("b"));
+        write!(writer, "c", &Rc::<str>::from("c"));
+        write!(writer, "d", &Arc::<str>::from("d"));
+        write!(writer, "e", &&string);
+        write!(writer, "f", &"f");
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c,d,e,f) VALUES ('a','b','c','d','it''s','f')"
+        );
+    }
+
+    #[test]
+    fn test_writer_borrowed_option() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Option<&[u8]>>("a").unwrap();
+        writer.write_field::<Option<&str>>("b").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a BLOB,b TEXT)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Some(b"data".as_slice()));
+        write!(writer, "b", &None::<&[u8]>);
+        write!(writer, "c", &Some("text"));
+        write!(writer, "d", &None::<&str>);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c,d) VALUES (0x64617461,NULL,'text',NULL)"
+        );
+    }
+
+    #[test]
+    fn test_writer_enum() {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        enum Color {
+            Red,
+            DarkGreen,
+        }
+
+        impl SqlEnum for Color {
+            const VARIANTS: &'static [&'static str] = &["Red", "Dark'Green"];
+
+            fn as_str(&self) -> &'static str {
+                match self {
+                    Self::Red => "Red",
+                    Self::DarkGreen => "Dark'Green",
+                }
+            }
+
+            fn from_variant(name: &str) -> Option<Self> {
+                match name {
+                    "Red" => Some(Self::Red),
+                    "Dark'Green" => Some(Self::DarkGreen),
+                    _ => None,
+                }
+            }
+        }
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Enum<Color>>("color").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (color ENUM('Red','Dark''Green') NOT NULL)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Enum(Color::Red));
+        write!(writer, "b", &Enum(Color::DarkGreen));
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b) VALUES ('Red','Dark''Green')"
+        );
+
+        for color in [Color::Red, Color::DarkGreen] {
+            assert_eq!(Color::from_variant(color.as_str()), Some(color));
+        }
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    struct Features(Vec<&'static str>);
+
+    impl SqlSet for Features {
+        const MEMBERS: &'static [&'static str] = &["search", "export", "it's"];
+
+        fn members(&self) -> Vec<&'static str> {
+            self.0.clone()
+        }
+
+        fn from_members(names: &[&str]) -> Option<Self> {
+            names
+                .iter()
+                .map(|name| Self::MEMBERS.iter().copied().find(|member| member == name))
+                .collect::<Option<Vec<_>>>()
+                .map(Self)
+        }
+    }
+
+    #[test]
+    fn test_writer_set() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Set<Features>>("features").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (features SET('search','export','it''s') NOT NULL)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Set(Features::default()));
+        write!(writer, "b", &Set(Features(vec!["search", "it's"])));
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b) VALUES ('','search,it''s')"
+        );
+    }
+
+    #[test]
+    fn test_writer_bits() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Bits<4>>("a").unwrap();
+        writer.write_field::<Option<Bits<64>>>("b").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a BIT(4) NOT NULL,b BIT(64))"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Bits::<4>(0));
+        write!(writer, "b", &Bits::<4>(5));
+        write!(writer, "c", &Bits::<16>(0x8001));
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c) VALUES (b'0000',b'0101',b'1000000000000001')"
+        );
+        assert!(writer.query.take_error().is_none());
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Bits::<4>(0x10));
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::OutOfRange { column, min: 0, max: 15, .. }) if column == "a"
+        ));
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Bits::<64>(u64::MAX));
+        assert!(writer.query.take_error().is_none());
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<PrimaryKey<AutoIncrement<Bits<8>>>>("id")
+            .unwrap();
+        assert!(writer.query.take_error().is_some());
+    }
+
+    #[test]
+    fn test_writer_year() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Year>("a").unwrap();
+        writer.write_field::<Option<Year>>("b").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a YEAR NOT NULL,b YEAR)"
+        );
+        assert_eq!(super::normalize_type("year(4)"), "year");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Year(1901));
+        write!(writer, "b", &Year(2155));
+        write!(writer, "c", &Year(0));
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c) VALUES (1901,2155,0)"
+        );
+        assert!(writer.query.take_error().is_none());
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Year(1900));
+        write!(writer, "b", &Year(2156));
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::OutOfRange { column, value, min: 1901, max: 2155 }) if column == "a" && value == "1900"
+        ));
+    }
+
+    #[test]
+    fn test_writer_nullable() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i64>("a").unwrap();
+        writer.write_field::<Nullable<i64>>("b").unwrap();
+        writer.write_field::<Nullable<Varchar<32>>>("c").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a BIGINT NOT NULL,b BIGINT,c VARCHAR(32))"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "b", &Nullable(3_i64));
+
+        assert_eq!(writer.sql(), "INSERT INTO test (b) VALUES (3)");
+    }
+
+    #[test]
+    fn test_writer_point() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Point>("a").unwrap();
+        writer.write_field::<Option<Point>>("b").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a POINT NOT NULL,b POINT)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Point::new(1.5, -2.0));
+        write!(writer, "b", &Point::new(f64::NAN, 0.0));
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b) VALUES (POINT(1.5, -2),NULL)"
+        );
+        assert_eq!(Point::new(1.5, -2.0).to_string(), "POINT(1.5 -2)");
+    }
+
+    #[test]
+    fn test_writer_raw() {
+        struct Inet6(std::net::Ipv6Addr);
+
+        impl Write<MySqlStore> for Inet6 {
+            fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+            where
+                W: Writer<MySqlStore>,
+            {
+                writer.write_raw_literal(format!("INET6_ATON('{}')", self.0))
+            }
+
+            fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+            where
+                W: TypeWriter<MySqlStore>,
+            {
+                writer.write_raw_type(String::from("VARBINARY(16)"))
+            }
+        }
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Inet6>("a").unwrap();
+        writer.write_field::<Option<Inet6>>("b").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a VARBINARY(16) NOT NULL,b VARBINARY(16))"
+        );
+
+        let addr = Inet6(std::net::Ipv6Addr::LOCALHOST);
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &addr);
+        write!(writer, "b", &None::<Inet6>);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b) VALUES (INET6_ATON('::1'),NULL)"
+        );
+
+        let filter = Filter::new().eq("a", &addr);
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE a = INET6_ATON('::1')"
+        );
+    }
+
+    #[test]
+    fn test_point_from_geometry() {
+        // SRID 0, little endian, type 1 (point), x = 1.5, y = -2.
+        let mut value = vec![0, 0, 0, 0, 1, 1, 0, 0, 0];
+        value.extend(1.5_f64.to_le_bytes());
+        value.extend((-2.0_f64).to_le_bytes());
+        assert_eq!(Point::from_geometry(&value), Some(Point::new(1.5, -2.0)));
+
+        let mut value = vec![0, 0, 0, 0, 0, 0, 0, 0, 1];
+        value.extend(1.5_f64.to_be_bytes());
+        value.extend((-2.0_f64).to_be_bytes());
+        assert_eq!(Point::from_geometry(&value), Some(Point::new(1.5, -2.0)));
+
+        // A line string is not a point.
+        value[8] = 2;
+        assert_eq!(Point::from_geometry(&value), None);
+        assert_eq!(Point::from_geometry(&value[..20]), None);
+    }
+
+    #[test]
+    fn test_writer_ip() {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Ipv4Addr>("a").unwrap();
+        writer.write_field::<Ipv6Addr>("b").unwrap();
+        writer.write_field::<IpAddr>("c").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a INT UNSIGNED NOT NULL,b BINARY(16) NOT NULL,c VARBINARY(16) NOT NULL)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Ipv4Addr::new(192, 168, 0, 1));
+        write!(writer, "b", &Ipv6Addr::LOCALHOST);
+        write!(writer, "c", &IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c) VALUES (3232235521,0x00000000000000000000000000000001,0x0a000001)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "c", IpAddr);
+        writer.write_conditions = true;
+        write!(writer, "c", &IpAddr::V6(Ipv6Addr::LOCALHOST));
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT c FROM test WHERE c = 0x00000000000000000000000000000001"
+        );
+    }
+
+    #[test]
+    fn test_writer_duration() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Duration>("timeout").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (timeout BIGINT UNSIGNED NOT NULL)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &Duration::from_millis(1500));
+        write!(writer, "b", &Duration::MAX);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b) VALUES (1500000000,18446744073709551615)"
+        );
+    }
+
+    #[test]
+    fn test_flatten() {
+        #[derive(Clone, Debug, StoreData)]
+        struct Geo {
+            lat: f64,
+            lon: f64,
+        }
+
+        #[derive(Clone, Debug, StoreData)]
+        struct Address {
+            city: String,
+            geo: Flatten<Geo>,
+        }
+
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "person")]
+        struct Person {
+            id: i32,
+            address: Flatten<Address>,
+            name: String,
+        }
+
+        let query = super::create(&PersonDescriptor);
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS person (id INT NOT NULL,address_city TEXT NOT NULL,address_geo_lat DOUBLE NOT NULL,address_geo_lon DOUBLE NOT NULL,name TEXT NOT NULL)"
+        );
+
+        let person = Person {
+            id: 1,
+            address: Flatten(Address {
+                city: String::from("Winterfell"),
+                geo: Flatten(Geo {
+                    lat: 1.5,
+                    lon: -2.0,
+                }),
+            }),
+            name: String::from("Robb"),
+        };
+
+        assert_eq!(
+            super::insert(&PersonDescriptor, &person).to_string(),
+            "INSERT INTO person (id,address_city,address_geo_lat,address_geo_lon,name) VALUES (1,'Winterfell',1.5E0,-2E0,'Robb')"
+        );
+
+        assert_eq!(
+            super::select_all(&PersonDescriptor).to_string(),
+            "SELECT id,address_city,address_geo_lat,address_geo_lon,name FROM person"
+        );
+    }
+
+    #[test]
+    fn test_renamed() {
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct UserId;
+
+        impl ColumnName for UserId {
+            const NAME: &'static str = "user_id";
+        }
+
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "user")]
+        struct User {
+            id: PrimaryKey<Renamed<i64, UserId>>,
+            name: String,
+        }
+
+        let query = super::create(&UserDescriptor);
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS user (user_id BIGINT NOT NULL,name TEXT NOT NULL,PRIMARY KEY (user_id))"
+        );
+
+        let user = User {
+            id: PrimaryKey(Renamed::new(1)),
+            name: String::from("Robb"),
+        };
+
+        assert_eq!(
+            super::insert(&UserDescriptor, &user).to_string(),
+            "INSERT INTO user (user_id,name) VALUES (1,'Robb')"
+        );
+
+        assert_eq!(
+            super::select(
+                &UserDescriptor,
+                &UserQuery::default().id(PrimaryKey(Renamed::new(1)))
+            )
+            .to_string(),
+            "SELECT user_id,name FROM user WHERE user_id = 1"
+        );
+
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "profile")]
+        struct Profile {
+            id: i64,
+            user: Option<Renamed<i64, UserId>>,
+        }
+
+        let profile = Profile { id: 1, user: None };
+
+        assert_eq!(
+            super::insert(&ProfileDescriptor, &profile).to_string(),
+            "INSERT INTO profile (id,user_id) VALUES (1,NULL)"
+        );
+
+        assert_eq!(
+            super::select(&ProfileDescriptor, &ProfileQuery::default().user(None)).to_string(),
+            "SELECT id,user_id FROM profile WHERE user_id IS NULL"
+        );
+    }
+
+    #[test]
+    fn test_writer_collation() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<Collated<Varchar<32>, CaseSensitive>>("a")
+            .unwrap();
+        writer
+            .write_field::<Option<Collated<String, CaseInsensitive>>>("b")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a VARCHAR(32) COLLATE utf8mb4_bin NOT NULL,b TEXT COLLATE utf8mb4_general_ci)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(
+            writer,
+            "a",
+            &Collated::<_, CaseSensitive>::new(String::from("Hello"))
+        );
+
+        assert_eq!(writer.sql(), "INSERT INTO test (a) VALUES ('Hello')");
+
+        let filter = Filter::new().eq_collate("name", "Hello", "utf8mb4_bin");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE name = 'Hello' COLLATE utf8mb4_bin"
+        );
+    }
+
+    #[test]
+    fn test_writer_create_i128() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i128>("a").unwrap();
+        writer.write_field::<u128>("b").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a DECIMAL(39,0) NOT NULL,b DECIMAL(39,0) UNSIGNED NOT NULL)"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_writer_json() {
+        use crate::Json;
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Json<Vec<String>>>("tags").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (tags JSON NOT NULL)"
+        );
+
+        let tags = Json(vec![String::from("it's"), String::from("a\"b")]);
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "tags", &tags);
+
+        assert_eq!(
+            writer.sql(),
+            r#"INSERT INTO test (tags) VALUES ('["it''s","a\\"b"]')"#
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_writer_json_value() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<serde_json::Value>("a").unwrap();
+        writer
+            .write_field::<Option<serde_json::Value>>("b")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a JSON NOT NULL,b JSON)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &serde_json::json!({ "name": "it's \"x\"" }));
+        write!(writer, "b", &Some(serde_json::Value::Null));
+        write!(writer, "c", &None::<serde_json::Value>);
+
+        assert_eq!(
+            writer.sql(),
+            r#"INSERT INTO test (a,b,c) VALUES ('{"name":"it''s \\"x\\""}','null',NULL)"#
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_writer_json_map() {
+        use std::collections::HashMap;
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<HashMap<String, serde_json::Value>>("attributes")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (attributes JSON NOT NULL)"
+        );
+
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            String::from("size"),
+            serde_json::json!({ "w": 1, "h": [2, 3] }),
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "attributes", &attributes);
+
+        assert_eq!(
+            writer.sql(),
+            r#"INSERT INTO test (attributes) VALUES ('{"size":{"h":[2,3],"w":1}}')"#
+        );
+    }
+
+    #[test]
+    fn test_writer_bytes_too_large() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "data", &vec![0_u8; 65535]);
+        assert!(writer.query.take_error().is_none());
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "data", &vec![0_u8; 65536]);
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::ValueTooLarge {
+                len: 65536,
+                max: 65535
+            })
+        ));
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "data", &MediumBytes(vec![0_u8; 65536]));
+        assert!(writer.query.take_error().is_none());
+    }
+
+    #[test]
+    fn test_writer_create_large() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Vec<u8>>("a").unwrap();
+        writer.write_field::<MediumBytes>("b").unwrap();
+        writer.write_field::<LongBytes>("c").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a BLOB NOT NULL,b MEDIUMBLOB NOT NULL,c LONGBLOB NOT NULL)"
+        );
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<String>("a").unwrap();
+        writer.write_field::<MediumText>("b").unwrap();
+        writer.write_field::<Option<LongText>>("c").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a TEXT NOT NULL,b MEDIUMTEXT NOT NULL,c LONGTEXT)"
+        );
+    }
+
+    #[test]
+    fn test_writer_create_option() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i32>("id").unwrap();
+        writer.write_field::<Option<String>>("name").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT)"
+        );
+    }
+
+    #[test]
+    fn test_writer_create_primary_key() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<PrimaryKey<i32>>("id").unwrap();
+        writer.write_field::<str>("name").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name TEXT NOT NULL,PRIMARY KEY (id))"
+        );
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<PrimaryKey<i32>>("id").unwrap();
+        writer
+            .write_field::<PrimaryKey<Varchar<32>>>("name")
+            .unwrap();
+        writer.write_field::<Option<i64>>("value").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name VARCHAR(32) NOT NULL,value BIGINT,PRIMARY KEY (id,name))"
+        );
+    }
+
+    #[test]
+    fn test_writer_create_auto_increment() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<PrimaryKey<AutoIncrement<u64>>>("id")
+            .unwrap();
+        writer.write_field::<str>("name").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,name TEXT NOT NULL,PRIMARY KEY (id))"
+        );
+        assert!(writer.query.take_error().is_none());
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<AutoIncrement<PrimaryKey<i32>>>("id")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL AUTO_INCREMENT,PRIMARY KEY (id))"
+        );
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<PrimaryKey<AutoIncrement<String>>>("id")
+            .unwrap();
+        writer.write_field::<AutoIncrement<f64>>("value").unwrap();
+
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::InvalidAutoIncrement { column }) if column == "id"
+        ));
+
+        assert_eq!(
+            super::normalize_type("BIGINT UNSIGNED NOT NULL AUTO_INCREMENT"),
+            "bigint unsigned"
+        );
+    }
+
+    #[test]
+    fn test_writer_create_bounded() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Bounded<u8, 1, 5>>("code").unwrap();
+        writer
+            .write_field::<Option<Bounded<i16, -10, 10>>>("order")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (code TINYINT UNSIGNED NOT NULL CHECK (code >= 1 AND code <= 5),`order` SMALLINT CHECK (`order` >= -10 AND `order` <= 10))"
+        );
+
+        assert_eq!(
+            super::normalize_type("TINYINT UNSIGNED NOT NULL CHECK (code >= 1 AND code <= 5)"),
+            "tinyint unsigned"
+        );
+    }
+
+    #[test]
+    fn test_writer_bounded_out_of_range() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "code", &Bounded::<u8, 1, 5>(5));
+        assert!(writer.query.take_error().is_none());
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "code", &Bounded::<u8, 1, 5>(6));
+        assert_eq!(writer.sql(), "INSERT INTO test (code) VALUES (6)");
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::OutOfRange { column, value, min: 1, max: 5 }) if column == "code" && value == "6"
+        ));
+
+        // Conditions may compare with values outside of the range.
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        writer.write_conditions = true;
+        write!(writer, "code", &Bounded::<u8, 1, 5>(0));
+        assert!(writer.query.take_error().is_none());
+    }
+
+    #[test]
+    fn test_select_bounded() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "rating")]
+        struct Rating {
+            id: u64,
+            stars: Bounded<u8, 1, 5>,
+        }
+
+        let mut query = super::select(&RatingDescriptor, &RatingQuery::default().id(1));
+        assert!(query.take_error().is_none());
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,stars FROM rating WHERE id = 1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_by_primary_key() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "membership")]
+        struct Membership {
+            person_id: PrimaryKey<i64>,
+            group_id: PrimaryKey<i64>,
+            role: String,
+        }
+
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let mut store = MySqlStore::from_pool(pool);
+        store.order_by_primary_key = true;
+
+        assert_eq!(
+            store.get_sql(MembershipDescriptor, MembershipQuery::default()).unwrap(),
+            ["SELECT person_id,group_id,role FROM membership ORDER BY person_id ASC,group_id ASC"]
+        );
+        // Types without a primary key are not ordered.
+        assert_eq!(
+            store.get_sql(TestDescriptor, TestQuery::default()).unwrap(),
+            ["SELECT id,name FROM test"]
+        );
+
+        let mut query = super::select_one(&MembershipDescriptor, &MembershipQuery::default());
+        query.order_by(String::from("role"), Order::Desc);
+        query.order_by_primary_key(&[String::from("person_id")]);
+        assert_eq!(
+            query.to_string(),
+            "SELECT person_id,group_id,role FROM membership ORDER BY role DESC LIMIT 1"
+        );
+    }
+
+    #[test]
+    fn test_key_filter() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "user")]
+        struct User {
+            id: PrimaryKey<i64>,
+            name: String,
+        }
+
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "membership")]
+        struct Membership {
+            person_id: PrimaryKey<i64>,
+            group_id: PrimaryKey<i64>,
+        }
+
+        let filter = super::key_filter(&UserDescriptor, &3_i64).unwrap();
+        assert_eq!(
+            super::select_one(&UserDescriptor, &filter).to_string(),
+            "SELECT id,name FROM user WHERE id = 3 LIMIT 1"
+        );
+
+        assert!(matches!(
+            super::key_filter(&TestDescriptor, &3_i32),
+            Err(Error::NoPrimaryKey)
+        ));
+        assert!(matches!(
+            super::key_filter(&MembershipDescriptor, &3_i64),
+            Err(Error::NoPrimaryKey)
+        ));
+    }
+
+    #[test]
+    fn test_keys_only() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "membership")]
+        struct Membership {
+            person_id: PrimaryKey<i64>,
+            group_id: PrimaryKey<i64>,
+        }
+
+        assert_eq!(
+            super::create(&MembershipDescriptor).to_string(),
+            "CREATE TABLE IF NOT EXISTS membership (person_id BIGINT NOT NULL,group_id BIGINT NOT NULL,PRIMARY KEY (person_id,group_id))"
+        );
+
+        let membership = Membership {
+            person_id: PrimaryKey(1),
+            group_id: PrimaryKey(2),
+        };
+
+        assert_eq!(
+            super::insert(&MembershipDescriptor, &membership).to_string(),
+            "INSERT INTO membership (person_id,group_id) VALUES (1,2)"
+        );
+        assert_eq!(
+            super::upsert(&MembershipDescriptor, &membership).to_string(),
+            "INSERT INTO membership (person_id,group_id) VALUES (1,2) ON DUPLICATE KEY UPDATE person_id=VALUES(person_id),group_id=VALUES(group_id)"
+        );
+
+        let query = MembershipQuery::default().person_id(PrimaryKey(1));
+        assert_eq!(
+            super::select(&MembershipDescriptor, &query).to_string(),
+            "SELECT person_id,group_id FROM membership WHERE person_id = 1"
+        );
+        assert_eq!(
+            super::delete(&MembershipDescriptor, &query).to_string(),
+            "DELETE FROM membership WHERE person_id = 1"
+        );
+    }
+
+    #[test]
+    fn test_children() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "post")]
+        struct Post {
+            id: PrimaryKey<i64>,
+            title: String,
+            tags: Children<String>,
+        }
+
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "note")]
+        struct Note {
+            title: String,
+            tags: Children<String>,
+        }
+
+        let parent = super::create(&PostDescriptor);
+        assert_eq!(
+            parent.to_string(),
+            "CREATE TABLE IF NOT EXISTS post (id BIGINT NOT NULL,title TEXT NOT NULL,PRIMARY KEY (id))"
+        );
+
+        let tables = super::child_tables(&parent);
+        assert_eq!(tables, ["post_tags"]);
+
+        let children = super::create_children(&parent, &tables).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(
+            children[0].to_string(),
+            "CREATE TABLE IF NOT EXISTS post_tags (post_id BIGINT NOT NULL,position INT UNSIGNED NOT NULL,value TEXT NOT NULL,PRIMARY KEY (post_id,position),FOREIGN KEY (post_id) REFERENCES post (id) ON DELETE CASCADE)"
+        );
+
+        let post = Post {
+            id: PrimaryKey(3),
+            title: String::from("hello"),
+            tags: Children(vec![String::from("a"), String::from("b")]),
+        };
+
+        let query = super::insert(&PostDescriptor, &post);
+        assert_eq!(
+            query.to_string(),
+            "INSERT INTO post (id,title) VALUES (3,'hello')"
+        );
+
+        let primary_key = super::primary_key(&PostDescriptor);
+        let tables = super::child_tables(&query);
+        let children = super::insert_children(&query, &primary_key, &tables).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(
+            children[0].to_string(),
+            "INSERT INTO post_tags (post_id,position,value) VALUES (3,0,'a'),(3,1,'b')"
+        );
+
+        // Nothing is inserted for an empty `Children`.
+        let post = Post {
+            id: PrimaryKey(4),
+            title: String::from("empty"),
+            tags: Children::default(),
+        };
+        let query = super::insert(&PostDescriptor, &post);
+        let tables = super::child_tables(&query);
+        assert!(super::insert_children(&query, &primary_key, &tables)
+            .unwrap()
+            .is_empty());
+
+        let query = super::select_one(&PostDescriptor, &Filter::new().eq("title", "hello"));
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,title FROM post WHERE title = 'hello' LIMIT 1"
+        );
+        assert_eq!(
+            super::select_children(&query, &primary_key, "post_tags").to_string(),
+            "SELECT post_id,value FROM post_tags WHERE (post_id) IN (SELECT id FROM (SELECT id,title FROM post WHERE title = 'hello' LIMIT 1) AS parent) ORDER BY post_id,position"
+        );
+
+        // Upserts would have to replace the child rows of the updated item.
+        let post = Post {
+            id: PrimaryKey(3),
+            title: String::from("hello"),
+            tags: Children(vec![String::from("a")]),
+        };
+        for mut query in [
+            super::upsert(&PostDescriptor, &post),
+            super::save(&PostDescriptor, &post),
+        ] {
+            assert!(matches!(
+                query.take_error(),
+                Some(Error::UnsupportedOperation {
+                    operation: "Children field into an upsert",
+                    kind: "INSERT"
+                })
+            ));
+        }
+        assert!(super::replace(&PostDescriptor, &post)
+            .take_error()
+            .is_none());
+        assert!(super::insert_ignore(&PostDescriptor, &post)
+            .take_error()
+            .is_none());
+
+        // The child table references the primary key of the parent.
+        let parent = super::create(&NoteDescriptor);
+        let tables = super::child_tables(&parent);
+        assert!(matches!(
+            super::create_children(&parent, &tables),
+            Err(Error::NoPrimaryKey)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_batched() {
+        let items = futures::stream::iter((1..=5).map(Ok::<_, ()>));
+        let batches: Vec<_> = futures::StreamExt::collect(super::batched(items, 2)).await;
+        assert_eq!(batches, [Ok(vec![1, 2]), Ok(vec![3, 4]), Ok(vec![5])]);
+
+        let items = futures::stream::iter((1..=4).map(Ok::<_, ()>));
+        let batches: Vec<_> = futures::StreamExt::collect(super::batched(items, 2)).await;
+        assert_eq!(batches, [Ok(vec![1, 2]), Ok(vec![3, 4])]);
+
+        // The items before an error are yielded first.
+        let items = futures::stream::iter([Ok(1), Ok(2), Ok(3), Err(()), Ok(4)]);
+        let batches: Vec<_> = futures::StreamExt::collect(super::batched(items, 2)).await;
+        assert_eq!(batches, [Ok(vec![1, 2]), Ok(vec![3]), Err(())]);
+
+        let items = futures::stream::iter((1..=2).map(Ok::<_, ()>));
+        let batches: Vec<_> = futures::StreamExt::collect(super::batched(items, 0)).await;
+        assert_eq!(batches, [Ok(vec![1]), Ok(vec![2])]);
+    }
+
+    #[tokio::test]
+    async fn test_children_unsupported() {
+        #[derive(Debug, StoreData)]
+        #[datastore(name = "post")]
+        struct Post {
+            id: PrimaryKey<i64>,
+            tags: Children<String>,
+        }
+
+        let post = || Post {
+            id: PrimaryKey(1),
+            tags: Children(vec![String::from("a")]),
+        };
+        let is_unsupported = |err: Error, expected: &str| matches!(err, Error::UnsupportedOperation { operation, .. } if operation == expected);
+
+        // The operations are rejected before a connection is acquired.
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .connect_lazy("mysql://localhost/test")
+            .unwrap();
+        let store = MySqlStore::from_pool(pool);
+        store.close().await;
+
+        let err = store.upsert(PostDescriptor, post()).await.unwrap_err();
+        assert!(is_unsupported(err, "Children field into an upsert"));
+        let err = store.save(PostDescriptor, post()).await.unwrap_err();
+        assert!(is_unsupported(err, "Children field into an upsert"));
+
+        let mut stream = Box::pin(store.get_stream::<Post, _, _>(PostDescriptor, Filter::new()));
+        let err = futures::StreamExt::next(&mut stream)
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(is_unsupported(err, "Children field into a stream"));
+        assert!(futures::StreamExt::next(&mut stream).await.is_none());
+
+        let err = store
+            .insert_many(PostDescriptor, vec![post()])
+            .await
+            .unwrap_err();
+        assert!(is_unsupported(
+            err,
+            "Children field into a multi-row insert"
+        ));
+        let err = store
+            .insert_batched(PostDescriptor, vec![post(), post()])
+            .await
+            .unwrap_err();
+        assert!(is_unsupported(
+            err,
+            "Children field into a multi-row insert"
+        ));
+        let err = store
+            .insert_returning_id(PostDescriptor, post())
+            .await
+            .unwrap_err();
+        assert!(is_unsupported(
+            err,
+            "Children field into insert_returning_id"
+        ));
+        let err = store
+            .update(PostDescriptor, post(), Filter::new().eq("id", &1))
+            .await
+            .unwrap_err();
+        assert!(is_unsupported(err, "Children field into an update"));
+
+        let err = store
+            .get_columns::<Post, _>(PostDescriptor, &["id"])
+            .await
+            .unwrap_err();
+        assert!(is_unsupported(
+            err,
+            "Children field into a column selection"
+        ));
+        let err = store
+            .get_distinct::<Post, _>(PostDescriptor, &["id"])
+            .await
+            .unwrap_err();
+        assert!(is_unsupported(
+            err,
+            "Children field into a column selection"
+        ));
+    }
+
+    #[test]
+    fn test_writer_create_varchar() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i32>("id").unwrap();
+        writer.write_field::<Varchar<255>>("name").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,name VARCHAR(255) NOT NULL)"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_writer_create_datetime() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i32>("id").unwrap();
+        writer
+            .write_field::<chrono::NaiveDateTime>("created")
+            .unwrap();
+        writer
+            .write_field::<chrono::DateTime<chrono::Utc>>("updated")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id INT NOT NULL,created DATETIME(6) NOT NULL,updated DATETIME(6) NOT NULL)"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_writer_create_as_timestamp() {
+        use crate::{AsTimestamp, CreatedAt};
+        use chrono::{DateTime, NaiveDateTime, Utc};
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<AsTimestamp<NaiveDateTime>>("a")
+            .unwrap();
+        writer
+            .write_field::<Option<AsTimestamp<DateTime<Utc>>>>("b")
+            .unwrap();
+        writer
+            .write_field::<CreatedAt<AsTimestamp<DateTime<Utc>>>>("created_at")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a TIMESTAMP(6) NOT NULL,b TIMESTAMP(6),\
+             created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6))"
+        );
+
+        let a = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &AsTimestamp(a));
+        write!(writer, "b", &Some(AsTimestamp(a.and_utc())));
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b) VALUES ('2024-01-02 03:04:05','2024-01-02 03:04:05')"
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_writer_create_as_timestamp_time() {
+        use crate::AsTimestamp;
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<time::OffsetDateTime>("a").unwrap();
+        writer
+            .write_field::<AsTimestamp<time::OffsetDateTime>>("b")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a DATETIME(6) NOT NULL,b TIMESTAMP(6) NOT NULL)"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_writer_timestamp_columns() {
+        use crate::{CreatedAt, UpdatedAt};
+        use chrono::NaiveDateTime;
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<CreatedAt<NaiveDateTime>>("created_at")
+            .unwrap();
+        writer
+            .write_field::<UpdatedAt<NaiveDateTime>>("updated_at")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (\
+             created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),\
+             updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) \
+             ON UPDATE CURRENT_TIMESTAMP(6))"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &1_i32);
+        write!(writer, "created_at", &CreatedAt::<NaiveDateTime>::default());
+        write!(writer, "updated_at", &UpdatedAt::<NaiveDateTime>::default());
+
+        assert_eq!(writer.sql(), "INSERT INTO test (id) VALUES (1)");
+
+        let created_at =
+            NaiveDateTime::parse_from_str("2022-01-02 03:04:05", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Update);
+        write!(writer, "created_at", &CreatedAt::new(created_at));
+        write!(writer, "updated_at", &UpdatedAt::<NaiveDateTime>::default());
+
+        assert_eq!(
+            writer.sql(),
+            "UPDATE test SET created_at = '2022-01-02 03:04:05'"
+        );
+    }
+
+    #[test]
+    fn test_writer_defaulted() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Defaulted<i64>>("credits").unwrap();
+        writer
+            .write_field::<Defaulted<Option<i64>>>("limit")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (credits BIGINT NOT NULL,`limit` BIGINT)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &1_i32);
+        write!(writer, "credits", &Defaulted::<i64>::default());
+        write!(writer, "limit", &Defaulted::new(None::<i64>));
+        write!(writer, "note", &None::<String>);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (id,`limit`,note) VALUES (1,NULL,NULL)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "credits", &Defaulted::new(5_i64));
+
+        assert_eq!(writer.sql(), "INSERT INTO test (credits) VALUES (5)");
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn test_writer_bytes() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<bytes::Bytes>("a").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a BLOB NOT NULL)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &bytes::Bytes::from_static(&[0, 1, 255]));
+
+        assert_eq!(writer.sql(), "INSERT INTO test (a) VALUES (0x0001ff)");
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_writer_create_uuid() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<uuid::Uuid>("id").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (id BINARY(16) NOT NULL)"
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_writer_create_decimal() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer
+            .write_field::<rust_decimal::Decimal>("price")
+            .unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (price DECIMAL(65,30) NOT NULL)"
+        );
+    }
+
+    #[test]
+    fn test_create_index() {
+        let query = crate::Query::create_index(
+            "test",
+            String::from("idx_name"),
+            vec![(String::from("name"), None)],
+            false,
+        );
+
+        assert_eq!(query.to_string(), "CREATE INDEX idx_name ON test (name)");
+
+        let query = crate::Query::create_index(
+            "test",
+            String::from("idx_id_name"),
+            vec![(String::from("id"), None), (String::from("name"), None)],
+            true,
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE UNIQUE INDEX idx_id_name ON test (id,name)"
+        );
+
+        let query = crate::Query::create_index(
+            "test",
+            String::from("idx_id_body"),
+            vec![
+                (String::from("id"), None),
+                (String::from("body"), Some(255)),
+            ],
+            false,
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "CREATE INDEX idx_id_body ON test (id,body(255))"
+        );
+
+        let query = crate::Query::index_exists("test", String::from("idx_name"));
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT EXISTS(SELECT 1 FROM information_schema.statistics WHERE table_schema = DATABASE() AND table_name = 'test' AND index_name = 'idx_name')"
+        );
+    }
+
+    #[test]
+    fn test_writer_delete() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Delete);
+        writer.write_conditions = true;
+        write!(writer, "id", &3_i32);
+
+        assert_eq!(writer.sql(), "DELETE FROM test WHERE id = 3");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Delete);
+        writer.write_conditions = true;
+        write!(writer, "id", &3_i32);
+        write!(writer, "name", "hello");
+
+        assert_eq!(
+            writer.sql(),
+            "DELETE FROM test WHERE id = 3 AND name = 'hello'"
+        );
+    }
+
+    #[test]
+    fn test_delete_limited() {
+        let query = super::delete_limited(
+            &TestDescriptor,
+            &TestQuery::default().name(String::from("hello")),
+            100,
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "DELETE FROM test WHERE name = 'hello' LIMIT 100"
+        );
+    }
+
+    #[test]
+    fn test_writer_drop() {
+        let writer = MySqlWriter::new("test", QueryKind::Drop);
+
+        assert_eq!(writer.sql(), "DROP TABLE IF EXISTS test");
+    }
+
+    #[test]
+    fn test_writer_exists() {
+        let writer = MySqlWriter::new("test", QueryKind::Exists);
+
+        assert_eq!(writer.sql(), "SELECT EXISTS(SELECT 1 FROM test)");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Exists);
+        writer.write_conditions = true;
+        write!(writer, "id", &3_i32);
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT EXISTS(SELECT 1 FROM test WHERE id = 3)"
+        );
+    }
+
+    #[test]
+    fn test_writer_insert() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &3_i32);
+
+        assert_eq!(writer.sql(), "INSERT INTO test (id) VALUES (3)");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &3_i32);
+        write!(writer, "name", "hello");
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (id,name) VALUES (3,'hello')"
+        );
+    }
+
+    #[test]
+    fn test_writer_nested_fields() {
+        // Writes the fields `tag` and `note` before its own value.
+        struct Tagged(i32);
+
+        impl Write<MySqlStore> for Tagged {
+            fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+            where
+                W: Writer<MySqlStore>,
+            {
+                writer.write_field("tag", "a")?;
+                writer.write_field("note", "b")?;
+                writer.write_i32(self.0)
+            }
+
+            fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+            where
+                W: TypeWriter<MySqlStore>,
+            {
+                writer.write_field::<str>("tag")?;
+                writer.write_field::<str>("note")?;
+                writer.write_i32()
+            }
+        }
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "value", &Tagged(3));
+        write!(writer, "id", &1_i32);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (tag,note,value,id) VALUES ('a','b',3,1)"
+        );
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<Tagged>("value").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (tag TEXT NOT NULL,note TEXT NOT NULL,value INT NOT NULL)"
+        );
+    }
+
+    #[test]
+    fn test_column_value_mismatch() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &1_i32);
+        write!(writer, "b", &2_i32);
+        assert!(writer.query.column_value_mismatch().is_none());
+
+        if let QueryInner::Insert { values, .. } = &mut writer.query.inner {
+            values[0].pop();
+        }
+        assert_eq!(writer.sql(), "INSERT INTO test (a,b) VALUES (1)");
+        assert!(matches!(
+            writer.query.column_value_mismatch(),
+            Some(Error::ColumnValueMismatch {
+                columns: 2,
+                values: 1
+            })
+        ));
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Update);
+        write!(writer, "a", &1_i32);
+        if let QueryInner::Update { columns, .. } = &mut writer.query.inner {
+            columns.push(String::from("b"));
+        }
+        assert!(matches!(
+            writer.query.column_value_mismatch(),
+            Some(Error::ColumnValueMismatch {
+                columns: 2,
+                values: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_writer_insert_many() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &3_i32);
+        write!(writer, "name", "hello");
+
+        let mut other = MySqlWriter::new("test", QueryKind::Insert);
+        write!(other, "id", &4_i32);
+        write!(other, "name", "world");
+
+        assert!(writer.query.append(other.query));
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (id,name) VALUES (3,'hello'),(4,'world')"
+        );
+
+        let mut other = MySqlWriter::new("test", QueryKind::Insert);
+        write!(other, "name", "world");
+        write!(other, "id", &4_i32);
+
+        assert!(!writer.query.append(other.query));
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (id,name) VALUES (3,'hello'),(4,'world')"
+        );
+    }
+
+    #[test]
+    fn test_writer_insert_i128() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &i128::MIN);
+        write!(writer, "b", &u128::MAX);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b) VALUES (-170141183460469231731687303715884105728,340282366920938463463374607431768211455)"
+        );
+    }
+
+    #[test]
+    fn test_writer_integer_bounds() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &u64::MAX);
+        write!(writer, "b", &i64::MIN);
+        write!(writer, "c", &u32::MAX);
+        write!(writer, "d", &i8::MIN);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c,d) VALUES (18446744073709551615,-9223372036854775808,4294967295,-128)"
+        );
+
+        let filter = Filter::new().gt("a", &-5_i64).le("b", &u64::MAX);
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "a", i64);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT a FROM test WHERE a > -5 AND b <= 18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn test_writer_insert_float() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &1.5_f64);
+        write!(writer, "b", &-0.0_f64);
+        write!(writer, "c", &f64::NAN);
+        write!(writer, "d", &f64::INFINITY);
+        write!(writer, "e", &f32::NEG_INFINITY);
+        write!(writer, "f", &-0.0_f32);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c,d,e,f) VALUES (1.5E0,-0E0,NULL,NULL,NULL,-0E0)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Delete);
+        writer.write_conditions = true;
+        write!(writer, "a", &f64::NAN);
+
+        assert_eq!(writer.sql(), "DELETE FROM test WHERE a IS NULL");
+    }
+
+    #[test]
+    fn test_writer_fixed_decimal() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<FixedDecimal<10, 2>>("price").unwrap();
+        writer.write_field::<f32>("a").unwrap();
+        writer.write_field::<f64>("b").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (price DECIMAL(10,2) NOT NULL,a FLOAT NOT NULL,b DOUBLE NOT NULL)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "price", &FixedDecimal::<10, 2>(19.99));
+        write!(writer, "nan", &FixedDecimal::<10, 2>(f64::NAN));
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (price,nan) VALUES (19.99E0,NULL)"
+        );
+    }
+
+    #[test]
+    fn test_writer_insert_option() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &Some(3_i32));
+        write!(writer, "name", &None::<String>);
+
+        assert_eq!(writer.sql(), "INSERT INTO test (id,name) VALUES (3,NULL)");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_writer_insert_datetime() {
+        let created = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "created", &created);
+        write!(writer, "updated", &created.and_utc());
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (created,updated) VALUES ('2024-01-02 03:04:05','2024-01-02 03:04:05')"
+        );
+
+        let created = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_micro_opt(3, 4, 5, 6)
+            .unwrap();
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "created", &created);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (created) VALUES ('2024-01-02 03:04:05.000006')"
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_writer_time() {
+        use time::{Date, Month, Time, UtcOffset};
+
+        let date = Date::from_calendar_date(2024, Month::January, 2).unwrap();
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<time::OffsetDateTime>("a").unwrap();
+        writer.write_field::<Date>("b").unwrap();
+        writer.write_field::<Option<Time>>("c").unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "CREATE TABLE IF NOT EXISTS test (a DATETIME(6) NOT NULL,b DATE NOT NULL,c TIME(6))"
+        );
+
+        let a = date
+            .with_hms(3, 4, 5)
+            .unwrap()
+            .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+        let b = date
+            .previous_day()
+            .unwrap()
+            .with_hms_micro(23, 30, 0, 6)
+            .unwrap()
+            .assume_offset(UtcOffset::from_hms(-1, 0, 0).unwrap());
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &a);
+        write!(writer, "b", &b);
+        write!(writer, "c", &date);
+        write!(writer, "d", &Time::from_hms_milli(3, 4, 5, 500).unwrap());
+        write!(
+            writer,
+            "e",
+            &Time::from_hms_nano(3, 4, 5, 123_456_789).unwrap()
+        );
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (a,b,c,d,e) VALUES ('2024-01-02 01:04:05','2024-01-02 00:30:00.000006','2024-01-02','03:04:05.500','03:04:05.123456789')"
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_writer_insert_decimal() {
+        use std::str::FromStr;
+
+        let price = rust_decimal::Decimal::from_str("-1234.5678901234567890123456").unwrap();
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "price", &price);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (price) VALUES (-1234.5678901234567890123456)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Delete);
+        writer.write_conditions = true;
+        write!(writer, "price", &rust_decimal::Decimal::new(15, 1));
+
+        assert_eq!(writer.sql(), "DELETE FROM test WHERE price = 1.5");
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_writer_uuid() {
+        let id = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &id);
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (id) VALUES (0x67e5504410b1426f9247bb680e5fe0c8)"
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", uuid::Uuid);
+        writer.write_conditions = true;
+        write!(writer, "id", &id);
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE id = 0x67e5504410b1426f9247bb680e5fe0c8"
+        );
+    }
+
+    #[test]
+    fn test_writer_upsert() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &3_i32);
+        write!(writer, "name", "hello");
+        writer.query.upsert();
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (id,name) VALUES (3,'hello') ON DUPLICATE KEY UPDATE id=VALUES(id),name=VALUES(name)"
+        );
+    }
+
+    #[test]
+    fn test_writer_upsert_returning_id() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &PrimaryKey(AutoIncrement(0_i32)));
+        write!(writer, "name", "hello");
+        writer.query.upsert_returning_id();
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (id,name) VALUES (0,'hello') ON DUPLICATE KEY UPDATE name=VALUES(name),id=LAST_INSERT_ID(id)"
+        );
+
+        // Without an `AUTO_INCREMENT` column all columns are updated.
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &0_i32);
+        write!(writer, "name", "hello");
+        writer.query.upsert_returning_id();
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT INTO test (id,name) VALUES (0,'hello') ON DUPLICATE KEY UPDATE id=VALUES(id),name=VALUES(name)"
+        );
+    }
+
+    #[test]
+    fn test_first_query_error() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "data", &vec![0_u8; 65536]);
+        write!(writer, "data", &1_i32);
+        assert!(matches!(
+            writer.query.validate(),
+            Err(Error::ValueTooLarge {
+                len: 65536,
+                max: 65535
+            })
+        ));
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        assert!(matches!(writer.query.validate(), Err(Error::NoColumns)));
+    }
+
+    #[test]
+    fn test_duplicate_column() {
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i32>("id").unwrap();
+        assert!(writer.query.take_error().is_none());
+
+        writer.write_field::<i64>("id").unwrap();
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::DuplicateColumn { name }) if name == "id"
+        ));
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &1_i32);
+        write!(writer, "id", &2_i32);
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::DuplicateColumn { name }) if name == "id"
+        ));
+
+        // Conditions may compare the same column more than once.
+        let mut query = super::select(
+            &TestDescriptor,
+            &Filter::new().gt("id", &1_i32).lt("id", &5_i32),
+        );
+        assert!(query.take_error().is_none());
+    }
+
+    #[test]
+    fn test_unsupported_operation() {
+        let filter = Filter::new().eq("id", &1_i32);
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &1_i32);
+        assert!(writer.query.take_error().is_none());
+
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::UnsupportedOperation {
+                operation: "condition",
+                kind: "INSERT"
+            })
+        ));
+
+        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
+        writer.write_field::<i32>("id").unwrap();
+        writer.query.push_conditions(filter.conditions().clone());
+        assert!(matches!(
+            writer.query.take_error(),
+            Some(Error::UnsupportedOperation {
+                operation: "condition",
+                kind: "CREATE"
+            })
+        ));
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Delete);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
+        assert!(writer.query.take_error().is_none());
+
+        writer.write_conditions = false;
+        write!(writer, "id", &1_i32);
+        let err = writer.query.take_error().unwrap();
+        assert!(matches!(
+            err,
+            Error::UnsupportedOperation {
+                operation: "column value",
+                kind: "DELETE"
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "DELETE queries don't support writing a column value"
+        );
+    }
+
+    #[test]
+    fn test_writer_replace() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &3_i32);
+        write!(writer, "name", "hello");
+        writer.query.replace();
+
+        assert_eq!(
+            writer.sql(),
+            "REPLACE INTO test (id,name) VALUES (3,'hello')"
+        );
+    }
+
+    #[test]
+    fn test_writer_insert_ignore() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &3_i32);
+        write!(writer, "name", "hello");
+        writer.query.ignore();
+
+        assert_eq!(
+            writer.sql(),
+            "INSERT IGNORE INTO test (id,name) VALUES (3,'hello')"
+        );
+    }
+
+    #[test]
+    fn test_writer_select() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+
+        assert_eq!(writer.sql(), "SELECT id FROM test");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        write_type!(writer, "name", str);
+
+        assert_eq!(writer.sql(), "SELECT id,name FROM test");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        write_type!(writer, "name", str);
+        writer.write_conditions = true;
+        write!(writer, "id", &3_i32);
+
+        assert_eq!(writer.sql(), "SELECT id,name FROM test WHERE id = 3");
+    }
+
+    #[test]
+    fn test_writer_select_bool() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "active", bool);
+        writer.write_conditions = true;
+        write!(writer, "active", &true);
+
+        assert_eq!(writer.sql(), "SELECT active FROM test WHERE active = TRUE");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "active", bool);
+        writer.write_conditions = true;
+        write!(writer, "active", &false);
+
+        assert_eq!(writer.sql(), "SELECT active FROM test WHERE active = FALSE");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &true);
+        write!(writer, "b", &false);
+
+        assert_eq!(writer.sql(), "INSERT INTO test (a,b) VALUES (TRUE,FALSE)");
+    }
+
+    #[test]
+    fn test_writer_truncate() {
+        let writer = MySqlWriter::new("test", QueryKind::Truncate);
+
+        assert_eq!(writer.sql(), "TRUNCATE TABLE test");
+    }
+
+    #[test]
+    fn test_writer_update() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Update);
+        write!(writer, "name", "hello");
+        writer.write_conditions = true;
+        write!(writer, "id", &3_i32);
+
+        assert_eq!(writer.sql(), "UPDATE test SET name = 'hello' WHERE id = 3");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Update);
+        write!(writer, "id", &3_i32);
+        write!(writer, "name", "hello");
+
+        assert_eq!(writer.sql(), "UPDATE test SET id = 3,name = 'hello'");
+    }
+
+    #[test]
+    fn test_update_fields() {
+        let query = super::update_fields(
+            &TestDescriptor,
+            &[("name", Value::from("it's")), ("id", Value::Null)],
+            &Filter::new().eq("id", &3),
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "UPDATE test SET name = 'it''s',id = NULL WHERE id = 3"
+        );
+
+        let query = super::update_fields(&TestDescriptor, &[], &Filter::new());
+        assert!(query.missing_columns());
+    }
+
+    #[test]
+    fn test_prepare() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &1_i32);
+        write!(writer, "name", "it's");
+        write!(writer, "price", &1.5_f64);
+        write!(writer, "data", &vec![1_u8, 2]);
+        write!(writer, "active", &true);
+        write!(writer, "deleted", &None::<i32>);
+
+        let sql = "INSERT INTO test (id,name,price,data,active,deleted) VALUES (?,?,?,?,?,?)";
+        assert_eq!(
+            writer.query.prepare(),
+            (
+                String::from(sql),
+                vec![
+                    Value::Int(1),
+                    Value::from("it's"),
+                    Value::Float(1.5),
+                    Value::Bytes(vec![1, 2]),
+                    Value::Bool(true),
+                    Value::Null,
+                ]
+            )
+        );
+
+        // Other values of the same columns share the sql.
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "id", &u64::MAX);
+        write!(writer, "name", "");
+        write!(writer, "price", &-2_f64);
+        write!(writer, "data", &Vec::<u8>::new());
+        write!(writer, "active", &false);
+        write!(writer, "deleted", &Some(3_i32));
+
+        assert_eq!(writer.query.prepare().0, sql);
+
+        // The values of the `SET` clause come before the conditions.
+        let mut writer = MySqlWriter::new("test", QueryKind::Update);
+        write!(writer, "name", "hello");
+        writer.write_conditions = true;
+        write!(writer, "id", &3_i32);
+
+        assert_eq!(
+            writer.query.prepare(),
+            (
+                String::from("UPDATE test SET name = ? WHERE id = ?"),
+                vec![Value::from("hello"), Value::Int(3)]
+            )
+        );
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        let filter = Filter::new()
+            .between("id", &1, &5)
+            .is_in("name", &["a", "b"]);
+        writer.query.push_conditions(filter.conditions().clone());
+        writer.query.limit(10);
+        writer.query.offset(20);
+
+        assert_eq!(
+            writer.query.prepare(),
+            (
+                String::from(
+                    "SELECT id FROM test WHERE id BETWEEN ? AND ? AND name IN (?,?) LIMIT ? OFFSET ?"
+                ),
+                vec![
+                    Value::Int(1),
+                    Value::Int(5),
+                    Value::from("a"),
+                    Value::from("b"),
+                    Value::Int(10),
+                    Value::Int(20),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_prepare_inline() {
+        // Literals that can't be bound without changing their meaning stay in the sql.
+        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
+        write!(writer, "a", &i128::MAX);
+        write!(writer, "b", &Point { x: 1.5, y: -2.0 });
+        write!(writer, "c", &1_i32);
+
+        assert_eq!(
+            writer.query.prepare(),
+            (
+                String::from("INSERT INTO test (a,b,c) VALUES (170141183460469231731687303715884105727,POINT(1.5, -2),?)"),
+                vec![Value::Int(1)]
+            )
+        );
+
+        // Raw queries already bind their values.
+        let query = Query::raw(String::from("SELECT ? + 1"), vec![Value::Int(1)]);
+        assert_eq!(
+            query.prepare(),
+            (String::from("SELECT ? + 1"), vec![Value::Int(1)])
+        );
+
+        // MySQL accepts at most 65535 placeholders in a statement.
+        let mut query = Query::new("test", QueryKind::Delete);
+        query.push_condition(Condition::in_list(
+            String::from("id"),
+            vec![String::from("1"); 65536],
+        ));
+        assert_eq!(query.prepare(), (query.to_string(), Vec::new()));
+    }
+
+    #[test]
+    fn test_value_from_literal() {
+        for value in [
+            Value::Null,
+            Value::Bool(true),
+            Value::Bool(false),
+            Value::Int(i64::MIN),
+            Value::Int(0),
+            Value::UInt(u64::MAX),
+            Value::Float(-0.5),
+            Value::Float(1e300),
+            Value::from("it's a \\ 'quote'"),
+            Value::from(""),
+            Value::Bytes(vec![0, 255]),
+        ] {
+            assert_eq!(Value::from_literal(&value.to_literal()), Some(value));
+        }
+
+        for literal in [
+            "19.99",
+            "18446744073709551616",
+            "007",
+            "-",
+            "1.E0",
+            "'a\\nb'",
+            "'it's'",
+            "0x1",
+            "0x+f",
+            "b'101'",
+            "POINT(1, 2)",
+            "LAST_INSERT_ID()",
+        ] {
+            assert_eq!(Value::from_literal(literal), None, "{}", literal);
+        }
+    }
+
+    #[test]
+    fn test_update_exprs() {
+        let query = super::update_exprs(
+            &TestDescriptor,
+            &[
+                ("id", UpdateExpr::Increment(Value::from(1))),
+                ("order", UpdateExpr::Decrement(Value::from(2))),
+                ("a", UpdateExpr::Greatest(Value::from(0))),
+                ("b", UpdateExpr::Least(Value::from("it's"))),
+                ("name", UpdateExpr::from(Value::Null)),
+            ],
+            &Filter::new().eq("id", &3),
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "UPDATE test SET id = id + 1,`order` = `order` - 2,a = GREATEST(a,0),b = LEAST(b,'it''s'),name = NULL WHERE id = 3"
+        );
+    }
+
+    #[test]
+    fn test_select_one() {
+        let query = super::select_one(
+            &TestDescriptor,
+            &TestQuery::default().name(String::from("hello")),
+        );
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test WHERE name = 'hello' LIMIT 1"
+        );
+    }
+
+    #[test]
+    fn test_dialect() {
+        use crate::dialect::{Dialect, MySqlDialect};
+
+        assert_eq!(MySqlDialect.quote_ident("name"), "name");
+        assert_eq!(MySqlDialect.quote_ident("order"), "`order`");
+        assert_eq!(MySqlDialect.quote_ident("first name"), "`first name`");
+        assert_eq!(MySqlDialect.quote_ident("a`; DROP"), "`a``; DROP`");
+        assert_eq!(MySqlDialect.quote_ident("123"), "`123`");
+        assert_eq!(MySqlDialect.quote_string_literal("it's"), "'it''s'");
+
+        let query = super::select(
+            &TestDescriptor,
+            &Filter::new().eq("order", &1_i32).eq("a`b", "c"),
+        );
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test WHERE `order` = 1 AND `a``b` = 'c'"
+        );
+    }
+
+    #[test]
+    fn test_select_top() {
+        let query = super::select_top(
+            &TestDescriptor,
+            &Filter::new().eq("name", "a"),
+            "id",
+            Order::Desc,
+            5,
+        );
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test WHERE name = 'a' ORDER BY id DESC LIMIT 5"
+        );
+    }
+
+    #[test]
+    fn test_select_ordered() {
+        let query = super::select_ordered(
+            &TestDescriptor,
+            &Filter::new(),
+            &[OrderBy::desc("name").nulls_last(), OrderBy::asc("id")],
+            Some(5),
+        );
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test ORDER BY (name IS NULL),name DESC,id ASC LIMIT 5"
+        );
+
+        let query = super::select_ordered(
+            &TestDescriptor,
+            &Filter::new(),
+            &[OrderBy::asc("order").nulls_first(), OrderBy::desc("id")],
+            None,
+        );
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test ORDER BY (`order` IS NULL) DESC,`order` ASC,id DESC"
+        );
+
+        let query = super::select_ordered(&TestDescriptor, &Filter::new(), &[], None);
+        assert_eq!(query.to_string(), "SELECT id,name FROM test");
+    }
+
+    #[test]
+    fn test_select_for_update() {
+        let mut query = super::select(&TestDescriptor, &Filter::new().eq("id", &1));
+        query.limit(1);
+        query.for_update();
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test WHERE id = 1 LIMIT 1 FOR UPDATE"
+        );
+    }
+
+    #[test]
+    fn test_query_builder() {
+        let query = crate::query::Query::select("test")
+            .columns(&["id", "order"])
+            .distinct()
+            .where_eq("name", "Robb")
+            .filter(
+                Filter::new()
+                    .gt("id", &3)
+                    .or(Filter::new().is_null("order")),
+            )
+            .order_by(OrderBy::desc("id"))
+            .limit(10)
+            .offset(20);
+        assert_eq!(
+            query.to_string(),
+            "SELECT DISTINCT id,`order` FROM test WHERE name = 'Robb' AND ((id > 3) OR (`order` IS NULL)) ORDER BY id DESC LIMIT 10 OFFSET 20"
+        );
+
+        let query = crate::query::Query::select("test");
+        assert_eq!(query.to_string(), "SELECT * FROM test");
+    }
+
+    #[test]
+    fn test_select_after() {
+        let query = super::select_after(&TestDescriptor, "id", None::<&i32>, 10);
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test ORDER BY id ASC LIMIT 10"
+        );
+
+        let query = super::select_after(&TestDescriptor, "id", Some(&20_i32), 10);
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test WHERE id > 20 ORDER BY id ASC LIMIT 10"
+        );
+
+        let query = super::select_after(&TestDescriptor, "name", Some("O'Reilly"), 10);
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test WHERE name > 'O''Reilly' ORDER BY name ASC LIMIT 10"
+        );
+    }
+
+    #[test]
+    fn test_select_in() {
+        let query = super::select_in(&TestDescriptor, "id", &[1_i32, 2, 3]);
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT id,name FROM test WHERE id IN (1,2,3)"
+        );
+
+        let ids: Vec<i32> = (0..2500).collect();
+        let chunks: Vec<_> = ids
+            .chunks(super::IDS_PER_QUERY)
+            .map(|ids| super::select_in(&TestDescriptor, "id", ids).to_string())
+            .collect();
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[1].contains("WHERE id IN (1000,1001,"));
+        assert!(chunks[1].ends_with(",1999)"));
+        assert!(chunks[2].contains("WHERE id IN (2000,2001,"));
+        assert!(chunks[2].ends_with(",2499)"));
+    }
+
+    #[test]
+    fn test_delete_in() {
+        let query = super::delete_in(&TestDescriptor, "id", &[1_i32, 2, 3]);
+
+        assert_eq!(query.to_string(), "DELETE FROM test WHERE id IN (1,2,3)");
+        assert!(query.has_conditions());
+    }
+
+    #[test]
+    fn test_delete_always_matches() {
+        let always = [
+            Filter::new(),
+            Filter::new().or(Filter::new()),
+            Filter::new().eq("id", &1_i32).or(Filter::new()),
+            Filter::new().not_in::<i32>("id", &[]),
+        ];
+        for filter in &always {
+            assert!(!super::delete(&TestDescriptor, filter).has_conditions());
+        }
+
+        let conditions = [
+            Filter::new()
+                .eq("id", &1_i32)
+                .or(Filter::new().eq("id", &2_i32)),
+            Filter::new().not_in::<i32>("id", &[]).eq("id", &1_i32),
+            Filter::new().is_in::<i32>("id", &[]),
+        ];
+        for filter in &conditions {
+            assert!(super::delete(&TestDescriptor, filter).has_conditions());
+        }
+    }
+
+    #[test]
+    fn test_raw() {
+        let query = Query::raw(
+            String::from("SELECT id FROM test WHERE name = ?"),
+            vec![Value::from("Robb")],
+        );
+
+        assert_eq!(query.to_string(), "SELECT id FROM test WHERE name = ?");
+        assert_eq!(query.kind(), "RAW");
+
+        assert_eq!(Value::from(3_u8), Value::UInt(3));
+        assert_eq!(Value::from(None::<i32>), Value::Null);
+        assert_eq!(Value::from(Some(-3_i32)), Value::Int(-3));
+    }
+
+    #[test]
+    fn test_select_columns() {
+        let query = super::select_columns(&TestDescriptor, &["name", "unknown"]);
+
+        assert_eq!(query.to_string(), "SELECT name FROM test");
+    }
+
+    #[test]
+    fn test_select_distinct() {
+        let query = super::select_distinct(&TestDescriptor, &["name"]);
+
+        assert_eq!(query.to_string(), "SELECT DISTINCT name FROM test");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "name", String);
+        writer.write_conditions = true;
+        write!(writer, "id", &3_i32);
+        writer.query.distinct();
+        writer.query.order_by(String::from("name"), Order::Asc);
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT DISTINCT name FROM test WHERE id = 3 ORDER BY name ASC"
+        );
+    }
+
+    #[test]
+    fn test_writer_select_between() {
+        let filter = Filter::new().between("id", &1_i32, &10_i32);
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE id BETWEEN 1 AND 10"
+        );
+
+        let filter = Filter::new().between("name", "a", "n'z");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE name BETWEEN 'a' AND 'n''z'"
+        );
+    }
+
+    #[test]
+    fn test_writer_select_or() {
+        let filter = Filter::new()
+            .eq("id", &1_i32)
+            .eq("name", "hello")
+            .or(Filter::new().eq("id", &2_i32))
+            .eq("name", "world");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn write_bool(&mut self) -> Result<(), Self::Error> {
-        self.write("BOOLEAN")
-    }
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE ((id = 1 AND name = 'hello') OR (id = 2)) AND name = 'world'"
+        );
 
-    fn write_i8(&mut self) -> Result<(), Self::Error> {
-        self.write("TINYINT")
-    }
+        let filter = Filter::new().or(Filter::new().eq("id", &2_i32));
 
-    fn write_i16(&mut self) -> Result<(), Self::Error> {
-        self.write("SMALLINT")
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn write_i32(&mut self) -> Result<(), Self::Error> {
-        self.write("INT")
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE ((TRUE) OR (id = 2))"
+        );
     }
 
-    fn write_i64(&mut self) -> Result<(), Self::Error> {
-        self.write("BIGINT")
-    }
+    #[test]
+    fn test_writer_select_null() {
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        write!(writer, "name", &None::<String>);
 
-    fn write_u8(&mut self) -> Result<(), Self::Error> {
-        self.write("TINYINT UNSIGNED")
-    }
+        assert_eq!(writer.sql(), "SELECT id FROM test WHERE name IS NULL");
 
-    fn write_u16(&mut self) -> Result<(), Self::Error> {
-        self.write("SMALLINT UNSIGNED")
-    }
+        let filter = Filter::new().eq("name", &None::<String>).is_not_null("id");
 
-    fn write_u32(&mut self) -> Result<(), Self::Error> {
-        self.write("INT UNSIGNED")
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn write_u64(&mut self) -> Result<(), Self::Error> {
-        self.write("BIGINT UNSIGNED")
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE name IS NULL AND id IS NOT NULL"
+        );
     }
 
-    fn write_f32(&mut self) -> Result<(), Self::Error> {
-        self.write("FLOAT")
-    }
+    #[test]
+    fn test_writer_select_filter() {
+        let filter = Filter::new().is_in("id", &[1_i32, 2, 3]);
 
-    fn write_f64(&mut self) -> Result<(), Self::Error> {
-        self.write("DOUBLE")
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn write_bytes(&mut self) -> Result<(), Self::Error> {
-        self.write("BLOB")
-    }
+        assert_eq!(writer.sql(), "SELECT id FROM test WHERE id IN (1,2,3)");
 
-    fn write_str(&mut self) -> Result<(), Self::Error> {
-        self.write("TEXT")
-    }
+        let filter = Filter::new().eq("name", "hello").is_in::<i32>("id", &[]);
 
-    fn write_field<T>(&mut self, key: &'static str) -> Result<(), Self::Error>
-    where
-        T: ?Sized + Write<MySqlStore>,
-    {
-        self.key = key;
-        T::write_type(self)
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
+
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE name = 'hello' AND FALSE"
+        );
     }
-}
 
-struct MySqlReader {
-    row: MySqlRow,
-    column: Option<&'static str>,
-}
+    #[test]
+    fn test_writer_select_approx_eq() {
+        let filter = Filter::new().approx_eq("price", 0.5, 0.25);
 
-impl MySqlReader {
-    fn new(row: MySqlRow) -> Self {
-        Self { row, column: None }
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn read<'r, T>(&'r mut self) -> Result<T, <Self as Reader<MySqlStore>>::Error>
-    where
-        T: sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
-    {
-        self.row.try_get(self.column.unwrap())
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE price BETWEEN 0.25E0 AND 0.75E0"
+        );
     }
-}
 
-impl Reader<MySqlStore> for MySqlReader {
-    type Error = sqlx::Error;
+    #[test]
+    fn test_writer_select_not_in() {
+        let filter =
+            Filter::new().not_in("name", &[String::from("deleted"), String::from("banned")]);
 
-    fn read_bool(&mut self) -> Result<bool, Self::Error> {
-        self.read()
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn read_i8(&mut self) -> Result<i8, Self::Error> {
-        self.read()
-    }
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE name NOT IN ('deleted','banned')"
+        );
 
-    fn read_i16(&mut self) -> Result<i16, Self::Error> {
-        self.read()
-    }
+        let filter = Filter::new().eq("id", &3_i32).not_in::<String>("name", &[]);
 
-    fn read_i32(&mut self) -> Result<i32, Self::Error> {
-        self.read()
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn read_i64(&mut self) -> Result<i64, Self::Error> {
-        self.read()
+        assert_eq!(writer.sql(), "SELECT id FROM test WHERE id = 3 AND TRUE");
     }
 
-    fn read_u8(&mut self) -> Result<u8, Self::Error> {
-        self.read()
-    }
+    #[test]
+    fn test_writer_select_compare() {
+        let filter = Filter::new()
+            .gt("id", &1_i32)
+            .le("id", &10_i32)
+            .lt("name", "n")
+            .ge("name", "a");
 
-    fn read_u16(&mut self) -> Result<u16, Self::Error> {
-        self.read()
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn read_u32(&mut self) -> Result<u32, Self::Error> {
-        self.read()
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE id > 1 AND id <= 10 AND name < 'n' AND name >= 'a'"
+        );
     }
 
-    fn read_u64(&mut self) -> Result<u64, Self::Error> {
-        self.read()
-    }
+    #[test]
+    fn test_writer_select_column_cmp_column() {
+        let filter = Filter::new()
+            .column_cmp_column("updated_at", Comparison::Gt, "created_at")
+            .column_cmp_column("id", Comparison::Ne, "order")
+            .column_cmp_column("a", Comparison::Eq, "b'c");
 
-    fn read_f32(&mut self) -> Result<f32, Self::Error> {
-        self.read()
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn read_f64(&mut self) -> Result<f64, Self::Error> {
-        self.read()
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE updated_at > created_at AND id <> `order` AND a = `b'c`"
+        );
     }
 
-    fn read_byte_buf(&mut self) -> Result<Vec<u8>, Self::Error> {
-        self.read()
-    }
+    #[test]
+    fn test_writer_select_json_path() {
+        let filter = Filter::new()
+            .json_path("data", "$.status", Comparison::Eq, "active")
+            .json_path("data", "$.retries", Comparison::Ge, &3)
+            .json_path("order", "$.a' OR '1'='1", Comparison::Eq, "x");
 
-    fn read_string(&mut self) -> Result<String, Self::Error> {
-        self.read()
-    }
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-    fn read_field<T>(&mut self, key: &'static str) -> Result<T, Self::Error>
-    where
-        T: Sized + datastore::Read<MySqlStore>,
-    {
-        self.column = Some(key);
-        T::read(self)
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE data->>'$.status' = 'active' AND data->>'$.retries' >= 3 \
+             AND `order`->>'$.a'' OR ''1''=''1' = 'x'"
+        );
+
+        let query = crate::query::Query::select("test").where_json_path(
+            "data",
+            "$.tags[0]",
+            Comparison::Ne,
+            "new",
+        );
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM test WHERE data->>'$.tags[0]' <> 'new'"
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{MySqlStore, MySqlWriter};
-    use crate::{mysql::MySqlTypeWriter, QueryKind};
+    #[test]
+    fn test_select_dynamic() {
+        let query = super::select_dynamic("person", &Filter::new().gt("age", &30)).unwrap();
+        assert_eq!(query.to_string(), "SELECT * FROM person WHERE age > 30");
 
-    use datastore::{TypeWriter, Writer};
+        let query = super::select_dynamic("person", &Filter::new()).unwrap();
+        assert_eq!(query.to_string(), "SELECT * FROM person");
 
-    macro_rules! write {
-        ($writer:expr, $key:expr, $val:expr) => {
-            <MySqlWriter as Writer<MySqlStore>>::write_field(&mut $writer, $key, $val).unwrap();
-        };
+        assert!(matches!(
+            super::select_dynamic("person; DROP TABLE person", &Filter::new()),
+            Err(Error::InvalidTableName { .. })
+        ));
     }
 
-    macro_rules! write_type {
-        ($writer:expr, $key:expr, $val:ty) => {
-            <MySqlWriter as TypeWriter<MySqlStore>>::write_field::<$val>(&mut $writer, $key)
-                .unwrap();
-        };
+    #[test]
+    fn test_format_temporal() {
+        use crate::value::{format_datetime, format_time};
+
+        assert_eq!(format_datetime(&[0], false).unwrap(), "0000-00-00");
+        assert_eq!(
+            format_datetime(&[4, 0xe8, 0x07, 1, 31], false).unwrap(),
+            "2024-01-31"
+        );
+        assert_eq!(
+            format_datetime(&[4, 0xe8, 0x07, 1, 31], true).unwrap(),
+            "2024-01-31 00:00:00"
+        );
+        assert_eq!(
+            format_datetime(&[7, 0xe8, 0x07, 1, 31, 12, 30, 5], true).unwrap(),
+            "2024-01-31 12:30:05"
+        );
+        assert_eq!(
+            format_datetime(
+                &[11, 0xe8, 0x07, 1, 31, 12, 30, 5, 0x40, 0xe2, 0x01, 0],
+                true
+            )
+            .unwrap(),
+            "2024-01-31 12:30:05.123456"
+        );
+        assert!(format_datetime(&[4, 0xe8, 0x07], true).is_err());
+
+        assert_eq!(format_time(&[0]).unwrap(), "00:00:00");
+        assert_eq!(
+            format_time(&[8, 1, 34, 0, 0, 0, 22, 59, 59]).unwrap(),
+            "-838:59:59"
+        );
+        assert_eq!(
+            format_time(&[12, 0, 0, 0, 0, 0, 1, 2, 3, 1, 0, 0, 0]).unwrap(),
+            "01:02:03.000001"
+        );
     }
 
     #[test]
-    fn test_writer_create() {
-        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
-        writer.write_field::<i32>("id").unwrap();
-
-        assert_eq!(writer.sql(), "CREATE TABLE IF NOT EXISTS test (id INT)");
+    fn test_writer_select_match_against() {
+        let filter = Filter::new()
+            .match_against(&["title", "body"], "+rust -java", MatchMode::Boolean)
+            .match_against(&["desc"], "Robb's", MatchMode::NaturalLanguage)
+            .match_against(&["body"], "database", MatchMode::QueryExpansion);
 
-        let mut writer = MySqlTypeWriter::new("test", QueryKind::Create);
-        writer.write_field::<i32>("id").unwrap();
-        writer.write_field::<str>("name").unwrap();
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
         assert_eq!(
             writer.sql(),
-            "CREATE TABLE IF NOT EXISTS test (id INT,name TEXT)"
+            "SELECT id FROM test WHERE MATCH (title,body) AGAINST ('+rust -java' IN BOOLEAN MODE) AND MATCH (`desc`) AGAINST ('Robb''s' IN NATURAL LANGUAGE MODE) AND MATCH (body) AGAINST ('database' WITH QUERY EXPANSION)"
         );
     }
 
     #[test]
-    fn test_writer_delete() {
-        let mut writer = MySqlWriter::new("test", QueryKind::Delete);
+    fn test_writer_select_ordered_str_bytes() {
+        let filter = Filter::new()
+            .gt("name", "M")
+            .le("name", "O'Brien")
+            .ge("data", &vec![0xffu8])
+            .lt("data", &vec![0x01u8, 0xab])
+            .between("key", &vec![0x00u8], &vec![0x7fu8]);
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
         writer.write_conditions = true;
-        write!(writer, "id", &3_i32);
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-        assert_eq!(writer.sql(), "DELETE FROM test WHERE id = 3");
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE name > 'M' AND name <= 'O''Brien' AND data >= 0xff AND data < 0x01ab AND `key` BETWEEN 0x00 AND 0x7f"
+        );
 
-        let mut writer = MySqlWriter::new("test", QueryKind::Delete);
+        // The same literals as for equality conditions.
+        let filter = Filter::new()
+            .eq("name", "O'Brien")
+            .eq("data", &vec![0xffu8]);
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
         writer.write_conditions = true;
-        write!(writer, "id", &3_i32);
-        write!(writer, "name", "hello");
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
         assert_eq!(
             writer.sql(),
-            "DELETE FROM test WHERE id = 3 AND name = 'hello'"
+            "SELECT id FROM test WHERE name = 'O''Brien' AND data = 0xff"
         );
     }
 
     #[test]
-    fn test_writer_insert() {
-        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
-        write!(writer, "id", &3_i32);
+    fn test_writer_select_like() {
+        let filter = Filter::new().like("name", "Rob%");
 
-        assert_eq!(writer.sql(), "INSERT INTO test (id) VALUES (3)");
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        write_type!(writer, "name", str);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
-        let mut writer = MySqlWriter::new("test", QueryKind::Insert);
-        write!(writer, "id", &3_i32);
-        write!(writer, "name", "hello");
+        assert_eq!(
+            writer.sql(),
+            "SELECT id,name FROM test WHERE name LIKE 'Rob%'"
+        );
+
+        let filter = Filter::new().like("name", "O'Reilly\\_%");
+
+        let mut writer = MySqlWriter::new("test", QueryKind::Select);
+        write_type!(writer, "id", i32);
+        writer.write_conditions = true;
+        DataQuery::<Test, MySqlStore>::write(&filter, &mut writer).unwrap();
 
         assert_eq!(
             writer.sql(),
-            "INSERT INTO test (id,name) VALUES (3,'hello')"
+            "SELECT id FROM test WHERE name LIKE 'O''Reilly\\\\_%'"
         );
     }
 
     #[test]
-    fn test_writer_select() {
+    fn test_writer_select_limit() {
         let mut writer = MySqlWriter::new("test", QueryKind::Select);
         write_type!(writer, "id", i32);
+        writer.query.limit(10);
 
-        assert_eq!(writer.sql(), "SELECT id FROM test");
+        assert_eq!(writer.sql(), "SELECT id FROM test LIMIT 10");
 
         let mut writer = MySqlWriter::new("test", QueryKind::Select);
         write_type!(writer, "id", i32);
-        write_type!(writer, "name", str);
+        writer.query.limit(10);
+        writer.query.offset(20);
 
-        assert_eq!(writer.sql(), "SELECT id,name FROM test");
+        assert_eq!(writer.sql(), "SELECT id FROM test LIMIT 10 OFFSET 20");
 
         let mut writer = MySqlWriter::new("test", QueryKind::Select);
         write_type!(writer, "id", i32);
-        write_type!(writer, "name", str);
         writer.write_conditions = true;
         write!(writer, "id", &3_i32);
+        writer.query.offset(20);
 
-        assert_eq!(writer.sql(), "SELECT id,name FROM test WHERE id = 3");
+        assert_eq!(
+            writer.sql(),
+            "SELECT id FROM test WHERE id = 3 LIMIT 18446744073709551615 OFFSET 20"
+        );
     }
 }
@@ -0,0 +1,170 @@
+//! A builder for sql queries, reusing the sql generation of [`MySqlStore`].
+//!
+//! Most queries are built from the [`StoreData`] types passed to the store. [`Query`] builds
+//! the same sql for tables and columns only known at runtime. The sql of a [`Query`] is read
+//! using its `Display` impl and can be executed using [`MySqlStore::query_raw`] or
+//! [`MySqlStore::query_raw_as`].
+//!
+//! [`MySqlStore`]: crate::MySqlStore
+//! [`MySqlStore::query_raw`]: crate::MySqlStore::query_raw
+//! [`MySqlStore::query_raw_as`]: crate::MySqlStore::query_raw_as
+//! [`StoreData`]: datastore::StoreData
+
+use std::fmt::{self, Display, Formatter};
+
+use datastore::Write;
+
+use crate::{Comparison, Conditions, Filter, MySqlStore, OrderBy, QueryKind};
+
+/// A `SELECT` query built at runtime.
+///
+/// Identifiers are quoted if necessary and values are written as escaped literals, like in the
+/// logged sql of all queries of the store.
+///
+/// # Examples
+///
+/// ```
+/// use datastore_mysql::query::Query;
+/// use datastore_mysql::{Filter, OrderBy};
+///
+/// let query = Query::select("person")
+///     .column("id")
+///     .column("name")
+///     .where_eq("age", &30)
+///     .filter(Filter::new().like("name", "R%"))
+///     .order_by(OrderBy::asc("name"))
+///     .limit(10);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "SELECT id,name FROM person WHERE age = 30 AND name LIKE 'R%' ORDER BY name ASC LIMIT 10"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Query {
+    table: String,
+    columns: Vec<String>,
+    distinct: bool,
+    conditions: Conditions,
+    order_by: Vec<OrderBy>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+impl Query {
+    /// Creates a new `SELECT` query on `table`. Without any columns all columns of the table
+    /// are selected.
+    ///
+    /// ```
+    /// use datastore_mysql::query::Query;
+    ///
+    /// assert_eq!(Query::select("order").to_string(), "SELECT * FROM `order`");
+    /// ```
+    pub fn select(table: &str) -> Self {
+        Self {
+            table: table.to_owned(),
+            columns: Vec::new(),
+            distinct: false,
+            conditions: Conditions::default(),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Adds `column` to the selected columns.
+    pub fn column(mut self, column: &str) -> Self {
+        self.columns.push(column.to_owned());
+        self
+    }
+
+    /// Adds all `columns` to the selected columns.
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.columns
+            .extend(columns.iter().map(|column| column.to_string()));
+        self
+    }
+
+    /// Only returns distinct rows (`SELECT DISTINCT`).
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Only returns rows where `column` is equal to `value`. See [`Filter::eq`].
+    pub fn where_eq<V>(self, column: &str, value: &V) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.filter(Filter::new().eq(column, value))
+    }
+
+    /// Only returns rows where the value at the JSON `path` within `column` compares to `value`
+    /// as given by `comparison`. See [`Filter::json_path`].
+    pub fn where_json_path<V>(
+        self,
+        column: &str,
+        path: &str,
+        comparison: Comparison,
+        value: &V,
+    ) -> Self
+    where
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.filter(Filter::new().json_path(column, path, comparison, value))
+    }
+
+    /// Only returns rows matching all conditions of `filter`, in addition to the existing
+    /// conditions.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.conditions.extend(filter.conditions().clone());
+        self
+    }
+
+    /// Orders the rows by `order_by`, after the columns that were already added.
+    pub fn order_by(mut self, order_by: OrderBy) -> Self {
+        self.order_by.push(order_by);
+        self
+    }
+
+    /// Returns at most `limit` rows.
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skips the first `offset` rows.
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut query = crate::Query::new(&self.table, QueryKind::Select);
+        for column in &self.columns {
+            query.push(column.clone(), String::new());
+        }
+
+        if self.distinct {
+            query.distinct();
+        }
+
+        query.push_conditions(self.conditions.clone());
+
+        for order_by in &self.order_by {
+            query.push_order_by(order_by.clone());
+        }
+
+        if let Some(limit) = self.limit {
+            query.limit(limit);
+        }
+
+        if let Some(offset) = self.offset {
+            query.offset(offset);
+        }
+
+        Display::fmt(&query, f)
+    }
+}
@@ -0,0 +1,289 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::connection;
+use crate::dialect::{Dialect, MySqlDialect};
+use crate::mysql::{execute, QueryOptions};
+use crate::{Error, MySqlStore, Query, Value};
+
+use datastore::{DataDescriptor, DataQuery, Read, StoreData};
+use sqlx::pool::PoolConnection;
+use sqlx::MySql;
+
+/// A single connection of a [`MySqlStore`], passed to the closure of
+/// [`MySqlStore::with_connection`].
+///
+/// All operations of a `Session` run on the same connection, so they share the state of the
+/// session: temporary tables, user variables and `LAST_INSERT_ID()`. Unlike a [`Transaction`]
+/// every operation is committed immediately. The connection is returned to the pool when the
+/// closure returns.
+///
+/// [`Transaction`]: crate::Transaction
+#[derive(Debug)]
+pub struct Session {
+    inner: PoolConnection<MySql>,
+    options: QueryOptions,
+    order_by_primary_key: bool,
+    /// The session holds table locks acquired by [`lock_tables`](Self::lock_tables).
+    tables_locked: bool,
+    /// Foreign key and unique checks are disabled by [`MySqlStore::import`].
+    checks_disabled: bool,
+}
+
+impl Session {
+    pub(crate) fn new(
+        inner: PoolConnection<MySql>,
+        options: QueryOptions,
+        order_by_primary_key: bool,
+    ) -> Self {
+        Self {
+            inner,
+            options,
+            order_by_primary_key,
+            tables_locked: false,
+            checks_disabled: false,
+        }
+    }
+
+    /// Deletes all items `T` matching the query `Q` and returns the number of deleted items.
+    /// Returns an error if the query has no conditions.
+    pub async fn delete<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        connection::delete(&mut self.inner, self.options, descriptor, query).await
+    }
+
+    /// Returns all items `T` matching the query `Q`.
+    pub async fn get<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        connection::get(
+            &mut self.inner,
+            self.options,
+            self.order_by_primary_key,
+            descriptor,
+            query,
+        )
+        .await
+    }
+
+    /// Returns an item `T` matching the query `Q`. If no matching item is found `None` is
+    /// returned.
+    pub async fn get_one<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<Option<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        connection::get_one(
+            &mut self.inner,
+            self.options,
+            self.order_by_primary_key,
+            descriptor,
+            query,
+        )
+        .await
+    }
+
+    /// Inserts a new item `T`. Items with [`Children`] fields are inserted in a transaction.
+    ///
+    /// [`Children`]: crate::Children
+    pub async fn insert<T, D>(&mut self, descriptor: D, data: T) -> Result<(), Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        connection::insert(&mut self.inner, self.options, descriptor, data).await
+    }
+
+    /// Inserts a new item `T` and returns the id generated for the `AUTO_INCREMENT` column of
+    /// the table. See [`MySqlStore::insert_returning_id`].
+    pub async fn insert_returning_id<T, D>(&mut self, descriptor: D, data: T) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        connection::insert_returning_id(&mut self.inner, self.options, descriptor, data).await
+    }
+
+    /// Updates all items `T` matching the query `Q` to `data` and returns the number of
+    /// matched items. See [`MySqlStore::update`].
+    pub async fn update<T, D, Q>(&mut self, descriptor: D, data: T, query: Q) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        connection::update(&mut self.inner, self.options, descriptor, data, query).await
+    }
+
+    /// Executes the raw `sql` and reads all returned rows into `T`. See
+    /// [`MySqlStore::query_raw`].
+    pub async fn query_raw<T>(&mut self, sql: &str, binds: &[Value]) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+    {
+        connection::query_raw(&mut self.inner, self.options, sql, binds).await
+    }
+
+    /// Executes the raw `sql` and reads the columns of all returned rows into `T` in order. See
+    /// [`MySqlStore::query_raw_as`].
+    pub async fn query_raw_as<T>(&mut self, sql: &str, binds: &[Value]) -> Result<Vec<T>, Error>
+    where
+        T: Read<MySqlStore> + Send,
+    {
+        connection::query_raw_as(&mut self.inner, self.options, sql, binds).await
+    }
+
+    /// Executes the raw `sql` and returns the first column of the first returned row. See
+    /// [`MySqlStore::query_raw_scalar`].
+    pub async fn query_raw_scalar<O>(&mut self, sql: &str, binds: &[Value]) -> Result<O, Error>
+    where
+        O: Send + Unpin + for<'r> sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
+    {
+        connection::query_raw_scalar(&mut self.inner, self.options, sql, binds).await
+    }
+
+    /// Executes the raw `sql` and returns the number of affected rows. See
+    /// [`MySqlStore::execute_raw`].
+    pub async fn execute_raw(&mut self, sql: &str, binds: &[Value]) -> Result<u64, Error> {
+        connection::execute_raw(&mut self.inner, self.options, sql, binds).await
+    }
+
+    /// Locks the tables of all `locks` for this session (`LOCK TABLES`), releasing all table
+    /// locks held by the session before. Returns [`Error::EmptyLockTables`] if `locks` is empty.
+    ///
+    /// While the locks are held other sessions wait for every access conflicting with them,
+    /// only hold them briefly. The session can only access the locked tables. The locks are
+    /// released by [`unlock_tables`], at the latest when the closure of
+    /// [`MySqlStore::with_connection`] returns.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use datastore_mysql::LockMode;
+    ///
+    /// store
+    ///     .with_connection(|session| {
+    ///         Box::pin(async move {
+    ///             session.lock_tables(&[("orders", LockMode::Write)]).await?;
+    ///             // Reorganize the table...
+    ///             session.unlock_tables().await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    ///
+    /// [`unlock_tables`]: Self::unlock_tables
+    pub async fn lock_tables(&mut self, locks: &[(&str, LockMode)]) -> Result<(), Error> {
+        let mut query = Query::raw(lock_tables(locks)?, Vec::new());
+
+        // A failed `LOCK TABLES` releases the locks held before too.
+        self.tables_locked = true;
+        execute(&mut *self.inner, self.options, &mut query).await?;
+        Ok(())
+    }
+
+    /// Releases all table locks held by the session (`UNLOCK TABLES`).
+    pub async fn unlock_tables(&mut self) -> Result<(), Error> {
+        let mut query = Query::raw(String::from("UNLOCK TABLES"), Vec::new());
+
+        execute(&mut *self.inner, self.options, &mut query).await?;
+        self.tables_locked = false;
+        Ok(())
+    }
+
+    /// Returns `true` if the session holds table locks.
+    pub(crate) fn tables_locked(&self) -> bool {
+        self.tables_locked
+    }
+
+    /// Enables or disables foreign key and unique checks for the session.
+    pub(crate) async fn set_checks(&mut self, enabled: bool) -> Result<(), Error> {
+        let value = if enabled { 1 } else { 0 };
+        let mut query = Query::raw(
+            format!(
+                "SET FOREIGN_KEY_CHECKS = {}, UNIQUE_CHECKS = {}",
+                value, value
+            ),
+            Vec::new(),
+        );
+
+        self.checks_disabled = true;
+        execute(&mut *self.inner, self.options, &mut query).await?;
+        self.checks_disabled = !enabled;
+        Ok(())
+    }
+}
+
+/// The lock acquired on a table by [`Session::lock_tables`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LockMode {
+    /// Other sessions can read but not write the table (`READ`).
+    Read,
+    /// Other sessions can neither read nor write the table (`WRITE`).
+    Write,
+}
+
+impl Display for LockMode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Read => write!(f, "READ"),
+            Self::Write => write!(f, "WRITE"),
+        }
+    }
+}
+
+/// Builds the `LOCK TABLES` statement acquiring the `locks`. `LOCK TABLES` without any tables
+/// is a syntax error.
+pub(crate) fn lock_tables(locks: &[(&str, LockMode)]) -> Result<String, Error> {
+    if locks.is_empty() {
+        return Err(Error::EmptyLockTables);
+    }
+
+    let dialect = MySqlDialect;
+
+    let locks: Vec<String> = locks
+        .iter()
+        .map(|(table, mode)| format!("{} {}", dialect.quote_ident(table), mode))
+        .collect();
+
+    Ok(format!("LOCK TABLES {}", locks.join(",")))
+}
+
+/// A [`Session`] whose connection is closed instead of returned to the pool if the session
+/// still holds table locks or has disabled checks when it's dropped, e.g. because the future
+/// using it was cancelled. Connections are returned to the pool without resetting the session,
+/// the next user of the connection would inherit the locks and checks otherwise.
+#[derive(Debug)]
+pub(crate) struct SessionGuard {
+    session: Option<Session>,
+}
+
+impl SessionGuard {
+    pub(crate) fn new(session: Session) -> Self {
+        Self {
+            session: Some(session),
+        }
+    }
+
+    pub(crate) fn session(&mut self) -> &mut Session {
+        self.session.as_mut().unwrap()
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        if let Some(session) = self.session.take() {
+            if session.tables_locked || session.checks_disabled {
+                log::warn!("Closing connection with table locks or disabled checks");
+                drop(session.inner.detach());
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use datastore::{DataDescriptor, StoreData, TypeWriter};
+
+use crate::dialect;
+use crate::{Error, MySqlStore};
+
+/// The maximum length of a table name in MySQL.
+const MAX_TABLE_NAME_LEN: usize = 64;
+
+/// A [`DataDescriptor`] using the table `table` instead of the table of the wrapped descriptor.
+///
+/// This allows storing the same type in multiple tables, e.g. one table per tenant or year.
+/// `WithTable` can be passed to every method of the store taking a descriptor. Child tables of
+/// [`Children`] fields are named after the overridden table.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore_mysql::WithTable;
+///
+/// let descriptor = WithTable::new(store.descriptor::<Event>(), "events_2024")?;
+///
+/// store.create(descriptor.clone()).await?;
+/// store.insert(descriptor, event).await?;
+/// ```
+///
+/// [`Children`]: crate::Children
+#[derive(Clone, Debug)]
+pub struct WithTable<D> {
+    descriptor: D,
+    table: String,
+}
+
+impl<D> WithTable<D> {
+    /// Creates a new `WithTable` using the table `table` for `descriptor`.
+    ///
+    /// The table name is written into the generated sql unquoted. Returns
+    /// [`Error::InvalidTableName`] unless it only contains ascii alphanumeric characters, `_`
+    /// and `$`, is at most 64 characters long and isn't a number or reserved word.
+    pub fn new(descriptor: D, table: &str) -> Result<Self, Error> {
+        validate_table_name(table)?;
+
+        Ok(Self {
+            descriptor,
+            table: table.to_owned(),
+        })
+    }
+
+    /// Returns the name of the table.
+    #[inline]
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Consumes the `WithTable`, returning the wrapped descriptor.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.descriptor
+    }
+}
+
+/// Returns [`Error::InvalidTableName`] unless `table` can be written into sql unquoted and is at
+/// most 64 characters long.
+pub(crate) fn validate_table_name(table: &str) -> Result<(), Error> {
+    if table.len() > MAX_TABLE_NAME_LEN || !dialect::is_plain_ident(table) {
+        return Err(Error::InvalidTableName {
+            name: table.to_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+impl<T, D> DataDescriptor<T, MySqlStore> for WithTable<D>
+where
+    T: StoreData<MySqlStore>,
+    D: DataDescriptor<T, MySqlStore>,
+{
+    fn ident(&self) -> &str {
+        &self.table
+    }
+
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        self.descriptor.write(writer)
+    }
+}
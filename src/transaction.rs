@@ -0,0 +1,888 @@
+use std::collections::HashMap;
+
+use crate::connection;
+use crate::dialect::{Dialect, MySqlDialect};
+use crate::mysql::{
+    self, check_exactly_one, execute, execute_unprepared, fetch_all, fetch_all_max,
+    fetch_all_with_children, fetch_as, fetch_bytes_with, fetch_dynamic, fetch_exactly_one,
+    fetch_exactly_one_with_children, fetch_optional, fetch_scalar, fetch_str_with, fetch_stream,
+    QueryOptions, IDS_PER_QUERY,
+};
+use crate::{
+    AggFunc, Error, Filter, MySqlStore, Order, OrderBy, Query, QueryKind, UpdateExpr, Value,
+};
+
+use datastore::{DataDescriptor, DataQuery, Read, StoreData, Write};
+use futures::Stream;
+use sqlx::MySql;
+
+/// The savepoint [`Transaction::delete_one`] rolls back to if more than one item was deleted.
+const DELETE_ONE_SAVEPOINT: &str = "datastore_delete_one";
+
+/// A transaction on a [`MySqlStore`].
+///
+/// A `Transaction` is created by calling [`MySqlStore::begin`]. All operations executed on the
+/// transaction only become visible to other connections after [`commit`] is called. Dropping
+/// the `Transaction` without calling [`commit`] rolls back all operations.
+///
+/// [`commit`]: Self::commit
+#[derive(Debug)]
+pub struct Transaction {
+    inner: sqlx::Transaction<'static, MySql>,
+    options: QueryOptions,
+    max_rows: Option<u64>,
+    order_by_primary_key: bool,
+}
+
+impl Transaction {
+    pub(crate) fn new(
+        inner: sqlx::Transaction<'static, MySql>,
+        options: QueryOptions,
+        max_rows: Option<u64>,
+        order_by_primary_key: bool,
+    ) -> Self {
+        Self {
+            inner,
+            options,
+            max_rows,
+            order_by_primary_key,
+        }
+    }
+
+    /// Commits all operations executed on the transaction.
+    pub async fn commit(self) -> Result<(), Error> {
+        self.inner.commit().await.map_err(Error::from)
+    }
+
+    /// Rolls back all operations executed on the transaction.
+    pub async fn rollback(self) -> Result<(), Error> {
+        self.inner.rollback().await.map_err(Error::from)
+    }
+
+    /// Executes all statements of the sql `script` on the transaction. See
+    /// [`MySqlStore::run_migration`].
+    pub async fn run_migration(&mut self, script: &str) -> Result<(), Error> {
+        for statement in mysql::split_statements(script) {
+            let mut query = Query::raw(statement.to_owned(), Vec::new());
+            execute_unprepared(&mut self.inner, self.options, &mut query).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates a savepoint named `name`. All operations executed on the transaction after the
+    /// savepoint can be rolled back using [`rollback_to`] without rolling back the whole
+    /// transaction. Creating a savepoint with the name of an existing savepoint replaces it.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut tx = store.begin().await?;
+    ///
+    /// for record in records {
+    ///     tx.savepoint("record").await?;
+    ///
+    ///     if tx.insert(store.descriptor::<Record>(), record).await.is_err() {
+    ///         tx.rollback_to("record").await?;
+    ///     }
+    /// }
+    ///
+    /// tx.commit().await?;
+    /// ```
+    ///
+    /// [`rollback_to`]: Self::rollback_to
+    pub async fn savepoint(&mut self, name: &str) -> Result<(), Error> {
+        let mut query = Query::raw(format!("SAVEPOINT {}", savepoint_ident(name)), Vec::new());
+
+        execute_unprepared(&mut self.inner, self.options, &mut query).await?;
+        Ok(())
+    }
+
+    /// Rolls back all operations executed on the transaction after the savepoint `name` was
+    /// created. The savepoint and the transaction stay active. Returns an error if no
+    /// savepoint named `name` exists.
+    pub async fn rollback_to(&mut self, name: &str) -> Result<(), Error> {
+        let mut query = Query::raw(
+            format!("ROLLBACK TO SAVEPOINT {}", savepoint_ident(name)),
+            Vec::new(),
+        );
+
+        execute_unprepared(&mut self.inner, self.options, &mut query).await?;
+        Ok(())
+    }
+
+    /// Returns the aggregate `func` of `column` for each distinct value of the `group_by`
+    /// column, over all items `T` matching the query `Q`. See [`MySqlStore::aggregate`].
+    pub async fn aggregate<T, D, Q, K>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+        func: AggFunc,
+        column: &str,
+        group_by: &str,
+    ) -> Result<Vec<(K, Option<f64>)>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+        K: Send + Unpin + for<'r> sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
+    {
+        let mut query = mysql::aggregate(&descriptor, &query, func, column, group_by);
+
+        fetch_as(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Deletes all items `T` matching the query `Q` and returns the number of deleted items.
+    /// Returns an error if the query has no conditions, use [`delete_all`] to delete all items.
+    ///
+    /// [`delete_all`]: Self::delete_all
+    pub async fn delete<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        connection::delete(&mut self.inner, self.options, descriptor, query).await
+    }
+
+    /// Deletes all items `T` whose `key_column` is equal to any of the `ids` and returns the
+    /// number of deleted items. See [`MySqlStore::delete_by_ids`].
+    pub async fn delete_by_ids<T, D, K>(
+        &mut self,
+        descriptor: D,
+        key_column: &str,
+        ids: &[K],
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send + Sync,
+        K: Write<MySqlStore> + Sync,
+    {
+        let mut deleted = 0;
+
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let mut query = mysql::delete_in(&descriptor, key_column, ids);
+
+            deleted += execute(&mut self.inner, self.options, &mut query)
+                .await?
+                .rows_affected();
+        }
+
+        Ok(deleted)
+    }
+
+    /// Deletes at most `limit` items `T` matching the query `Q` and returns the number of
+    /// deleted items. See [`MySqlStore::delete_limited`].
+    pub async fn delete_limited<T, D, Q>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+        limit: u64,
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::delete_limited(&descriptor, &query, limit);
+
+        if !query.has_conditions() {
+            return Err(Error::EmptyDeleteConditions);
+        }
+
+        let res = execute(&mut self.inner, self.options, &mut query).await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Deletes the only item `T` matching the query `Q`. See [`MySqlStore::delete_one`].
+    ///
+    /// If more than one item was deleted the delete is rolled back to a savepoint created
+    /// before the delete, other operations executed on the transaction are kept.
+    pub async fn delete_one<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<(), Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let mut count = mysql::count(&descriptor, &query);
+        let mut query = mysql::delete_limited(&descriptor, &query, 2);
+
+        if !query.has_conditions() {
+            return Err(Error::EmptyDeleteConditions);
+        }
+
+        let matched: i64 = fetch_scalar(&mut self.inner, self.options, &mut count).await?;
+        check_exactly_one(matched as u64)?;
+
+        self.savepoint(DELETE_ONE_SAVEPOINT).await?;
+        let res = execute(&mut self.inner, self.options, &mut query).await?;
+        if let Err(err) = check_exactly_one(res.rows_affected()) {
+            self.rollback_to(DELETE_ONE_SAVEPOINT).await?;
+            return Err(err);
+        }
+
+        let mut query = Query::raw(
+            format!(
+                "RELEASE SAVEPOINT {}",
+                savepoint_ident(DELETE_ONE_SAVEPOINT)
+            ),
+            Vec::new(),
+        );
+        execute_unprepared(&mut self.inner, self.options, &mut query).await?;
+        Ok(())
+    }
+
+    /// Deletes all items `T`.
+    pub async fn delete_all<T, D>(&mut self, descriptor: D) -> Result<(), Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        let mut query = Query::new(descriptor.ident(), QueryKind::Delete);
+
+        execute(&mut self.inner, self.options, &mut query).await?;
+        Ok(())
+    }
+
+    /// Returns `true` if any item `T` matches the query `Q`.
+    pub async fn exists<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<bool, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::exists(&descriptor, &query);
+
+        let exists: i64 = fetch_scalar(&mut self.inner, self.options, &mut query).await?;
+        Ok(exists != 0)
+    }
+
+    /// Returns all items `T` matching the query `Q`.
+    pub async fn get<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        connection::get(
+            &mut self.inner,
+            self.options,
+            self.order_by_primary_key,
+            descriptor,
+            query,
+        )
+        .await
+    }
+
+    /// Returns all items `T`. Fails with [`Error::TooManyRows`] if there are more than
+    /// [`max_rows`] items.
+    ///
+    /// [`max_rows`]: crate::MySqlStoreConfig::max_rows
+    pub async fn get_all<T, D>(&mut self, descriptor: D) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send + Sync,
+    {
+        let mut query = mysql::select_all(&descriptor);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&mysql::primary_key(&descriptor));
+        }
+
+        if !query.children().is_empty() {
+            return fetch_all_with_children(
+                &mut self.inner,
+                self.options,
+                &mysql::primary_key(&descriptor),
+                query,
+                self.max_rows,
+            )
+            .await;
+        }
+
+        fetch_all_max(&mut self.inner, self.options, query, self.max_rows).await
+    }
+
+    /// Returns at most `limit` items `T` whose `key_column` is greater than `last_value`,
+    /// ordered by `key_column`. See [`MySqlStore::get_after`].
+    pub async fn get_after<T, D, V>(
+        &mut self,
+        descriptor: D,
+        key_column: &str,
+        last_value: Option<&V>,
+        limit: u64,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send + Sync,
+        V: ?Sized + Write<MySqlStore> + Sync,
+    {
+        let mut query = mysql::select_after(&descriptor, key_column, last_value, limit);
+
+        if !query.children().is_empty() {
+            return fetch_all_with_children(
+                &mut self.inner,
+                self.options,
+                &mysql::primary_key(&descriptor),
+                query,
+                None,
+            )
+            .await;
+        }
+
+        fetch_all(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Returns the first `limit` items `T` matching the query `Q`, ordered by `order_column`.
+    /// See [`MySqlStore::get_top`].
+    pub async fn get_top<T, D, Q>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+        order_column: &str,
+        order: Order,
+        limit: u64,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut query = mysql::select_top(&descriptor, &query, order_column, order, limit);
+
+        if !query.children().is_empty() {
+            return fetch_all_with_children(
+                &mut self.inner,
+                self.options,
+                &mysql::primary_key(&descriptor),
+                query,
+                None,
+            )
+            .await;
+        }
+
+        fetch_all(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Returns the items `T` matching the query `Q`, ordered by all columns of `order_by`. See
+    /// [`MySqlStore::get_ordered`].
+    pub async fn get_ordered<T, D, Q>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+        order_by: &[OrderBy],
+        limit: Option<u64>,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        if limit == Some(0) {
+            return Ok(Vec::new());
+        }
+
+        let mut query = mysql::select_ordered(&descriptor, &query, order_by, limit);
+
+        if !query.children().is_empty() {
+            return fetch_all_with_children(
+                &mut self.inner,
+                self.options,
+                &mysql::primary_key(&descriptor),
+                query,
+                None,
+            )
+            .await;
+        }
+
+        fetch_all(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Returns all items `T` whose `key_column` is equal to any of the `ids`. See
+    /// [`MySqlStore::get_by_ids`].
+    pub async fn get_by_ids<T, D, K>(
+        &mut self,
+        descriptor: D,
+        key_column: &str,
+        ids: &[K],
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send + Sync,
+        K: Write<MySqlStore> + Sync,
+    {
+        let mut entries = Vec::new();
+
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let mut query = mysql::select_in(&descriptor, key_column, ids);
+
+            if !query.children().is_empty() {
+                entries.extend(
+                    fetch_all_with_children::<T>(
+                        &mut self.inner,
+                        self.options,
+                        &mysql::primary_key(&descriptor),
+                        query,
+                        None,
+                    )
+                    .await?,
+                );
+                continue;
+            }
+
+            entries.extend(fetch_all::<_, T>(&mut self.inner, self.options, &mut query).await?);
+        }
+
+        Ok(entries)
+    }
+
+    /// Returns all items `T`, only selecting the given `columns`. See
+    /// [`MySqlStore::get_columns`].
+    pub async fn get_columns<T, D>(
+        &mut self,
+        descriptor: D,
+        columns: &[&str],
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send + Sync,
+    {
+        let mut query = mysql::select_columns(&descriptor, columns);
+
+        fetch_all(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Returns all distinct items `T`, only selecting the given `columns`. See
+    /// [`MySqlStore::get_distinct`].
+    pub async fn get_distinct<T, D>(
+        &mut self,
+        descriptor: D,
+        columns: &[&str],
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send + Sync,
+    {
+        let mut query = mysql::select_distinct(&descriptor, columns);
+
+        fetch_all(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Returns a [`Stream`] of all items `T` matching the query `Q`. See
+    /// [`MySqlStore::get_stream`].
+    pub fn get_stream<T, D, Q>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+    ) -> impl Stream<Item = Result<T, Error>> + Send + '_
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::select(&descriptor, &query);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&mysql::primary_key(&descriptor));
+        }
+        // The child rows are loaded for all returned items at once, which a stream can't do.
+        mysql::unsupported_children(&mut query, "Children field into a stream");
+
+        fetch_stream(&mut self.inner, self.options, &mut query)
+    }
+
+    /// Returns the item `T` whose primary key is `key`, or `None` if no item has the key. See
+    /// [`MySqlStore::get_by_key`].
+    pub async fn get_by_key<T, D, K>(&mut self, descriptor: D, key: &K) -> Result<Option<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        K: ?Sized + Write<MySqlStore>,
+    {
+        let filter = mysql::key_filter(&descriptor, key)?;
+
+        self.get_one(descriptor, filter).await
+    }
+
+    /// Returns all items `T` where `column` is equal to `value`. See [`MySqlStore::get_where`].
+    pub async fn get_where<T, D, V>(
+        &mut self,
+        descriptor: D,
+        column: &str,
+        value: &V,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.get(descriptor, Filter::new().eq(column, value)).await
+    }
+
+    /// Returns an item `T` where `column` is equal to `value`, or `None` if no item matches.
+    /// See [`MySqlStore::get_one_where`].
+    pub async fn get_one_where<T, D, V>(
+        &mut self,
+        descriptor: D,
+        column: &str,
+        value: &V,
+    ) -> Result<Option<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        V: ?Sized + Write<MySqlStore>,
+    {
+        self.get_one(descriptor, Filter::new().eq(column, value))
+            .await
+    }
+
+    /// Returns all items `T` matching the query `Q` and locks their rows until the transaction
+    /// is committed or rolled back (`SELECT ... FOR UPDATE`).
+    ///
+    /// Other transactions can't update, delete or lock the rows in the meantime, they wait
+    /// for the lock instead. Rows of [`Children`] fields are not locked.
+    ///
+    /// Locking is only available on a `Transaction`. Outside of a transaction every query is
+    /// committed immediately and the locks would be released right away.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut tx = store.begin().await?;
+    /// let accounts: Vec<Account> = tx
+    ///     .get_for_update(store.descriptor::<Account>(), Filter::new().is_in("id", &[1, 2]))
+    ///     .await?;
+    /// // Update the accounts...
+    /// tx.commit().await?;
+    /// ```
+    ///
+    /// [`Children`]: crate::Children
+    pub async fn get_for_update<T, D, Q>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::select(&descriptor, &query);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&mysql::primary_key(&descriptor));
+        }
+        query.for_update();
+
+        if !query.children().is_empty() {
+            return fetch_all_with_children(
+                &mut self.inner,
+                self.options,
+                &mysql::primary_key(&descriptor),
+                query,
+                None,
+            )
+            .await;
+        }
+
+        fetch_all(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Returns an item `T` matching the query `Q` and locks its row until the transaction is
+    /// committed or rolled back. See [`get_for_update`].
+    ///
+    /// [`get_for_update`]: Self::get_for_update
+    pub async fn get_one_for_update<T, D, Q>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+    ) -> Result<Option<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::select_one(&descriptor, &query);
+        if self.order_by_primary_key {
+            query.order_by_primary_key(&mysql::primary_key(&descriptor));
+        }
+        query.for_update();
+
+        if !query.children().is_empty() {
+            let entries = fetch_all_with_children(
+                &mut self.inner,
+                self.options,
+                &mysql::primary_key(&descriptor),
+                query,
+                None,
+            )
+            .await?;
+            return Ok(entries.into_iter().next());
+        }
+
+        fetch_optional(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Returns an item `T` matching the query `Q`. If no matching item is found `None` is
+    /// returned.
+    pub async fn get_one<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<Option<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        connection::get_one(
+            &mut self.inner,
+            self.options,
+            self.order_by_primary_key,
+            descriptor,
+            query,
+        )
+        .await
+    }
+
+    /// Returns the only item `T` matching the query `Q`. See [`MySqlStore::get_exactly_one`].
+    pub async fn get_exactly_one<T, D, Q>(&mut self, descriptor: D, query: Q) -> Result<T, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let query = mysql::select(&descriptor, &query);
+
+        if !query.children().is_empty() {
+            return fetch_exactly_one_with_children(
+                &mut self.inner,
+                self.options,
+                &mysql::primary_key(&descriptor),
+                query,
+            )
+            .await;
+        }
+
+        fetch_exactly_one(&mut self.inner, self.options, query).await
+    }
+
+    /// Calls `f` with the bytes of the binary `column` of all items `T` matching the query `Q`.
+    /// See [`MySqlStore::get_bytes_with`].
+    pub async fn get_bytes_with<T, D, Q, F>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+        column: &str,
+        f: F,
+    ) -> Result<(), Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+        F: FnMut(&[u8]) + Send,
+    {
+        let mut query = mysql::select_bytes(&descriptor, &query, column);
+
+        fetch_bytes_with(&mut self.inner, self.options, &mut query, column, f).await
+    }
+
+    /// Calls `f` with the string of the text `column` of all items `T` matching the query `Q`.
+    /// See [`MySqlStore::get_str_with`].
+    pub async fn get_str_with<T, D, Q, F>(
+        &mut self,
+        descriptor: D,
+        query: Q,
+        column: &str,
+        f: F,
+    ) -> Result<(), Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+        F: FnMut(&str) + Send,
+    {
+        let mut query = mysql::select_bytes(&descriptor, &query, column);
+
+        fetch_str_with(&mut self.inner, self.options, &mut query, column, f).await
+    }
+
+    /// Inserts a new item `T`.
+    pub async fn insert<T, D>(&mut self, descriptor: D, data: T) -> Result<(), Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        connection::insert(&mut self.inner, self.options, descriptor, data).await
+    }
+
+    /// Inserts a new item `T` and returns the id generated for the `AUTO_INCREMENT` column of
+    /// the table. See [`MySqlStore::insert_returning_id`].
+    pub async fn insert_returning_id<T, D>(&mut self, descriptor: D, data: T) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        connection::insert_returning_id(&mut self.inner, self.options, descriptor, data).await
+    }
+
+    /// Inserts a new item `T`, or updates all columns of the existing item if an item with the
+    /// same primary or unique key already exists.
+    pub async fn upsert<T, D>(&mut self, descriptor: D, data: T) -> Result<(), Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::upsert(&descriptor, &data);
+
+        execute(&mut self.inner, self.options, &mut query).await?;
+        Ok(())
+    }
+
+    /// Inserts a new item `T`, replacing the existing item if an item with the same primary or
+    /// unique key already exists. See [`MySqlStore::replace`].
+    pub async fn replace<T, D>(&mut self, descriptor: D, data: T) -> Result<(), Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::replace(&descriptor, &data);
+
+        connection::execute_insert(
+            &mut self.inner,
+            self.options,
+            &mysql::primary_key(&descriptor),
+            &mut query,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Inserts the item `T` or updates the existing item with the same primary or unique key
+    /// and returns the id of the item. See [`MySqlStore::save`].
+    pub async fn save<T, D>(&mut self, descriptor: D, data: T) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::save(&descriptor, &data);
+
+        let res = execute(&mut self.inner, self.options, &mut query).await?;
+        Ok(res.last_insert_id())
+    }
+
+    /// Inserts a new item `T` unless an item with the same primary or unique key already
+    /// exists and returns the number of inserted items. See [`MySqlStore::insert_ignore`].
+    pub async fn insert_ignore<T, D>(&mut self, descriptor: D, data: T) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::insert_ignore(&descriptor, &data);
+
+        connection::execute_insert(
+            &mut self.inner,
+            self.options,
+            &mysql::primary_key(&descriptor),
+            &mut query,
+        )
+        .await
+    }
+
+    /// Updates all items `T` matching the query `Q` to `data` and returns the number of
+    /// matched items. See [`MySqlStore::update`].
+    pub async fn update<T, D, Q>(&mut self, descriptor: D, data: T, query: Q) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        connection::update(&mut self.inner, self.options, descriptor, data, query).await
+    }
+
+    /// Updates only the given `fields` of all items `T` matching the query `Q` and returns the
+    /// number of matched items. See [`MySqlStore::update_fields`].
+    pub async fn update_fields<T, D, Q>(
+        &mut self,
+        descriptor: D,
+        fields: &[(&str, Value)],
+        query: Q,
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::update_fields(&descriptor, fields, &query);
+
+        let res = execute(&mut self.inner, self.options, &mut query).await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Updates the columns of all items `T` matching the query `Q` to the result of an
+    /// [`UpdateExpr`] and returns the number of matched items. See
+    /// [`MySqlStore::update_exprs`].
+    pub async fn update_exprs<T, D, Q>(
+        &mut self,
+        descriptor: D,
+        exprs: &[(&str, UpdateExpr)],
+        query: Q,
+    ) -> Result<u64, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+        D: DataDescriptor<T, MySqlStore> + Send,
+        Q: DataQuery<T, MySqlStore> + Send,
+    {
+        let mut query = mysql::update_exprs(&descriptor, exprs, &query);
+
+        let res = execute(&mut self.inner, self.options, &mut query).await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Returns all rows of `table` matching `filter` as maps from the name to the value of each
+    /// column. See [`MySqlStore::get_dynamic`].
+    pub async fn get_dynamic(
+        &mut self,
+        table: &str,
+        filter: Filter,
+    ) -> Result<Vec<HashMap<String, Value>>, Error> {
+        let mut query = mysql::select_dynamic(table, &filter)?;
+
+        fetch_dynamic(&mut self.inner, self.options, &mut query).await
+    }
+
+    /// Executes the raw `sql` and reads all returned rows into `T`. See
+    /// [`MySqlStore::query_raw`].
+    pub async fn query_raw<T>(&mut self, sql: &str, binds: &[Value]) -> Result<Vec<T>, Error>
+    where
+        T: StoreData<MySqlStore> + Send + Sync + 'static,
+    {
+        connection::query_raw(&mut self.inner, self.options, sql, binds).await
+    }
+
+    /// Executes the raw `sql` and reads the columns of all returned rows into `T` in order. See
+    /// [`MySqlStore::query_raw_as`].
+    pub async fn query_raw_as<T>(&mut self, sql: &str, binds: &[Value]) -> Result<Vec<T>, Error>
+    where
+        T: Read<MySqlStore> + Send,
+    {
+        connection::query_raw_as(&mut self.inner, self.options, sql, binds).await
+    }
+
+    /// Executes the raw `sql` and returns the first column of the first returned row. See
+    /// [`MySqlStore::query_raw_scalar`].
+    pub async fn query_raw_scalar<O>(&mut self, sql: &str, binds: &[Value]) -> Result<O, Error>
+    where
+        O: Send + Unpin + for<'r> sqlx::Decode<'r, MySql> + sqlx::Type<MySql>,
+    {
+        connection::query_raw_scalar(&mut self.inner, self.options, sql, binds).await
+    }
+
+    /// Executes the raw `sql` and returns the number of affected rows. See
+    /// [`MySqlStore::execute_raw`].
+    pub async fn execute_raw(&mut self, sql: &str, binds: &[Value]) -> Result<u64, Error> {
+        connection::execute_raw(&mut self.inner, self.options, sql, binds).await
+    }
+}
+
+/// Quotes the name of a savepoint as an identifier.
+fn savepoint_ident(name: &str) -> String {
+    MySqlDialect.quote_ident(name)
+}
@@ -1,362 +1,4027 @@
-use datastore::{Read, Reader, TypeWriter, Write, Writer};
+use std::any::Any;
+use std::borrow::Cow;
+#[cfg(feature = "json")]
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+#[cfg(feature = "json")]
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::MySqlStore;
+use datastore::{DataDescriptor, Read, Reader, StoreData, TypeWriter, Write, Writer};
+use sqlx::mysql::MySqlRow;
+use sqlx::Row;
+
+use crate::mysql::{self, format_value};
+use crate::{hints, Error, MySqlStore};
+
+/// A type that can be stored in a column of a [`MySqlStore`].
+///
+/// `Storable` is implemented for every type that implements [`Write<MySqlStore>`] and
+/// [`Read<MySqlStore>`], it only exists to produce a clearer error message. A field of a type that can't be stored fails
+/// with a trait bound error inside the code generated by the `StoreData` derive. Use
+/// [`assert_storable!`] to check the field types instead.
+///
+/// [`Write<MySqlStore>`]: Write
+/// [`Read<MySqlStore>`]: Read
+/// [`assert_storable!`]: crate::assert_storable
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not storable in `MySqlStore`",
+    label = "not storable in `MySqlStore`",
+    note = "implement `Write<MySqlStore>` and `Read<MySqlStore>` for `{Self}` or use one of the \
+            types supported by `datastore-mysql`"
+)]
+pub trait Storable: Write<MySqlStore> + Read<MySqlStore> {}
+
+impl<T> Storable for T where T: Write<MySqlStore> + Read<MySqlStore> {}
+
+/// Asserts at compile time that all given types are [`Storable`].
+///
+/// # Examples
+///
+/// ```
+/// use datastore_mysql::{assert_storable, Varchar};
+///
+/// assert_storable!(i64, String, Option<Varchar<255>>);
+/// ```
+///
+/// ```compile_fail
+/// use datastore_mysql::assert_storable;
+///
+/// struct Address;
+///
+/// // error: `Address` is not storable in `MySqlStore`
+/// assert_storable!(Address);
+/// ```
+#[macro_export]
+macro_rules! assert_storable {
+    ($($t:ty),+ $(,)?) => {
+        const _: fn() = || {
+            fn assert_storable<T: $crate::Storable>() {}
+            $(assert_storable::<$t>();)+
+        };
+    };
+}
+
+/// Writes values as raw sql, for types without a matching [`Writer`] hook.
+///
+/// `RawWriter` is implemented for every [`Writer`] of a [`MySqlStore`]. Together with
+/// [`RawTypeWriter`] this allows [`Write`] implementations of custom types to use any column
+/// type and any sql expression as the value, e.g. a function call like `INET6_ATON('::1')`.
+///
+/// # Security
+///
+/// The sql fragment is written into the query unchanged. The implementer is responsible for
+/// quoting and escaping all parts of the fragment that come from the value, otherwise the
+/// fragment allows sql injection. Never include untrusted input without escaping it.
+///
+/// # Examples
+///
+/// ```
+/// use datastore::{TypeWriter, Write, Writer};
+/// use datastore_mysql::{MySqlStore, RawTypeWriter, RawWriter};
+///
+/// /// An IPv6 address stored using `INET6_ATON`.
+/// pub struct Inet6(std::net::Ipv6Addr);
+///
+/// impl Write<MySqlStore> for Inet6 {
+///     fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+///     where
+///         W: Writer<MySqlStore>,
+///     {
+///         // The display of an `Ipv6Addr` never contains quotes.
+///         writer.write_raw_literal(format!("INET6_ATON('{}')", self.0))
+///     }
+///
+///     fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+///     where
+///         W: TypeWriter<MySqlStore>,
+///     {
+///         writer.write_raw_type(String::from("VARBINARY(16)"))
+///     }
+/// }
+/// ```
+pub trait RawWriter: Writer<MySqlStore> {
+    /// Writes the sql fragment `sql` as the value of the current column.
+    ///
+    /// The MySQL writers write `sql` unchanged in place of the value. Other writers see a
+    /// string value containing `sql`. `sql` is never escaped, see the [security] notes.
+    ///
+    /// [security]: RawWriter#security
+    fn write_raw_literal(&mut self, sql: String) -> Result<(), Self::Error> {
+        hints::with(
+            |hints| hints.literal = Some(sql.clone()),
+            || self.write_str(&sql),
+        )
+    }
+}
+
+impl<W> RawWriter for W where W: ?Sized + Writer<MySqlStore> {}
+
+/// Writes column types as raw sql, for types without a matching [`TypeWriter`] hook.
+///
+/// `RawTypeWriter` is implemented for every [`TypeWriter`] of a [`MySqlStore`]. See
+/// [`RawWriter`] for an example.
+pub trait RawTypeWriter: TypeWriter<MySqlStore> {
+    /// Writes the column type `column_type` for the current column, e.g. `VARBINARY(16)`.
+    ///
+    /// The column is `NOT NULL` unless the type is wrapped in an `Option`, attributes like
+    /// `NOT NULL` must not be part of `column_type`. Other writers see a string column.
+    /// `column_type` is written unchanged, see the [security] notes of [`RawWriter`].
+    ///
+    /// [security]: RawWriter#security
+    fn write_raw_type(&mut self, column_type: String) -> Result<(), Self::Error> {
+        hints::with(
+            |hints| hints.column_type = Some(column_type),
+            || self.write_str(),
+        )
+    }
+}
+
+impl<W> RawTypeWriter for W where W: ?Sized + TypeWriter<MySqlStore> {}
+
+/// A string that is stored in a `VARCHAR(N)` column instead of a `TEXT` column.
+///
+/// Unlike `TEXT` columns, `VARCHAR` columns can be used in a normal index. MySQL counts `N` in
+/// characters, not bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Varchar<const N: usize>(pub String);
+
+impl<const N: usize> Varchar<N> {
+    /// Creates a new `Varchar` from the given `String`.
+    #[inline]
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `Varchar`, returning the wrapped `String`.
+    #[inline]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const N: usize> Deref for Varchar<N> {
+    type Target = String;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> DerefMut for Varchar<N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> From<String> for Varchar<N> {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: usize> From<&str> for Varchar<N> {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl<const N: usize> Display for Varchar<N> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// Implements the common traits of a wrapper type around `$inner`.
+macro_rules! impl_wrapper {
+    ($ty:ident, $inner:ty) => {
+        impl $ty {
+            #[doc = concat!("Creates a new `", stringify!($ty), "` from the given value.")]
+            #[inline]
+            pub fn new(value: $inner) -> Self {
+                Self(value)
+            }
+
+            #[doc = concat!("Consumes the `", stringify!($ty), "`, returning the wrapped value.")]
+            #[inline]
+            pub fn into_inner(self) -> $inner {
+                self.0
+            }
+        }
+
+        impl Deref for $ty {
+            type Target = $inner;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $ty {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl From<$inner> for $ty {
+            #[inline]
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+    };
+}
+
+/// The maximum length in bytes of a `BLOB` column.
+pub(crate) const BLOB_MAX_LEN: usize = (1 << 16) - 1;
+/// The maximum length in bytes of a `MEDIUMBLOB` column.
+const MEDIUMBLOB_MAX_LEN: usize = (1 << 24) - 1;
+/// The maximum length in bytes of a `LONGBLOB` column.
+const LONGBLOB_MAX_LEN: usize = u32::MAX as usize;
+
+/// Bytes that are stored in a `MEDIUMBLOB` column, holding up to 16 MiB.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MediumBytes(pub Vec<u8>);
+
+impl_wrapper!(MediumBytes, Vec<u8>);
+
+/// Bytes that are stored in a `LONGBLOB` column, holding up to 4 GiB.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LongBytes(pub Vec<u8>);
+
+impl_wrapper!(LongBytes, Vec<u8>);
+
+/// A string that is stored in a `MEDIUMTEXT` column, holding up to 16 MiB.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MediumText(pub String);
+
+impl_wrapper!(MediumText, String);
+
+impl Display for MediumText {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A string that is stored in a `LONGTEXT` column, holding up to 4 GiB.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LongText(pub String);
+
+impl_wrapper!(LongText, String);
+
+impl Display for LongText {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A float that is stored in a `DECIMAL(M,D)` column instead of a `DOUBLE` column.
+///
+/// The column stores exactly `D` digits after the decimal point and `M` digits in total. MySQL
+/// rounds written values to `D` digits and rejects values with more than `M - D` digits before
+/// the decimal point. Reading the value converts the exact decimal back into the closest `f64`.
+/// `NaN` and infinite values are written as `NULL`.
+///
+/// `M` must be between 1 and 65 and `D` must be between 0 and 30 and not greater than `M`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::FixedDecimal;
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Product {
+///     id: i64,
+///     price: FixedDecimal<10, 2>,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct FixedDecimal<const M: u8, const D: u8>(pub f64);
+
+impl<const M: u8, const D: u8> FixedDecimal<M, D> {
+    /// Creates a new `FixedDecimal` from the given value.
+    #[inline]
+    pub fn new(value: f64) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `FixedDecimal`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> f64 {
+        self.0
+    }
+}
+
+impl<const M: u8, const D: u8> Deref for FixedDecimal<M, D> {
+    type Target = f64;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const M: u8, const D: u8> DerefMut for FixedDecimal<M, D> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const M: u8, const D: u8> From<f64> for FixedDecimal<M, D> {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
+impl<const M: u8, const D: u8> Display for FixedDecimal<M, D> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// An integer that is stored in a `BIT(N)` column, e.g. for compact flags.
+///
+/// Bit `i` of the value is bit `i` of the column, counted from the least significant bit. The
+/// value is written as a binary literal with the most significant bit first, e.g. `5` in a
+/// `BIT(4)` column is written as `b'0101'`. Writing a value with bits set above `N` returns
+/// [`Error::OutOfRange`] before the query is sent.
+///
+/// `N` must be between 1 and 64, other widths fail to compile.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::Bits;
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Account {
+///     id: i64,
+///     flags: Bits<8>,
+/// }
+/// ```
+///
+/// [`Error::OutOfRange`]: crate::Error::OutOfRange
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bits<const N: u8>(pub u64);
+
+impl<const N: u8> Bits<N> {
+    /// Evaluating the constant fails to compile if `N` is not between 1 and 64.
+    const VALID_WIDTH: () = assert!(N >= 1 && N <= 64, "Bits requires N between 1 and 64");
+
+    /// The largest value of a `BIT(N)` column.
+    const MAX: u64 = u64::MAX >> (64 - N as u32);
+
+    /// Creates a new `Bits` from the given value.
+    #[inline]
+    pub fn new(value: u64) -> Self {
+        let () = Self::VALID_WIDTH;
+        Self(value)
+    }
+
+    /// Returns `true` if no bits above `N` are set.
+    #[inline]
+    pub fn is_in_range(&self) -> bool {
+        let () = Self::VALID_WIDTH;
+        self.0 <= Self::MAX
+    }
+
+    /// Consumes the `Bits`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> u64 {
+        self.0
+    }
+}
+
+impl<const N: u8> Deref for Bits<N> {
+    type Target = u64;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: u8> DerefMut for Bits<N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const N: u8> From<u64> for Bits<N> {
+    #[inline]
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: u8> Display for Bits<N> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:01$b}", self.0, N as usize)
+    }
+}
+
+/// A year that is stored in a 1 byte `YEAR` column, e.g. a birth year.
+///
+/// `YEAR` stores the years 1901 to 2155 and `0`. Writing any other year returns
+/// [`Error::OutOfRange`] before the query is sent.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::Year;
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Person {
+///     id: i64,
+///     birth_year: Year,
+/// }
+/// ```
+///
+/// [`Error::OutOfRange`]: crate::Error::OutOfRange
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(pub u16);
+
+impl Year {
+    /// Creates a new `Year` from the given value.
+    #[inline]
+    pub fn new(value: u16) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `Year`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if the year can be stored in a `YEAR` column.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.0 == 0 || (1901..=2155).contains(&self.0)
+    }
+}
+
+impl Deref for Year {
+    type Target = u16;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Year {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<u16> for Year {
+    #[inline]
+    fn from(value: u16) -> Self {
+        Self(value)
+    }
+}
+
+impl Display for Year {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A point with the coordinates `x` and `y` that is stored in a `POINT` column, e.g. a
+/// longitude and latitude.
+///
+/// Points are written using the `POINT(x, y)` function. Reading a point decodes the internal
+/// geometry format MySQL returns for the column, the select doesn't need `ST_AsText`. Only
+/// points with finite coordinates can be written, other points are written as `NULL`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::Point;
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Store {
+///     id: i64,
+///     location: Point,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Point {
+    /// The x coordinate, e.g. the longitude.
+    pub x: f64,
+    /// The y coordinate, e.g. the latitude.
+    pub y: f64,
+}
+
+impl Point {
+    /// Creates a new `Point` with the coordinates `x` and `y`.
+    #[inline]
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Parses a point from the internal geometry format of MySQL: The 4 byte SRID followed by
+    /// the well-known binary representation of the point.
+    pub(crate) fn from_geometry(value: &[u8]) -> Option<Self> {
+        let value: &[u8; 25] = value.try_into().ok()?;
+
+        // The byte order of the well-known binary: `0` is big endian, `1` little endian.
+        let big_endian = match value[4] {
+            0 => true,
+            1 => false,
+            _ => return None,
+        };
+
+        let u32_at = |i: usize| {
+            let bytes = [value[i], value[i + 1], value[i + 2], value[i + 3]];
+            if big_endian {
+                u32::from_be_bytes(bytes)
+            } else {
+                u32::from_le_bytes(bytes)
+            }
+        };
+        let f64_at = |i: usize| {
+            let bytes: [u8; 8] = value[i..i + 8].try_into().unwrap();
+            if big_endian {
+                f64::from_be_bytes(bytes)
+            } else {
+                f64::from_le_bytes(bytes)
+            }
+        };
+
+        // The geometry type `1` is a point.
+        if u32_at(5) != 1 {
+            return None;
+        }
+
+        Some(Self {
+            x: f64_at(9),
+            y: f64_at(17),
+        })
+    }
+}
+
+/// Formats the point as well-known text, e.g. `POINT(1.5 -2)`.
+impl Display for Point {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "POINT({} {})", self.x, self.y)
+    }
+}
+
+/// A Rust enum with unit variants that can be stored in an `ENUM` column using [`Enum`].
+///
+/// Reading a value that is not one of the [`VARIANTS`], e.g. a variant added by a newer version
+/// of the application, returns [`Error::UnknownEnumVariant`]. Types with a catch-all variant
+/// can return it from [`from_unknown`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use datastore_mysql::SqlEnum;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// enum Color {
+///     Red,
+///     Green,
+/// }
+///
+/// impl SqlEnum for Color {
+///     const VARIANTS: &'static [&'static str] = &["Red", "Green"];
+///
+///     fn as_str(&self) -> &'static str {
+///         match self {
+///             Self::Red => "Red",
+///             Self::Green => "Green",
+///         }
+///     }
+///
+///     fn from_variant(name: &str) -> Option<Self> {
+///         match name {
+///             "Red" => Some(Self::Red),
+///             "Green" => Some(Self::Green),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+///
+/// A catch-all variant keeps unknown values instead of failing the query:
+///
+/// ```
+/// use datastore_mysql::SqlEnum;
+///
+/// #[derive(Clone, Debug, PartialEq, Eq)]
+/// enum Status {
+///     Active,
+///     Other(String),
+/// }
+///
+/// impl SqlEnum for Status {
+///     const VARIANTS: &'static [&'static str] = &["Active"];
+///
+///     fn as_str(&self) -> &str {
+///         match self {
+///             Self::Active => "Active",
+///             Self::Other(name) => name,
+///         }
+///     }
+///
+///     fn from_variant(name: &str) -> Option<Self> {
+///         match name {
+///             "Active" => Some(Self::Active),
+///             _ => None,
+///         }
+///     }
+///
+///     fn from_unknown(name: &str) -> Option<Self> {
+///         Some(Self::Other(name.to_owned()))
+///     }
+/// }
+///
+/// assert_eq!(Status::from_unknown("Archived"), Some(Status::Other(String::from("Archived"))));
+/// ```
+///
+/// [`Error::UnknownEnumVariant`]: crate::Error::UnknownEnumVariant
+/// [`VARIANTS`]: Self::VARIANTS
+/// [`from_unknown`]: Self::from_unknown
+pub trait SqlEnum: Sized {
+    /// The names of all variants, in the order of the `ENUM` definition.
+    const VARIANTS: &'static [&'static str];
+
+    /// Returns the name of the variant. The name must be contained in [`VARIANTS`], writing
+    /// other names fails unless the strict sql mode is disabled.
+    ///
+    /// [`VARIANTS`]: Self::VARIANTS
+    fn as_str(&self) -> &str;
+
+    /// Returns the variant with the given `name`, or `None` if no variant has that name.
+    fn from_variant(name: &str) -> Option<Self>;
+
+    /// Returns the variant for a `name` that [`from_variant`] doesn't know, e.g. an
+    /// `Other(String)` variant. Returns `None` by default, reading an unknown name then returns
+    /// [`Error::UnknownEnumVariant`].
+    ///
+    /// [`Error::UnknownEnumVariant`]: crate::Error::UnknownEnumVariant
+    /// [`from_variant`]: Self::from_variant
+    #[inline]
+    fn from_unknown(name: &str) -> Option<Self> {
+        let _ = name;
+        None
+    }
+}
+
+/// A [`SqlEnum`] that is stored in an `ENUM` column.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Enum<T>(pub T);
+
+impl<T> Enum<T> {
+    /// Creates a new `Enum` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `Enum`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Enum<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Enum<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Enum<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// A collection of named members that can be stored in a `SET` column using [`Set`], e.g. a
+/// set of feature flags.
+///
+/// Member names must not contain `,`, MySQL separates the members of a `SET` value with `,`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeSet;
+///
+/// use datastore_mysql::SqlSet;
+///
+/// #[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// struct Features(BTreeSet<&'static str>);
+///
+/// impl SqlSet for Features {
+///     const MEMBERS: &'static [&'static str] = &["search", "export", "beta"];
+///
+///     fn members(&self) -> Vec<&'static str> {
+///         self.0.iter().copied().collect()
+///     }
+///
+///     fn from_members(names: &[&str]) -> Option<Self> {
+///         names
+///             .iter()
+///             .map(|name| Self::MEMBERS.iter().copied().find(|member| member == name))
+///             .collect::<Option<BTreeSet<_>>>()
+///             .map(Self)
+///     }
+/// }
+/// ```
+pub trait SqlSet: Sized {
+    /// The names of all members, in the order of the `SET` definition.
+    const MEMBERS: &'static [&'static str];
+
+    /// Returns the names of the members contained in the collection. All names must be
+    /// contained in [`MEMBERS`].
+    ///
+    /// [`MEMBERS`]: Self::MEMBERS
+    fn members(&self) -> Vec<&'static str>;
+
+    /// Returns the collection containing the members with the given `names`, or `None` if any
+    /// name is not a member.
+    fn from_members(names: &[&str]) -> Option<Self>;
+}
+
+/// A [`SqlSet`] that is stored in a `SET` column.
+///
+/// The value is written as the names of the contained members, separated by `,`. An empty
+/// collection is stored as `''`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Set<T>(pub T);
+
+impl<T> Set<T> {
+    /// Creates a new `Set` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `Set`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Set<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Set<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Set<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// A nested [`StoreData`] type whose fields are stored in columns of the parent table.
+///
+/// Each field of `T` is stored in a column named after the `Flatten` field and the field of
+/// `T`, separated by `_`. `Flatten` fields can be nested. An `Option<Flatten<T>>` is not
+/// supported, make the fields of `T` optional instead.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::Flatten;
+///
+/// #[derive(Clone, Debug, StoreData)]
+/// pub struct Address {
+///     city: String,
+///     zip: String,
+/// }
+///
+/// // Stored in the columns `id`, `address_city` and `address_zip`.
+/// #[derive(Debug, StoreData)]
+/// pub struct Person {
+///     id: i64,
+///     address: Flatten<Address>,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Flatten<T>(pub T);
+
+impl<T> Flatten<T> {
+    /// Creates a new `Flatten` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `Flatten`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Flatten<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Flatten<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Flatten<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// A list of values stored in a child table instead of a column of the parent table.
+///
+/// The values of a `Children` field are stored in the table `{parent}_{field}`, e.g. the
+/// `tags` of a `post` are stored in `post_tags`. The child table has a column for each column
+/// of the primary key of the parent, named `{parent}_{column}`, a `position` column storing the
+/// index of the value and a `value` column storing the value. The rows reference the parent row
+/// with a foreign key and are deleted with it. The parent table must have a [`PrimaryKey`],
+/// otherwise [`Error::NoPrimaryKey`] is returned.
+///
+/// [`create`] and [`create_strict`] create the child tables with the parent table. The
+/// inserting operations [`insert`], [`replace`] and [`insert_ignore`] of [`MySqlStore`] and
+/// [`Transaction`] insert the parent row and its child rows in a transaction. `replace` deletes
+/// the child rows of the replaced item, `insert_ignore` inserts no child rows if the item is
+/// skipped. [`get`], [`get_all`], [`get_one`], [`get_top`], [`get_ordered`], [`get_by_ids`],
+/// [`get_exactly_one`], [`get_page`] and [`get_after`] load the child rows with one additional
+/// query per `Children` field, selecting the child rows of all returned items.
+///
+/// All other operations return [`Error::UnsupportedOperation`] for items with `Children`
+/// fields instead of losing the child rows, e.g. [`update`], [`upsert`], [`save`],
+/// [`insert_many`], [`insert_batched`], [`insert_returning_id`], [`get_columns`],
+/// [`get_distinct`] and [`get_stream`]. Raw queries like [`query_raw`] can't load the child
+/// rows, they return the error when reading the rows.
+///
+/// `Children` only supports a single level, `T` can't contain another `Children`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{Children, PrimaryKey};
+///
+/// // Stored in the tables `post` and `post_tags`.
+/// #[derive(Debug, StoreData)]
+/// pub struct Post {
+///     id: PrimaryKey<i64>,
+///     tags: Children<String>,
+/// }
+/// ```
+///
+/// [`Error::NoPrimaryKey`]: crate::Error::NoPrimaryKey
+/// [`Error::UnsupportedOperation`]: crate::Error::UnsupportedOperation
+/// [`MySqlStore`]: crate::MySqlStore
+/// [`Transaction`]: crate::Transaction
+/// [`create`]: datastore::Store::create
+/// [`create_strict`]: crate::MySqlStore::create_strict
+/// [`insert`]: datastore::Store::insert
+/// [`replace`]: crate::MySqlStore::replace
+/// [`insert_ignore`]: crate::MySqlStore::insert_ignore
+/// [`get`]: datastore::Store::get
+/// [`get_all`]: datastore::Store::get_all
+/// [`get_one`]: datastore::Store::get_one
+/// [`get_top`]: crate::MySqlStore::get_top
+/// [`get_ordered`]: crate::MySqlStore::get_ordered
+/// [`get_by_ids`]: crate::MySqlStore::get_by_ids
+/// [`get_exactly_one`]: crate::MySqlStore::get_exactly_one
+/// [`get_page`]: crate::MySqlStore::get_page
+/// [`get_after`]: crate::MySqlStore::get_after
+/// [`update`]: crate::MySqlStore::update
+/// [`upsert`]: crate::MySqlStore::upsert
+/// [`save`]: crate::MySqlStore::save
+/// [`insert_many`]: crate::MySqlStore::insert_many
+/// [`insert_batched`]: crate::MySqlStore::insert_batched
+/// [`insert_returning_id`]: crate::MySqlStore::insert_returning_id
+/// [`get_columns`]: crate::MySqlStore::get_columns
+/// [`get_distinct`]: crate::MySqlStore::get_distinct
+/// [`get_stream`]: crate::MySqlStore::get_stream
+/// [`query_raw`]: crate::MySqlStore::query_raw
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Children<T>(pub Vec<T>);
+
+impl<T> Children<T> {
+    /// Creates a new `Children` from the given values.
+    #[inline]
+    pub fn new(values: Vec<T>) -> Self {
+        Self(values)
+    }
+
+    /// Consumes the `Children`, returning the wrapped values.
+    #[inline]
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Children<T> {
+    type Target = Vec<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Children<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for Children<T> {
+    #[inline]
+    fn from(values: Vec<T>) -> Self {
+        Self(values)
+    }
+}
+
+/// A timestamp column that is set by the database when a row is inserted.
+///
+/// The column is created with `DEFAULT CURRENT_TIMESTAMP(6)`. A `CreatedAt(None)` is not
+/// written, inserting it lets the database fill in the current time and updating it keeps the
+/// stored value. A `CreatedAt(Some(_))` is written like the wrapped value. Reading always
+/// returns `Some`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{CreatedAt, UpdatedAt};
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Post {
+///     id: i64,
+///     created_at: CreatedAt<chrono::NaiveDateTime>,
+///     updated_at: UpdatedAt<chrono::NaiveDateTime>,
+/// }
+///
+/// let post = Post {
+///     id: 1,
+///     created_at: CreatedAt::default(),
+///     updated_at: UpdatedAt::default(),
+/// };
+///
+/// // INSERT INTO post (id) VALUES (1)
+/// store.insert(store.descriptor::<Post>(), post).await?;
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreatedAt<T>(pub Option<T>);
+
+/// A timestamp column that is set by the database when a row is inserted or updated.
+///
+/// The column is created with `DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6)`. An
+/// `UpdatedAt(None)` is not written, inserting or updating it lets the database fill in the
+/// current time. See [`CreatedAt`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdatedAt<T>(pub Option<T>);
+
+/// A column that is filled in by the database with its `DEFAULT` value when it isn't written.
+///
+/// A `Defaulted(None)` is not written: Inserting it omits the column from the `INSERT` so the
+/// server applies the default of the column, updating it keeps the stored value. A
+/// `Defaulted(Some(_))` is written like the wrapped value. Use `Defaulted<Option<T>>` to insert
+/// an explicit `NULL` with `Defaulted(Some(None))` instead. Reading always returns `Some`.
+///
+/// The column is created like `T`, the default is defined by the schema, e.g. a migration.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::Defaulted;
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Account {
+///     id: i64,
+///     // Created with `DEFAULT 100`.
+///     credits: Defaulted<i64>,
+///     note: Option<String>,
+/// }
+///
+/// let account = Account {
+///     id: 1,
+///     credits: Defaulted::default(),
+///     note: None,
+/// };
+///
+/// // INSERT INTO account (id,note) VALUES (1,NULL)
+/// store.insert(store.descriptor::<Account>(), account).await?;
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Defaulted<T>(pub Option<T>);
+
+/// Implements the common traits of a column type wrapping an `Option<T>` that isn't written
+/// if it is `None`.
+macro_rules! impl_optional_column {
+    ($ty:ident) => {
+        impl<T> $ty<T> {
+            #[doc = concat!("Creates a new `", stringify!($ty), "` that writes the given value.")]
+            #[inline]
+            pub fn new(value: T) -> Self {
+                Self(Some(value))
+            }
+
+            #[doc = concat!("Consumes the `", stringify!($ty), "`, returning the wrapped value.")]
+            #[inline]
+            pub fn into_inner(self) -> Option<T> {
+                self.0
+            }
+        }
+
+        impl<T> Deref for $ty<T> {
+            type Target = Option<T>;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<T> DerefMut for $ty<T> {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<T> From<T> for $ty<T> {
+            #[inline]
+            fn from(value: T) -> Self {
+                Self(Some(value))
+            }
+        }
+    };
+}
+
+impl_optional_column!(CreatedAt);
+impl_optional_column!(UpdatedAt);
+impl_optional_column!(Defaulted);
+
+/// A date and time type that is stored in a `DATETIME(6)` column and can be used in
+/// [`CreatedAt`] and [`UpdatedAt`].
+///
+/// This trait is sealed. It is implemented for `chrono::NaiveDateTime` and
+/// `chrono::DateTime<Utc>` (requires the `chrono` feature), `time::OffsetDateTime` (requires
+/// the `time` feature) and [`AsTimestamp<T>`], which is stored in a `TIMESTAMP(6)` column
+/// instead.
+pub trait Timestamp: Write<MySqlStore> + Read<MySqlStore> + sealed::Sealed {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+#[cfg(feature = "chrono")]
+impl sealed::Sealed for chrono::NaiveDateTime {}
+#[cfg(feature = "chrono")]
+impl Timestamp for chrono::NaiveDateTime {}
+#[cfg(feature = "chrono")]
+impl sealed::Sealed for chrono::DateTime<chrono::Utc> {}
+#[cfg(feature = "chrono")]
+impl Timestamp for chrono::DateTime<chrono::Utc> {}
+#[cfg(feature = "time")]
+impl sealed::Sealed for time::OffsetDateTime {}
+#[cfg(feature = "time")]
+impl Timestamp for time::OffsetDateTime {}
+impl<T> sealed::Sealed for AsTimestamp<T> where T: Timestamp {}
+impl<T> Timestamp for AsTimestamp<T> where T: Timestamp {}
+
+/// A [`Timestamp`] that is stored in a `TIMESTAMP(6)` column instead of a `DATETIME(6)` column.
+///
+/// Both column types store the written date and time, but differ in their range and time zone
+/// handling:
+/// - `DATETIME` stores the value as written, without any time zone. It covers the years `1000`
+///   to `9999`.
+/// - `TIMESTAMP` converts the value from the `time_zone` of the session to UTC when writing and
+///   back when reading. It only covers `1970-01-01 00:00:01` to `2038-01-19 03:14:07` UTC,
+///   writing values outside of this range fails in strict mode and stores `0000-00-00 00:00:00`
+///   otherwise.
+///
+/// Values are written as UTC literals like for `DATETIME` columns, so the session must use the
+/// UTC time zone `+00:00`. This is the default of every connection. Setting
+/// [`MySqlStoreConfig::time_zone`] to another time zone shifts all written and read values of
+/// `TIMESTAMP` columns, but not of `DATETIME` columns.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{AsTimestamp, CreatedAt};
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Session {
+///     id: i64,
+///     // Created as `expires_at TIMESTAMP(6) NOT NULL`.
+///     expires_at: AsTimestamp<chrono::DateTime<chrono::Utc>>,
+///     // Created as `created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)`.
+///     created_at: CreatedAt<AsTimestamp<chrono::DateTime<chrono::Utc>>>,
+/// }
+/// ```
+///
+/// [`MySqlStoreConfig::time_zone`]: crate::MySqlStoreConfig::time_zone
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsTimestamp<T>(pub T);
+
+impl<T> AsTimestamp<T> {
+    /// Creates a new `AsTimestamp` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `AsTimestamp`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for AsTimestamp<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for AsTimestamp<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for AsTimestamp<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Display for AsTimestamp<T>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A column that is part of the primary key of the table.
+///
+/// All `PrimaryKey` fields of a type together form the primary key. Primary key columns are
+/// always `NOT NULL`.
+///
+/// A type may consist of only `PrimaryKey` fields, e.g. for association tables that only
+/// store the keys of two other tables. Upserting such a row leaves an existing row unchanged.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{PrimaryKey, Varchar};
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Person {
+///     id: PrimaryKey<i64>,
+///     name: Varchar<255>,
+/// }
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Membership {
+///     person_id: PrimaryKey<i64>,
+///     group_id: PrimaryKey<i64>,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PrimaryKey<T>(pub T);
+
+impl<T> PrimaryKey<T> {
+    /// Creates a new `PrimaryKey` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `PrimaryKey`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for PrimaryKey<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for PrimaryKey<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for PrimaryKey<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Display for PrimaryKey<T>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// An integer column that is filled in by the database using `AUTO_INCREMENT`.
+///
+/// The column must be part of the primary key, e.g. `PrimaryKey<AutoIncrement<u64>>`. Inserting
+/// `0` makes the database generate the next id, which is returned by
+/// [`MySqlStore::insert_returning_id`]. Only integer types can be auto-incremented, creating the
+/// table of any other type returns [`Error::InvalidAutoIncrement`].
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{AutoIncrement, PrimaryKey, Varchar};
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Person {
+///     id: PrimaryKey<AutoIncrement<u64>>,
+///     name: Varchar<255>,
+/// }
+/// ```
+///
+/// [`MySqlStore::insert_returning_id`]: crate::MySqlStore::insert_returning_id
+/// [`Error::InvalidAutoIncrement`]: crate::Error::InvalidAutoIncrement
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AutoIncrement<T>(pub T);
+
+impl<T> AutoIncrement<T> {
+    /// Creates a new `AutoIncrement` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `AutoIncrement`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for AutoIncrement<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for AutoIncrement<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for AutoIncrement<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Display for AutoIncrement<T>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// An integer column limited to the values in `MIN..=MAX`.
+///
+/// The column is created with a `CHECK` constraint rejecting other values, which is enforced by
+/// MySQL 8.0.16 and later. Writing a value outside of the range also returns
+/// [`Error::OutOfRange`] before the query is sent. Values read from the database are not checked.
+///
+/// Using a `Bounded` with `MIN` larger than `MAX` fails to compile. `Bounded` doesn't implement
+/// [`Default`], the default value of `T` may be outside of the range.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{Bounded, PrimaryKey};
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Order {
+///     id: PrimaryKey<u64>,
+///     // One of the status codes 1 to 5.
+///     status: Bounded<u8, 1, 5>,
+/// }
+/// ```
+///
+/// [`Error::OutOfRange`]: crate::Error::OutOfRange
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<T, const MIN: i64, const MAX: i64>(pub T);
+
+impl<T, const MIN: i64, const MAX: i64> Bounded<T, MIN, MAX> {
+    /// Evaluating the constant fails to compile if `MIN` is larger than `MAX`.
+    const VALID_RANGE: () = assert!(MIN <= MAX, "Bounded requires MIN <= MAX");
+
+    /// Creates a new `Bounded` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        let () = Self::VALID_RANGE;
+        Self(value)
+    }
+
+    /// Consumes the `Bounded`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> Bounded<T, MIN, MAX>
+where
+    T: Copy + Into<i128>,
+{
+    /// Returns `true` if the value is in the range `MIN..=MAX`.
+    #[inline]
+    pub fn is_in_range(&self) -> bool {
+        let () = Self::VALID_RANGE;
+        (i128::from(MIN)..=i128::from(MAX)).contains(&self.0.into())
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> Deref for Bounded<T, MIN, MAX> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> DerefMut for Bounded<T, MIN, MAX> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> From<T> for Bounded<T, MIN, MAX> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> Display for Bounded<T, MIN, MAX>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A column that is read as `T::default()` if it is missing from the row.
+///
+/// Reading a field whose column was not selected, e.g. because the field was added before the
+/// column was created on all servers, otherwise fails the whole row. Unlike `Option<T>`, a column
+/// that is present but `NULL` is still read by `T`. The column is written like `T`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{OrDefault, PrimaryKey, Varchar};
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Person {
+///     id: PrimaryKey<u64>,
+///     name: Varchar<255>,
+///     // Not yet created on all servers.
+///     nickname: OrDefault<Varchar<255>>,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrDefault<T>(pub T);
+
+impl<T> OrDefault<T> {
+    /// Creates a new `OrDefault` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `OrDefault`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for OrDefault<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for OrDefault<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for OrDefault<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Display for OrDefault<T>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A column that is created without `NOT NULL`, even though it's read and written as `T`.
+///
+/// Unlike `Option<T>` the Rust type has no `None`: Values are always written, and reading a
+/// `NULL` fails like for `T`. This allows other applications sharing the table to store `NULL`s
+/// in rows that are never read as `T`, e.g. rows filtered by a condition on the column.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::Nullable;
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Invoice {
+///     id: i64,
+///     // Created as `paid_at DATETIME(6)`, `NULL` for invoices written by the billing service.
+///     paid_at: Nullable<chrono::NaiveDateTime>,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Nullable<T>(pub T);
+
+impl<T> Nullable<T> {
+    /// Creates a new `Nullable` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `Nullable`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Nullable<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Nullable<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Nullable<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Display for Nullable<T>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A MySQL collation used by a [`Collated`] column.
+///
+/// # Examples
+///
+/// ```
+/// use datastore_mysql::Collation;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// pub struct Unicode;
+///
+/// impl Collation for Unicode {
+///     const NAME: &'static str = "utf8mb4_unicode_ci";
+/// }
+/// ```
+pub trait Collation {
+    /// The name of the collation, e.g. `utf8mb4_bin`.
+    const NAME: &'static str;
+}
+
+/// The case-sensitive `utf8mb4_bin` [`Collation`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CaseSensitive;
+
+impl Collation for CaseSensitive {
+    const NAME: &'static str = "utf8mb4_bin";
+}
+
+/// The case-insensitive `utf8mb4_general_ci` [`Collation`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CaseInsensitive;
+
+impl Collation for CaseInsensitive {
+    const NAME: &'static str = "utf8mb4_general_ci";
+}
+
+/// A string column that uses the [`Collation`] `C` instead of the default collation of the
+/// table.
+///
+/// The collation determines how values of the column are compared, e.g. whether equality
+/// conditions are case-sensitive. `T` must be a string type like `String` or [`Varchar<N>`].
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{CaseSensitive, Collated, Varchar};
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Person {
+///     id: i64,
+///     name: Collated<Varchar<255>, CaseSensitive>,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Collated<T, C>(pub T, PhantomData<C>);
+
+impl<T, C> Collated<T, C> {
+    /// Creates a new `Collated` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Consumes the `Collated`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, C> Deref for Collated<T, C> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, C> DerefMut for Collated<T, C> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, C> From<T> for Collated<T, C> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, C> Display for Collated<T, C>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// The name of the column of a [`Renamed`] field.
+///
+/// # Examples
+///
+/// ```
+/// use datastore_mysql::ColumnName;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// pub struct UserId;
+///
+/// impl ColumnName for UserId {
+///     const NAME: &'static str = "user_id";
+/// }
+/// ```
+pub trait ColumnName {
+    /// The name of the column, e.g. `user_id`.
+    const NAME: &'static str;
+}
+
+/// A field that is stored in the column [`ColumnName`] `N` instead of the column named after
+/// the field.
+///
+/// This maps a field to a column of an existing schema whose name differs from the name of the
+/// field. The column is used everywhere the field is, including the conditions of the
+/// generated query type. Columns passed by name, e.g. to [`Filter`], must use the name of the
+/// column. Within a [`Flatten`] field the column is prefixed like any other column.
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::{ColumnName, Renamed};
+///
+/// pub struct UserId;
+///
+/// impl ColumnName for UserId {
+///     const NAME: &'static str = "user_id";
+/// }
+///
+/// // Stored in the columns `user_id` and `name`.
+/// #[derive(Debug, StoreData)]
+/// pub struct User {
+///     id: Renamed<i64, UserId>,
+///     name: String,
+/// }
+/// ```
+///
+/// [`Filter`]: crate::Filter
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Renamed<T, N>(pub T, PhantomData<N>);
+
+impl<T, N> Renamed<T, N> {
+    /// Creates a new `Renamed` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Consumes the `Renamed`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, N> Deref for Renamed<T, N> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, N> DerefMut for Renamed<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, N> From<T> for Renamed<T, N> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, N> Display for Renamed<T, N>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A value that is stored as serialized JSON in a `JSON` column.
+///
+/// Values nested within the column can be filtered using [`Filter::json_path`].
+///
+/// # Examples
+///
+/// ```ignore
+/// use datastore::StoreData;
+/// use datastore_mysql::Json;
+///
+/// #[derive(Debug, StoreData)]
+/// pub struct Post {
+///     id: i64,
+///     tags: Json<Vec<String>>,
+/// }
+/// ```
+///
+/// [`Filter::json_path`]: crate::Filter::json_path
+#[cfg(feature = "json")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "json")]
+impl<T> Json<T> {
+    /// Creates a new `Json` from the given value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Consumes the `Json`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T> DerefMut for Json<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T> From<T> for Json<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
 
 impl Write<MySqlStore> for bool {
     fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
     where
         W: Writer<MySqlStore>,
     {
-        writer.write_bool(*self)
+        writer.write_bool(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_bool()
+    }
+}
+
+impl Write<MySqlStore> for i8 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_i8(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_i8()
+    }
+}
+
+impl Write<MySqlStore> for i16 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_i16(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_i16()
+    }
+}
+
+impl Write<MySqlStore> for i32 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_i32(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_i32()
+    }
+}
+
+impl Write<MySqlStore> for i64 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_i64(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_i64()
+    }
+}
+
+impl Write<MySqlStore> for u8 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_u8(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_u8()
+    }
+}
+
+impl Write<MySqlStore> for u16 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_u16(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_u16()
+    }
+}
+
+impl Write<MySqlStore> for u32 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_u32(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_u32()
+    }
+}
+
+impl Write<MySqlStore> for u64 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_u64(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_u64()
+    }
+}
+
+/// Writes the value as an unquoted literal into a `DECIMAL(39,0)` column.
+impl Write<MySqlStore> for i128 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        let value = self.to_string();
+        hints::with(
+            |hints| hints.literal = Some(value.clone()),
+            || writer.write_str(&value),
+        )
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("DECIMAL(39,0)")),
+            || writer.write_str(),
+        )
+    }
+}
+
+/// Writes the value as an unquoted literal into a `DECIMAL(39,0) UNSIGNED` column.
+impl Write<MySqlStore> for u128 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        let value = self.to_string();
+        hints::with(
+            |hints| hints.literal = Some(value.clone()),
+            || writer.write_str(&value),
+        )
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("DECIMAL(39,0) UNSIGNED")),
+            || writer.write_str(),
+        )
+    }
+}
+
+impl Write<MySqlStore> for f32 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_f32(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_f32()
+    }
+}
+
+impl Write<MySqlStore> for f64 {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_f64(*self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_f64()
+    }
+}
+
+/// Writes the `char` into a `CHAR(1)` column. Storing characters outside of the Basic
+/// Multilingual Plane requires the `utf8mb4` charset.
+impl Write<MySqlStore> for char {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(self.encode_utf8(&mut [0; 4]))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("CHAR(1)")),
+            || writer.write_str(),
+        )
+    }
+}
+
+impl Write<MySqlStore> for [u8] {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_bytes(self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_bytes()
+    }
+}
+
+impl Write<MySqlStore> for Vec<u8> {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_bytes(self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_bytes()
+    }
+}
+
+impl Write<MySqlStore> for str {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_str()
+    }
+}
+
+impl Write<MySqlStore> for String {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_str()
+    }
+}
+
+/// Implements `Write` for types that are written like a `str`.
+macro_rules! impl_write_str {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Write<MySqlStore> for $ty {
+                fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+                where
+                    W: Writer<MySqlStore>,
+                {
+                    writer.write_str(AsRef::<str>::as_ref(self))
+                }
+
+                fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+                where
+                    W: TypeWriter<MySqlStore>,
+                {
+                    writer.write_str()
+                }
+            }
+        )*
+    };
+}
+
+impl_write_str!(&str, &String, Cow<'_, str>, Box<str>, Rc<str>, Arc<str>);
+
+/// Implements `Write` for types that are written like a `[u8]`.
+macro_rules! impl_write_bytes {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Write<MySqlStore> for $ty {
+                fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+                where
+                    W: Writer<MySqlStore>,
+                {
+                    writer.write_bytes(AsRef::<[u8]>::as_ref(self))
+                }
+
+                fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+                where
+                    W: TypeWriter<MySqlStore>,
+                {
+                    writer.write_bytes()
+                }
+            }
+        )*
+    };
+}
+
+// Borrowed bytes allow writing an `Option<&[u8]>` without copying the bytes.
+impl_write_bytes!(
+    &[u8],
+    &Vec<u8>,
+    Cow<'_, [u8]>,
+    Box<[u8]>,
+    Rc<[u8]>,
+    Arc<[u8]>
+);
+
+impl<const N: usize> Write<MySqlStore> for Varchar<N> {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(&self.0)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(format!("VARCHAR({})", N)),
+            || writer.write_str(),
+        )
+    }
+}
+
+impl Write<MySqlStore> for MediumBytes {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.max_len = Some(MEDIUMBLOB_MAX_LEN),
+            || writer.write_bytes(&self.0),
+        )
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("MEDIUMBLOB")),
+            || writer.write_bytes(),
+        )
+    }
+}
+
+impl Write<MySqlStore> for LongBytes {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.max_len = Some(LONGBLOB_MAX_LEN),
+            || writer.write_bytes(&self.0),
+        )
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("LONGBLOB")),
+            || writer.write_bytes(),
+        )
+    }
+}
+
+impl Write<MySqlStore> for MediumText {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(&self.0)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("MEDIUMTEXT")),
+            || writer.write_str(),
+        )
+    }
+}
+
+impl Write<MySqlStore> for LongText {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(&self.0)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("LONGTEXT")),
+            || writer.write_str(),
+        )
+    }
+}
+
+impl<const M: u8, const D: u8> Write<MySqlStore> for FixedDecimal<M, D> {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_f64(self.0)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(format!("DECIMAL({},{})", M, D)),
+            || writer.write_f64(),
+        )
+    }
+}
+
+impl<const N: u8> Write<MySqlStore> for Bits<N> {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        let in_range = self.is_in_range();
+
+        hints::with(
+            |hints| {
+                hints.literal = Some(format!("b'{}'", self));
+                if !in_range {
+                    // `BIT(N)` holds at most 63 bits here, the maximum fits into an `i64`.
+                    hints.range = Some((0, Self::MAX as i64));
+                }
+            },
+            || writer.write_u64(self.0),
+        )
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        let () = Self::VALID_WIDTH;
+        hints::with(
+            |hints| hints.column_type = Some(format!("BIT({})", N)),
+            || writer.write_u64(),
+        )
+    }
+}
+
+impl Write<MySqlStore> for Year {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        if self.is_valid() {
+            writer.write_u16(self.0)
+        } else {
+            hints::with(
+                |hints| hints.range = Some((1901, 2155)),
+                || writer.write_u16(self.0),
+            )
+        }
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("YEAR")),
+            || writer.write_u16(),
+        )
+    }
+}
+
+impl Write<MySqlStore> for Point {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        // `POINT()` has no literals for `NaN` and the infinities.
+        let literal = if self.x.is_finite() && self.y.is_finite() {
+            format!("POINT({}, {})", self.x, self.y)
+        } else {
+            String::from("NULL")
+        };
+
+        hints::with(
+            |hints| hints.literal = Some(literal),
+            || writer.write_str(&self.to_string()),
+        )
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("POINT")),
+            || writer.write_bytes(),
+        )
+    }
+}
+
+/// # Panics
+///
+/// Panics if serializing the value fails.
+#[cfg(feature = "json")]
+impl<T> Write<MySqlStore> for Json<T>
+where
+    T: serde::Serialize,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        let value = serde_json::to_string(&self.0).expect("failed to serialize JSON value");
+        writer.write_str(&value)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("JSON")),
+            || writer.write_str(),
+        )
+    }
+}
+
+/// A `serde_json::Value` is stored in a `JSON` column like a [`Json`] value. A JSON `null` is
+/// written as the JSON literal `null`, use `Option<serde_json::Value>` for a nullable column
+/// that distinguishes it from a SQL `NULL`.
+#[cfg(feature = "json")]
+impl Write<MySqlStore> for serde_json::Value {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        Json(self).write(writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        Json::<Self>::write_type(writer)
+    }
+}
+
+/// A `HashMap` is stored as a JSON object in a `JSON` column. Keys are serialized as JSON
+/// strings.
+///
+/// # Panics
+///
+/// Panics if serializing the map fails, e.g. because a key can't be serialized as a string.
+#[cfg(feature = "json")]
+impl<K, V, S> Write<MySqlStore> for HashMap<K, V, S>
+where
+    K: serde::Serialize + Eq + Hash,
+    V: serde::Serialize,
+    S: BuildHasher,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        let value = serde_json::to_string(self).expect("failed to serialize JSON value");
+        writer.write_str(&value)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("JSON")),
+            || writer.write_str(),
+        )
+    }
+}
+
+impl<T> Write<MySqlStore> for Enum<T>
+where
+    T: SqlEnum,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(self.0.as_str())
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        let variants: Vec<String> = T::VARIANTS
+            .iter()
+            .map(|variant| format_value(*variant))
+            .collect();
+
+        hints::with(
+            |hints| hints.column_type = Some(format!("ENUM({})", variants.join(","))),
+            || writer.write_str(),
+        )
+    }
+}
+
+impl<T> Write<MySqlStore> for Set<T>
+where
+    T: SqlSet,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(&self.0.members().join(","))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        let members: Vec<String> = T::MEMBERS
+            .iter()
+            .map(|member| format_value(*member))
+            .collect();
+
+        hints::with(
+            |hints| hints.column_type = Some(format!("SET({})", members.join(","))),
+            || writer.write_str(),
+        )
+    }
+}
+
+/// Writes the address as a number into an `INT UNSIGNED` column.
+impl Write<MySqlStore> for Ipv4Addr {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_u32(u32::from(*self))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_u32()
+    }
+}
+
+/// Writes the octets of the address into a `BINARY(16)` column.
+impl Write<MySqlStore> for Ipv6Addr {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_bytes(&self.octets())
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("BINARY(16)")),
+            || writer.write_bytes(),
+        )
+    }
+}
+
+/// Writes the 4 or 16 octets of the address into a `VARBINARY(16)` column. This matches the
+/// format of the MySQL `INET6_ATON` function.
+impl Write<MySqlStore> for IpAddr {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        match self {
+            Self::V4(addr) => writer.write_bytes(&addr.octets()),
+            Self::V6(addr) => writer.write_bytes(&addr.octets()),
+        }
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("VARBINARY(16)")),
+            || writer.write_bytes(),
+        )
+    }
+}
+
+/// Writes the duration as the number of nanoseconds into a `BIGINT UNSIGNED` column.
+///
+/// Durations longer than `u64::MAX` nanoseconds (about 584 years) are saturated to `u64::MAX`
+/// nanoseconds.
+impl Write<MySqlStore> for Duration {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_u64(u64::try_from(self.as_nanos()).unwrap_or(u64::MAX))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_u64()
+    }
+}
+
+impl<T> Write<MySqlStore> for Option<T>
+where
+    T: Write<MySqlStore>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        match self {
+            Some(value) => value.write(writer),
+            None => {
+                hints::set_column_of_write::<T>();
+                hints::with(|hints| hints.null = true, || writer.write_str(""))
+            }
+        }
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(|hints| hints.nullable = true, || T::write_type(writer))
+    }
+}
+
+/// Writes the value of a column with a `DEFAULT`. `None` is not written, which lets the
+/// database fill in the `DEFAULT` or `ON UPDATE` value of the column.
+fn write_or_default<T, W>(value: &Option<T>, writer: &mut W) -> Result<(), W::Error>
+where
+    T: Write<MySqlStore>,
+    W: Writer<MySqlStore>,
+{
+    match value {
+        Some(value) => value.write(writer),
+        None => hints::with(|hints| hints.skip = true, || writer.write_str("")),
+    }
+}
+
+impl<T> Write<MySqlStore> for CreatedAt<T>
+where
+    T: Timestamp,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        write_or_default(&self.0, writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_default = Some("CURRENT_TIMESTAMP(6)"),
+            || T::write_type(writer),
+        )
+    }
+}
+
+impl<T> Write<MySqlStore> for Defaulted<T>
+where
+    T: Write<MySqlStore>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        write_or_default(&self.0, writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        T::write_type(writer)
+    }
+}
+
+impl<T> Write<MySqlStore> for UpdatedAt<T>
+where
+    T: Timestamp,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        write_or_default(&self.0, writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| {
+                hints.column_default = Some("CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6)")
+            },
+            || T::write_type(writer),
+        )
+    }
+}
+
+impl<T> Write<MySqlStore> for PrimaryKey<T>
+where
+    T: Write<MySqlStore>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        self.0.write(writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(|hints| hints.primary_key = true, || T::write_type(writer))
+    }
+}
+
+impl<T> Write<MySqlStore> for AutoIncrement<T>
+where
+    T: Write<MySqlStore>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        hints::with(|hints| hints.auto_increment = true, || self.0.write(writer))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.auto_increment = true,
+            || T::write_type(writer),
+        )
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> Write<MySqlStore> for Bounded<T, MIN, MAX>
+where
+    T: Write<MySqlStore> + Copy + Into<i128>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        if self.is_in_range() {
+            self.0.write(writer)
+        } else {
+            hints::with(
+                |hints| hints.range = Some((MIN, MAX)),
+                || self.0.write(writer),
+            )
+        }
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        let () = Self::VALID_RANGE;
+        hints::with(
+            |hints| hints.range = Some((MIN, MAX)),
+            || T::write_type(writer),
+        )
+    }
+}
+
+impl<T> Write<MySqlStore> for OrDefault<T>
+where
+    T: Write<MySqlStore>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        self.0.write(writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        T::write_type(writer)
+    }
+}
+
+impl<T> Write<MySqlStore> for AsTimestamp<T>
+where
+    T: Timestamp,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        self.0.write(writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        // All `Timestamp`s are written as strings, only the column type differs.
+        hints::with(
+            |hints| hints.column_type = Some(String::from("TIMESTAMP(6)")),
+            || writer.write_str(),
+        )
+    }
+}
+
+impl<T> Write<MySqlStore> for Nullable<T>
+where
+    T: Write<MySqlStore>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        self.0.write(writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(|hints| hints.nullable = true, || T::write_type(writer))
+    }
+}
+
+impl<T, C> Write<MySqlStore> for Collated<T, C>
+where
+    T: Write<MySqlStore>,
+    C: Collation,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        self.0.write(writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.collation = Some(C::NAME),
+            || T::write_type(writer),
+        )
+    }
+}
+
+impl<T, N> Write<MySqlStore> for Renamed<T, N>
+where
+    T: Write<MySqlStore>,
+    N: ColumnName,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        hints::set_column(N::NAME);
+        self.0.write(writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::set_column(N::NAME);
+        T::write_type(writer)
+    }
+}
+
+impl<T> Write<MySqlStore> for Flatten<T>
+where
+    T: StoreData<MySqlStore>,
+    T::Descriptor: Default,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        hints::flatten(|| self.0.write(writer))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::flatten(|| T::Descriptor::default().write(writer))
+    }
+}
+
+impl<T> Write<MySqlStore> for Children<T>
+where
+    T: Write<MySqlStore>,
+{
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        let values = mysql::write_child_values(&self.0);
+        hints::with(
+            |hints| hints.child = Some(values),
+            || writer.write_bool(false),
+        )
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        let column_type = mysql::write_child_type::<T>();
+        hints::with(
+            |hints| hints.child = Some(vec![column_type]),
+            || writer.write_bool(),
+        )
+    }
+}
+
+/// Writes a `DATETIME(6)` literal. MySQL rounds the value to microseconds.
+#[cfg(feature = "chrono")]
+impl Write<MySqlStore> for chrono::NaiveDateTime {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(&self.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("DATETIME(6)")),
+            || writer.write_str(),
+        )
+    }
+}
+
+/// Writes the UTC time as a `DATETIME(6)` literal.
+#[cfg(feature = "chrono")]
+impl Write<MySqlStore> for chrono::DateTime<chrono::Utc> {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        self.naive_utc().write(writer)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        chrono::NaiveDateTime::write_type(writer)
+    }
+}
+
+/// Writes the time converted to UTC as a `DATETIME(6)` literal. MySQL rounds the value to
+/// microseconds.
+///
+/// `DATETIME` columns are used instead of `TIMESTAMP` columns, which MySQL converts using the
+/// time zone of the session. Use [`AsTimestamp`] for a `TIMESTAMP` column. The value is always
+/// read back with a UTC offset.
+#[cfg(feature = "time")]
+impl Write<MySqlStore> for time::OffsetDateTime {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        let utc = self.to_offset(time::UtcOffset::UTC);
+        writer.write_str(&format!(
+            "{} {}",
+            format_date(utc.date()),
+            format_time(utc.time())
+        ))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("DATETIME(6)")),
+            || writer.write_str(),
+        )
+    }
+}
+
+/// Writes a `DATE` literal.
+#[cfg(feature = "time")]
+impl Write<MySqlStore> for time::Date {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(&format_date(*self))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("DATE")),
+            || writer.write_str(),
+        )
+    }
+}
+
+/// Writes a `TIME(6)` literal. MySQL rounds the value to microseconds.
+#[cfg(feature = "time")]
+impl Write<MySqlStore> for time::Time {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_str(&format_time(*self))
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("TIME(6)")),
+            || writer.write_str(),
+        )
+    }
+}
+
+/// Formats `date` as `YYYY-MM-DD`.
+#[cfg(feature = "time")]
+fn format_date(date: time::Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+/// Formats `time` as `HH:MM:SS`, followed by the fractional seconds in 3, 6 or 9 digits if
+/// they are not zero.
+#[cfg(feature = "time")]
+fn format_time(time: time::Time) -> String {
+    let mut string = format!(
+        "{:02}:{:02}:{:02}",
+        time.hour(),
+        time.minute(),
+        time.second()
+    );
+
+    match time.nanosecond() {
+        0 => (),
+        nanos if nanos % 1_000_000 == 0 => string.push_str(&format!(".{:03}", nanos / 1_000_000)),
+        nanos if nanos % 1_000 == 0 => string.push_str(&format!(".{:06}", nanos / 1_000)),
+        nanos => string.push_str(&format!(".{:09}", nanos)),
+    }
+
+    string
+}
+
+/// Writes the value as an unquoted literal into a `DECIMAL(65,30)` column.
+#[cfg(feature = "decimal")]
+impl Write<MySqlStore> for rust_decimal::Decimal {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        let value = self.to_string();
+        hints::with(
+            |hints| hints.literal = Some(value.clone()),
+            || writer.write_str(&value),
+        )
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("DECIMAL(65,30)")),
+            || writer.write_str(),
+        )
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Write<MySqlStore> for bytes::Bytes {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_bytes(self)
+    }
+
+    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+    where
+        W: TypeWriter<MySqlStore>,
+    {
+        writer.write_bytes()
+    }
+}
+
+/// Writes the bytes of the `Uuid` into a `BINARY(16)` column.
+#[cfg(feature = "uuid")]
+impl Write<MySqlStore> for uuid::Uuid {
+    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+    where
+        W: Writer<MySqlStore>,
+    {
+        writer.write_bytes(self.as_bytes())
     }
 
     fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        W: TypeWriter<MySqlStore>,
+    {
+        hints::with(
+            |hints| hints.column_type = Some(String::from("BINARY(16)")),
+            || writer.write_bytes(),
+        )
+    }
+}
+
+// === impl Read ===
+
+impl Read<MySqlStore> for bool {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_bool()
+    }
+}
+
+impl Read<MySqlStore> for i8 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_i8()
+    }
+}
+
+impl Read<MySqlStore> for i16 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_i16()
+    }
+}
+
+impl Read<MySqlStore> for i32 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_i32()
+    }
+}
+
+impl Read<MySqlStore> for i64 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_i64()
+    }
+}
+
+impl Read<MySqlStore> for u8 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_u8()
+    }
+}
+
+impl Read<MySqlStore> for u16 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_u16()
+    }
+}
+
+impl Read<MySqlStore> for u32 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_u32()
+    }
+}
+
+impl Read<MySqlStore> for u64 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_u64()
+    }
+}
+
+impl Read<MySqlStore> for i128 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        hints::read_parsed(reader)
+    }
+}
+
+impl Read<MySqlStore> for u128 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        hints::read_parsed(reader)
+    }
+}
+
+impl Read<MySqlStore> for f32 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_f32()
+    }
+}
+
+impl Read<MySqlStore> for f64 {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        reader.read_f64()
+    }
+}
+
+/// Returns an error if the column contains more than one character.
+impl Read<MySqlStore> for char {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
+    {
+        hints::read_with(reader, decode_char)
+    }
+}
+
+fn decode_char(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error> {
+    let value: &str = row.try_get(column)?;
+
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        // MySQL removes trailing spaces from CHAR columns.
+        (None, _) => Ok(Box::new(' ')),
+        (Some(c), None) => Ok(Box::new(c)),
+        (Some(_), Some(_)) => Err(sqlx::Error::ColumnDecode {
+            index: column.to_owned(),
+            source: "expected a single character".into(),
+        }),
+    }
+}
+
+impl Read<MySqlStore> for Vec<u8> {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+    where
+        R: Reader<MySqlStore>,
     {
-        writer.write_bool()
+        reader.read_byte_buf()
     }
 }
 
-impl Write<MySqlStore> for i8 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+impl Read<MySqlStore> for String {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_i8(*self)
+        reader.read_string()
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+/// Implements `Read` for owned types that are read like a `String`.
+macro_rules! impl_read_str {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Read<MySqlStore> for $ty {
+                fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+                where
+                    R: Reader<MySqlStore>,
+                {
+                    reader.read_string().map(Into::into)
+                }
+            }
+        )*
+    };
+}
+
+impl_read_str!(Cow<'_, str>, Box<str>, Rc<str>, Arc<str>);
+
+impl<const N: usize> Read<MySqlStore> for Varchar<N> {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_i8()
+        reader.read_string().map(Self)
     }
 }
 
-impl Write<MySqlStore> for i16 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+impl Read<MySqlStore> for MediumBytes {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_i16(*self)
+        reader.read_byte_buf().map(Self)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+impl Read<MySqlStore> for LongBytes {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_i16()
+        reader.read_byte_buf().map(Self)
     }
 }
 
-impl Write<MySqlStore> for i32 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+impl Read<MySqlStore> for MediumText {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_i32(*self)
+        reader.read_string().map(Self)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+impl Read<MySqlStore> for LongText {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_i32()
+        reader.read_string().map(Self)
     }
 }
 
-impl Write<MySqlStore> for i64 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+impl<const M: u8, const D: u8> Read<MySqlStore> for FixedDecimal<M, D> {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_i64(*self)
+        hints::read_with(reader, decode_fixed_decimal).map(Self)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+/// Decodes a `DECIMAL` column into an `f64`. sqlx only decodes `FLOAT` and `DOUBLE` columns
+/// into floats, but MySQL sends decimals as strings.
+fn decode_fixed_decimal(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error> {
+    let value: &str = row.try_get_unchecked(column)?;
+
+    match value.parse::<f64>() {
+        Ok(value) => Ok(Box::new(value)),
+        Err(err) => Err(sqlx::Error::ColumnDecode {
+            index: column.to_owned(),
+            source: err.into(),
+        }),
+    }
+}
+
+impl Read<MySqlStore> for Year {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_i64()
+        // `YEAR` columns are unsigned and decoded like a `SMALLINT UNSIGNED`.
+        reader.read_u16().map(Self)
     }
 }
 
-impl Write<MySqlStore> for u8 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+/// Returns an error if the column doesn't contain a point.
+impl Read<MySqlStore> for Point {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_u8(*self)
+        hints::read_with(reader, decode_point)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+fn decode_point(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error> {
+    // sqlx has no type for `GEOMETRY` columns, skip the type check of `&[u8]`.
+    let value: &[u8] = row.try_get_unchecked(column)?;
+
+    match Point::from_geometry(value) {
+        Some(point) => Ok(Box::new(point)),
+        None => Err(sqlx::Error::ColumnDecode {
+            index: column.to_owned(),
+            source: format!("invalid point of {} bytes", value.len()).into(),
+        }),
+    }
+}
+
+impl<const N: u8> Read<MySqlStore> for Bits<N> {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_u8()
+        let () = Self::VALID_WIDTH;
+        hints::read_with(reader, decode_bits).map(Self)
     }
 }
 
-impl Write<MySqlStore> for u16 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+fn decode_bits(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error> {
+    // sqlx decodes the big-endian bytes of a `BIT` column into an integer.
+    let value: u64 = row.try_get_unchecked(column)?;
+    Ok(Box::new(value))
+}
+
+#[cfg(feature = "json")]
+impl<T> Read<MySqlStore> for Json<T>
+where
+    T: serde::de::DeserializeOwned + 'static,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_u16(*self)
+        hints::read_with(reader, decode_json::<T>)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+/// A JSON `null` is read as `serde_json::Value::Null`. Reading a SQL `NULL` fails, use
+/// `Option<serde_json::Value>` for nullable columns.
+#[cfg(feature = "json")]
+impl Read<MySqlStore> for serde_json::Value {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_u16()
+        Json::<Self>::read(reader).map(Json::into_inner)
     }
 }
 
-impl Write<MySqlStore> for u32 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+#[cfg(feature = "json")]
+fn decode_json<T>(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error>
+where
+    T: serde::de::DeserializeOwned + 'static,
+{
+    // JSON columns are not compatible with `&str`, skip the type check.
+    let value: &str = row.try_get_unchecked(column)?;
+
+    match serde_json::from_str::<T>(value) {
+        Ok(value) => Ok(Box::new(Json(value))),
+        Err(err) => Err(sqlx::Error::ColumnDecode {
+            index: column.to_owned(),
+            source: Box::new(err),
+        }),
+    }
+}
+
+/// A `NULL` column or a JSON `null` is read as an empty map.
+#[cfg(feature = "json")]
+impl<K, V, S> Read<MySqlStore> for HashMap<K, V, S>
+where
+    K: serde::de::DeserializeOwned + Eq + Hash + 'static,
+    V: serde::de::DeserializeOwned + 'static,
+    S: BuildHasher + Default + 'static,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_u32(*self)
+        hints::read_with(reader, decode_json_map::<K, V, S>)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+#[cfg(feature = "json")]
+fn decode_json_map<K, V, S>(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error>
+where
+    K: serde::de::DeserializeOwned + Eq + Hash + 'static,
+    V: serde::de::DeserializeOwned + 'static,
+    S: BuildHasher + Default + 'static,
+{
+    // JSON columns are not compatible with `&str`, skip the type check.
+    let value: Option<&str> = row.try_get_unchecked(column)?;
+
+    let map = match value {
+        Some(value) => match serde_json::from_str::<Option<HashMap<K, V, S>>>(value) {
+            Ok(map) => map.unwrap_or_default(),
+            Err(err) => {
+                return Err(sqlx::Error::ColumnDecode {
+                    index: column.to_owned(),
+                    source: Box::new(err),
+                })
+            }
+        },
+        None => HashMap::default(),
+    };
+
+    Ok(Box::new(map))
+}
+
+impl<T> Read<MySqlStore> for Enum<T>
+where
+    T: SqlEnum + 'static,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_u32()
+        hints::read_with(reader, decode_enum::<T>)
     }
 }
 
-impl Write<MySqlStore> for u64 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+impl<T> Read<MySqlStore> for Set<T>
+where
+    T: SqlSet + 'static,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_u64(*self)
+        hints::read_with(reader, decode_set::<T>)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+fn decode_set<T>(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error>
+where
+    T: SqlSet + 'static,
+{
+    let value: &str = row.try_get(column)?;
+
+    // `''` is the empty set, not a set containing an empty member.
+    let names: Vec<&str> = if value.is_empty() {
+        Vec::new()
+    } else {
+        value.split(',').collect()
+    };
+
+    match T::from_members(&names) {
+        Some(value) => Ok(Box::new(Set(value))),
+        None => Err(sqlx::Error::ColumnDecode {
+            index: column.to_owned(),
+            source: format!("unknown set member in: {}", value).into(),
+        }),
+    }
+}
+
+fn decode_enum<T>(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error>
+where
+    T: SqlEnum + 'static,
+{
+    let value: &str = row.try_get(column)?;
+
+    match T::from_variant(value).or_else(|| T::from_unknown(value)) {
+        Some(value) => Ok(Box::new(Enum(value))),
+        None => Err(sqlx::Error::ColumnDecode {
+            index: column.to_owned(),
+            source: Box::new(UnknownVariant(value.to_owned())),
+        }),
+    }
+}
+
+/// The source of the decode error of an unknown [`SqlEnum`] variant, returned by the reader as
+/// [`Error::UnknownEnumVariant`].
+///
+/// [`Error::UnknownEnumVariant`]: crate::Error::UnknownEnumVariant
+#[derive(Debug)]
+pub(crate) struct UnknownVariant(pub String);
+
+impl Display for UnknownVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown enum variant: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownVariant {}
+
+impl Read<MySqlStore> for Ipv4Addr {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_u64()
+        reader.read_u32().map(Self::from)
     }
 }
 
-impl Write<MySqlStore> for f32 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+/// Returns an error if the column doesn't contain exactly 16 bytes.
+impl Read<MySqlStore> for Ipv6Addr {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_f32(*self)
+        hints::read_with(reader, decode_ipv6)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+/// Returns an error if the column doesn't contain exactly 4 or 16 bytes.
+impl Read<MySqlStore> for IpAddr {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_f32()
+        hints::read_with(reader, decode_ip)
     }
 }
 
-impl Write<MySqlStore> for f64 {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+fn decode_ipv6(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error> {
+    let value: &[u8] = row.try_get(column)?;
+
+    match <[u8; 16]>::try_from(value) {
+        Ok(octets) => Ok(Box::new(Ipv6Addr::from(octets))),
+        Err(_) => Err(invalid_ip(column, value)),
+    }
+}
+
+fn decode_ip(row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error> {
+    let value: &[u8] = row.try_get(column)?;
+
+    if let Ok(octets) = <[u8; 4]>::try_from(value) {
+        return Ok(Box::new(IpAddr::from(octets)));
+    }
+
+    match <[u8; 16]>::try_from(value) {
+        Ok(octets) => Ok(Box::new(IpAddr::from(octets))),
+        Err(_) => Err(invalid_ip(column, value)),
+    }
+}
+
+fn invalid_ip(column: &str, value: &[u8]) -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: column.to_owned(),
+        source: format!("invalid ip address of {} bytes", value.len()).into(),
+    }
+}
+
+impl Read<MySqlStore> for Duration {
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_f64(*self)
+        reader.read_u64().map(Self::from_nanos)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+impl<T> Read<MySqlStore> for Option<T>
+where
+    T: Read<MySqlStore>,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_f64()
+        hints::set_column_of_read::<T>();
+        let is_null = hints::with(|hints| hints.is_null = true, || reader.read_bool())?;
+
+        if is_null {
+            Ok(None)
+        } else {
+            T::read(reader).map(Some)
+        }
     }
 }
 
-impl Write<MySqlStore> for [u8] {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+impl<T, C> Read<MySqlStore> for Collated<T, C>
+where
+    T: Read<MySqlStore>,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_bytes(self)
+        T::read(reader).map(Self::new)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+impl<T, N> Read<MySqlStore> for Renamed<T, N>
+where
+    T: Read<MySqlStore>,
+    N: ColumnName,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_bytes()
+        hints::set_column(N::NAME);
+        T::read(reader).map(Self::new)
     }
 }
 
-impl Write<MySqlStore> for Vec<u8> {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+impl<T> Read<MySqlStore> for Flatten<T>
+where
+    T: StoreData<MySqlStore>,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_bytes(&self)
+        hints::flatten(|| T::read(reader)).map(Self)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+impl<T> Read<MySqlStore> for Children<T>
+where
+    T: Read<MySqlStore> + 'static,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_bytes()
+        hints::read_with(reader, decode_children::<T>).map(Self)
     }
 }
 
-impl Write<MySqlStore> for str {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+fn decode_children<T>(_row: &MySqlRow, column: &str) -> Result<Box<dyn Any>, sqlx::Error>
+where
+    T: Read<MySqlStore> + 'static,
+{
+    match mysql::read_child_values::<T>(column) {
+        Ok(values) => Ok(Box::new(values)),
+        Err(Error::Sqlx(err)) => Err(err),
+        Err(err) => Err(sqlx::Error::Decode(Box::new(err))),
+    }
+}
+
+impl<T> Read<MySqlStore> for CreatedAt<T>
+where
+    T: Timestamp,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_str(self)
+        T::read(reader).map(Self::new)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+impl<T> Read<MySqlStore> for UpdatedAt<T>
+where
+    T: Timestamp,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_str()
+        T::read(reader).map(Self::new)
     }
 }
 
-impl Write<MySqlStore> for String {
-    fn write<W>(&self, writer: &mut W) -> Result<(), W::Error>
+impl<T> Read<MySqlStore> for Defaulted<T>
+where
+    T: Read<MySqlStore>,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: Writer<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_str(&self)
+        T::read(reader).map(Self::new)
     }
+}
 
-    fn write_type<W>(writer: &mut W) -> Result<(), W::Error>
+impl<T> Read<MySqlStore> for PrimaryKey<T>
+where
+    T: Read<MySqlStore>,
+{
+    fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
-        W: TypeWriter<MySqlStore>,
+        R: Reader<MySqlStore>,
     {
-        writer.write_str()
+        T::read(reader).map(Self)
     }
 }
 
-// === impl Read ===
-
-impl Read<MySqlStore> for bool {
+impl<T> Read<MySqlStore> for AutoIncrement<T>
+where
+    T: Read<MySqlStore>,
+{
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_bool()
+        T::read(reader).map(Self)
     }
 }
 
-impl Read<MySqlStore> for i8 {
+impl<T, const MIN: i64, const MAX: i64> Read<MySqlStore> for Bounded<T, MIN, MAX>
+where
+    T: Read<MySqlStore>,
+{
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_i8()
+        let () = Self::VALID_RANGE;
+        T::read(reader).map(Self)
     }
 }
 
-impl Read<MySqlStore> for i16 {
+impl<T> Read<MySqlStore> for AsTimestamp<T>
+where
+    T: Timestamp,
+{
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_i16()
+        T::read(reader).map(Self)
     }
 }
 
-impl Read<MySqlStore> for i32 {
+impl<T> Read<MySqlStore> for Nullable<T>
+where
+    T: Read<MySqlStore>,
+{
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_i32()
+        T::read(reader).map(Self)
     }
 }
 
-impl Read<MySqlStore> for i64 {
+impl<T> Read<MySqlStore> for OrDefault<T>
+where
+    T: Read<MySqlStore> + Default,
+{
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_i64()
+        hints::set_column_of_read::<T>();
+        let is_missing = hints::with(|hints| hints.is_missing = true, || reader.read_bool())?;
+
+        if is_missing {
+            Ok(Self(T::default()))
+        } else {
+            T::read(reader).map(Self)
+        }
     }
 }
 
-impl Read<MySqlStore> for u8 {
+#[cfg(feature = "chrono")]
+impl Read<MySqlStore> for chrono::NaiveDateTime {
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_u8()
+        hints::read_decoded(reader)
     }
 }
 
-impl Read<MySqlStore> for u16 {
+#[cfg(feature = "chrono")]
+impl Read<MySqlStore> for chrono::DateTime<chrono::Utc> {
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_u16()
+        hints::read_decoded(reader)
     }
 }
 
-impl Read<MySqlStore> for u32 {
+#[cfg(feature = "time")]
+impl Read<MySqlStore> for time::OffsetDateTime {
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_u32()
+        hints::read_decoded(reader)
     }
 }
 
-impl Read<MySqlStore> for u64 {
+#[cfg(feature = "time")]
+impl Read<MySqlStore> for time::Date {
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_u64()
+        hints::read_decoded(reader)
     }
 }
 
-impl Read<MySqlStore> for f32 {
+#[cfg(feature = "time")]
+impl Read<MySqlStore> for time::Time {
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_f32()
+        hints::read_decoded(reader)
     }
 }
 
-impl Read<MySqlStore> for f64 {
+#[cfg(feature = "decimal")]
+impl Read<MySqlStore> for rust_decimal::Decimal {
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_f64()
+        hints::read_decoded(reader)
     }
 }
 
-impl Read<MySqlStore> for Vec<u8> {
+#[cfg(feature = "uuid")]
+impl Read<MySqlStore> for uuid::Uuid {
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_byte_buf()
+        hints::read_decoded(reader)
     }
 }
 
-impl Read<MySqlStore> for String {
+#[cfg(feature = "bytes")]
+impl Read<MySqlStore> for bytes::Bytes {
     fn read<R>(reader: &mut R) -> Result<Self, R::Error>
     where
         R: Reader<MySqlStore>,
     {
-        reader.read_string()
+        // `Bytes` takes ownership of the buffer without copying it.
+        reader.read_byte_buf().map(Self::from)
     }
 }
+
+/// Reads the elements of a tuple from consecutive columns, e.g. the columns of a row returned by
+/// [`MySqlStore::query_raw_as`]. The columns are read by position, not by name.
+///
+/// [`MySqlStore::query_raw_as`]: crate::MySqlStore::query_raw_as
+macro_rules! impl_read_tuple {
+    ($($t:ident),+) => {
+        impl<$($t),+> Read<MySqlStore> for ($($t,)+)
+        where
+            $($t: Read<MySqlStore>,)+
+        {
+            fn read<R>(reader: &mut R) -> Result<Self, R::Error>
+            where
+                R: Reader<MySqlStore>,
+            {
+                Ok(($($t::read(reader)?,)+))
+            }
+        }
+    };
+}
+
+impl_read_tuple!(A);
+impl_read_tuple!(A, B);
+impl_read_tuple!(A, B, C);
+impl_read_tuple!(A, B, C, D);
+impl_read_tuple!(A, B, C, D, E);
+impl_read_tuple!(A, B, C, D, E, F);
+impl_read_tuple!(A, B, C, D, E, F, G);
+impl_read_tuple!(A, B, C, D, E, F, G, H);
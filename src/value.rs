@@ -0,0 +1,339 @@
+use sqlx::error::BoxDynError;
+use sqlx::mysql::{MySqlArguments, MySqlValueRef};
+use sqlx::{Arguments, Decode, MySql, TypeInfo, ValueRef};
+
+use crate::dialect::{Dialect, MySqlDialect};
+use crate::mysql::format_value;
+
+/// A dynamically typed value, used for values whose type isn't known by a [`StoreData`] type.
+///
+/// `Value`s bound to a raw sql query are sent to the database separately from the sql and are
+/// never interpolated into it. Every `?` in the sql of the query is replaced by one `Value`, in
+/// order. `Value`s used to update single fields are bound like all values of generated queries,
+/// see [prepared statements].
+///
+/// See [`MySqlStore::query_raw`], [`MySqlStore::execute_raw`] and
+/// [`MySqlStore::update_fields`]. Rows read by [`MySqlStore::get_dynamic`] contain a `Value` for
+/// each column.
+///
+/// [`StoreData`]: datastore::StoreData
+/// [prepared statements]: crate#prepared-statements
+/// [`MySqlStore::query_raw`]: crate::MySqlStore::query_raw
+/// [`MySqlStore::execute_raw`]: crate::MySqlStore::execute_raw
+/// [`MySqlStore::update_fields`]: crate::MySqlStore::update_fields
+/// [`MySqlStore::get_dynamic`]: crate::MySqlStore::get_dynamic
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A `NULL` value.
+    Null,
+    /// A `BOOL` value.
+    Bool(bool),
+    /// A signed integer value.
+    Int(i64),
+    /// An unsigned integer value.
+    UInt(u64),
+    /// A floating point value.
+    Float(f64),
+    /// A string value.
+    Text(String),
+    /// A binary value.
+    Bytes(Vec<u8>),
+}
+
+impl Value {
+    /// Formats the value as a sql literal.
+    pub(crate) fn to_literal(&self) -> String {
+        match self {
+            Self::Null => String::from("NULL"),
+            Self::Bool(val) => format_value(val),
+            Self::Int(val) => format_value(val),
+            Self::UInt(val) => format_value(val),
+            Self::Float(val) => format_value(val),
+            Self::Text(val) => format_value(val),
+            Self::Bytes(val) => format_value(val),
+        }
+    }
+
+    /// Decodes a value of any column type returned by a prepared statement. All queries returning
+    /// rows are executed as prepared statements.
+    ///
+    /// Integers are decoded as `Int` or `UInt` depending on their signedness, `YEAR` and `BIT`
+    /// as `UInt`. Binary strings are decoded as `Bytes`. All other types, e.g. `DECIMAL`,
+    /// `DATETIME` or `JSON`, are decoded as `Text` formatted like MySQL.
+    pub(crate) fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        if value.is_null() {
+            return Ok(Self::Null);
+        }
+
+        let ty = value.type_info().into_owned();
+        match ty.name() {
+            "BOOLEAN" => Ok(Self::Bool(<i64 as Decode<MySql>>::decode(value)? != 0)),
+            "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "BIGINT" => {
+                Ok(Self::Int(<i64 as Decode<MySql>>::decode(value)?))
+            }
+            "TINYINT UNSIGNED" | "SMALLINT UNSIGNED" | "MEDIUMINT UNSIGNED" | "INT UNSIGNED"
+            | "BIGINT UNSIGNED" | "YEAR" | "BIT" => {
+                Ok(Self::UInt(<u64 as Decode<MySql>>::decode(value)?))
+            }
+            "FLOAT" => Ok(Self::Float(<f32 as Decode<MySql>>::decode(value)?.into())),
+            "DOUBLE" => Ok(Self::Float(<f64 as Decode<MySql>>::decode(value)?)),
+            "DATE" => {
+                format_datetime(<&[u8] as Decode<MySql>>::decode(value)?, false).map(Self::Text)
+            }
+            "DATETIME" | "TIMESTAMP" => {
+                format_datetime(<&[u8] as Decode<MySql>>::decode(value)?, true).map(Self::Text)
+            }
+            "TIME" => format_time(<&[u8] as Decode<MySql>>::decode(value)?).map(Self::Text),
+            "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB"
+            | "GEOMETRY" => Ok(Self::Bytes(
+                <&[u8] as Decode<MySql>>::decode(value)?.to_vec(),
+            )),
+            _ => Ok(Self::Text(
+                <&str as Decode<MySql>>::decode(value)?.to_owned(),
+            )),
+        }
+    }
+
+    /// Parses a literal written by the writer of the store, the inverse of [`Value::to_literal`].
+    ///
+    /// Returns `None` for all literals that can't be bound without changing their meaning:
+    /// `DECIMAL` literals and integers outside of the 64 bit range would be bound as a string or
+    /// float, sql expressions like `POINT(1, 2)` or `b'101'` are not values at all.
+    pub(crate) fn from_literal(literal: &str) -> Option<Self> {
+        match literal {
+            "NULL" => return Some(Self::Null),
+            "TRUE" => return Some(Self::Bool(true)),
+            "FALSE" => return Some(Self::Bool(false)),
+            _ => (),
+        }
+
+        if let Some(string) = literal
+            .strip_prefix('\'')
+            .and_then(|literal| literal.strip_suffix('\''))
+        {
+            return unescape_string(string).map(Self::Text);
+        }
+
+        if let Some(hex) = literal.strip_prefix("0x") {
+            return decode_hex(hex).map(Self::Bytes);
+        }
+
+        // Floats are written as `DOUBLE` literals with an exponent, e.g. `1.5E0`.
+        if let Some(float) = literal.strip_suffix("E0") {
+            let digits = float.strip_prefix('-').unwrap_or(float);
+            let (int, frac) = digits.split_once('.').unwrap_or((digits, "0"));
+            if !is_integer(int) || frac.is_empty() || !frac.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            return float.parse().ok().map(Self::Float);
+        }
+
+        if !is_integer(literal.strip_prefix('-').unwrap_or(literal)) {
+            return None;
+        }
+
+        match literal.parse() {
+            Ok(val) => Some(Self::Int(val)),
+            Err(_) => literal.parse().ok().map(Self::UInt),
+        }
+    }
+
+    /// Adds the value as the next parameter to `arguments`.
+    pub(crate) fn bind(&self, arguments: &mut MySqlArguments) {
+        match self {
+            Self::Null => arguments.add(None::<i64>),
+            Self::Bool(val) => arguments.add(*val),
+            Self::Int(val) => arguments.add(*val),
+            Self::UInt(val) => arguments.add(*val),
+            Self::Float(val) => arguments.add(*val),
+            Self::Text(val) => arguments.add(val.clone()),
+            Self::Bytes(val) => arguments.add(val.clone()),
+        }
+    }
+}
+
+/// Returns `true` if `digits` is an unsigned integer without leading zeros.
+fn is_integer(digits: &str) -> bool {
+    match digits.as_bytes() {
+        [] => false,
+        [b'0'] => true,
+        [b'0', ..] => false,
+        bytes => bytes.iter().all(u8::is_ascii_digit),
+    }
+}
+
+/// Unescapes the content of a string literal quoted by [`MySqlDialect`]. Returns `None` if it
+/// contains any other escape sequence.
+fn unescape_string(string: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(string.len());
+    let mut chars = string.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' | '\'' if chars.next() == Some(c) => unescaped.push(c),
+            '\\' | '\'' => return None,
+            c => unescaped.push(c),
+        }
+    }
+
+    Some(unescaped)
+}
+
+/// Decodes the hex digits of a `0x` literal.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 == 1 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
+
+/// Returns the payload of a binary temporal value, which is prefixed by its length.
+fn temporal_payload(buf: &[u8]) -> Result<&[u8], BoxDynError> {
+    let (len, buf) = buf.split_first().ok_or("empty temporal value")?;
+    buf.get(..usize::from(*len))
+        .ok_or_else(|| "truncated temporal value".into())
+}
+
+/// Formats a binary `DATE`, `DATETIME` or `TIMESTAMP` value like MySQL, e.g.
+/// `2024-01-31 12:30:00`. Only values with a time part include the time.
+pub(crate) fn format_datetime(buf: &[u8], with_time: bool) -> Result<String, BoxDynError> {
+    let buf = temporal_payload(buf)?;
+
+    // Trailing zero parts are omitted: 0, 4, 7 or 11 bytes are sent.
+    let mut bytes = [0; 11];
+    bytes[..buf.len().min(11)].copy_from_slice(&buf[..buf.len().min(11)]);
+
+    let year = u16::from_le_bytes([bytes[0], bytes[1]]);
+    let mut string = format!("{:04}-{:02}-{:02}", year, bytes[2], bytes[3]);
+
+    if with_time {
+        string.push_str(&format!(" {:02}:{:02}:{:02}", bytes[4], bytes[5], bytes[6]));
+
+        let micros = u32::from_le_bytes([bytes[7], bytes[8], bytes[9], bytes[10]]);
+        if micros != 0 {
+            string.push_str(&format!(".{:06}", micros));
+        }
+    }
+
+    Ok(string)
+}
+
+/// Formats a binary `TIME` value like MySQL, e.g. `-838:59:59`.
+pub(crate) fn format_time(buf: &[u8]) -> Result<String, BoxDynError> {
+    let buf = temporal_payload(buf)?;
+
+    // Trailing zero parts are omitted: 0, 8 or 12 bytes are sent.
+    let mut bytes = [0; 12];
+    bytes[..buf.len().min(12)].copy_from_slice(&buf[..buf.len().min(12)]);
+
+    let days = u32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+    let hours = u64::from(days) * 24 + u64::from(bytes[5]);
+    let mut string = format!(
+        "{}{:02}:{:02}:{:02}",
+        if bytes[0] != 0 { "-" } else { "" },
+        hours,
+        bytes[6],
+        bytes[7]
+    );
+
+    let micros = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+    if micros != 0 {
+        string.push_str(&format!(".{:06}", micros));
+    }
+
+    Ok(string)
+}
+
+macro_rules! impl_from {
+    ($($t:ty => $variant:ident),*$(,)?) => {
+        $(
+            impl From<$t> for Value {
+                #[inline]
+                fn from(val: $t) -> Self {
+                    Self::$variant(val.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from! {
+    bool => Bool,
+    i8 => Int,
+    i16 => Int,
+    i32 => Int,
+    i64 => Int,
+    u8 => UInt,
+    u16 => UInt,
+    u32 => UInt,
+    u64 => UInt,
+    f32 => Float,
+    f64 => Float,
+    &str => Text,
+    String => Text,
+    &[u8] => Bytes,
+    Vec<u8> => Bytes,
+}
+
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    #[inline]
+    fn from(val: Option<T>) -> Self {
+        match val {
+            Some(val) => val.into(),
+            None => Self::Null,
+        }
+    }
+}
+
+/// The new value of a column updated by [`MySqlStore::update_exprs`], calculated by the
+/// database from the current value of the column.
+///
+/// This allows atomic updates like incrementing a counter without reading the item first.
+/// Only these expressions are supported. The operands are written into the expression as escaped
+/// literals, only `Set` values are bound like the values of [`MySqlStore::update_fields`].
+///
+/// [`MySqlStore::update_exprs`]: crate::MySqlStore::update_exprs
+/// [`MySqlStore::update_fields`]: crate::MySqlStore::update_fields
+#[derive(Clone, Debug, PartialEq)]
+pub enum UpdateExpr {
+    /// Sets the column to the value (`col = value`).
+    Set(Value),
+    /// Adds the value to the column (`col = col + value`).
+    Increment(Value),
+    /// Subtracts the value from the column (`col = col - value`).
+    Decrement(Value),
+    /// Sets the column to the larger of its value and the value (`col = GREATEST(col, value)`).
+    Greatest(Value),
+    /// Sets the column to the smaller of its value and the value (`col = LEAST(col, value)`).
+    Least(Value),
+}
+
+impl UpdateExpr {
+    /// Formats the new value of `column` as sql.
+    pub(crate) fn to_sql(&self, column: &str) -> String {
+        let column = MySqlDialect.quote_ident(column);
+
+        match self {
+            Self::Set(value) => value.to_literal(),
+            Self::Increment(value) => format!("{} + {}", column, value.to_literal()),
+            Self::Decrement(value) => format!("{} - {}", column, value.to_literal()),
+            Self::Greatest(value) => format!("GREATEST({},{})", column, value.to_literal()),
+            Self::Least(value) => format!("LEAST({},{})", column, value.to_literal()),
+        }
+    }
+}
+
+impl From<Value> for UpdateExpr {
+    #[inline]
+    fn from(value: Value) -> Self {
+        Self::Set(value)
+    }
+}
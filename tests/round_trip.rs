@@ -0,0 +1,831 @@
+//! Round trips of every supported type through a real MySQL server.
+//!
+//! All tests are ignored by default. They require the MySQL server at `DATABASE_URL` or a
+//! docker daemon to start a container, see the [`common`] module:
+//!
+//! ```text
+//! cargo test --all-features -- --ignored
+//! ```
+//!
+//! Every test uses its own tables, so the tests can run in parallel against the same database.
+
+mod common;
+
+use std::borrow::Cow;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+use datastore::{Store, StoreData};
+use datastore_mysql::{
+    Bits, ColumnName, Enum, FixedDecimal, LongBytes, LongText, MediumBytes, MediumText, OrDefault,
+    Point, PrimaryKey, Renamed, Set, SqlEnum, SqlSet, Varchar, Year,
+};
+
+use common::connect;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Color {
+    Red,
+    Green,
+}
+
+impl SqlEnum for Color {
+    const VARIANTS: &'static [&'static str] = &["Red", "Green"];
+
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Red => "Red",
+            Self::Green => "Green",
+        }
+    }
+
+    fn from_variant(name: &str) -> Option<Self> {
+        match name {
+            "Red" => Some(Self::Red),
+            "Green" => Some(Self::Green),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Permissions {
+    read: bool,
+    write: bool,
+}
+
+impl SqlSet for Permissions {
+    const MEMBERS: &'static [&'static str] = &["read", "write"];
+
+    fn members(&self) -> Vec<&'static str> {
+        let mut members = Vec::new();
+        if self.read {
+            members.push("read");
+        }
+        if self.write {
+            members.push("write");
+        }
+        members
+    }
+
+    fn from_members(names: &[&str]) -> Option<Self> {
+        let mut permissions = Self::default();
+        for name in names {
+            match *name {
+                "read" => permissions.read = true,
+                "write" => permissions.write = true,
+                _ => return None,
+            }
+        }
+        Some(permissions)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, StoreData)]
+#[datastore(name = "round_trip_integers")]
+struct Integers {
+    id: PrimaryKey<i32>,
+    boolean: bool,
+    a: i8,
+    b: i16,
+    c: i32,
+    d: i64,
+    e: i128,
+    f: u8,
+    g: u16,
+    h: u32,
+    i: u64,
+    j: u128,
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_integers() {
+    let store = connect().await;
+
+    store
+        .drop_table::<Integers, _>(IntegersDescriptor)
+        .await
+        .unwrap();
+    store
+        .create::<Integers, _>(IntegersDescriptor)
+        .await
+        .unwrap();
+
+    let items = vec![
+        Integers {
+            id: PrimaryKey(1),
+            boolean: false,
+            a: i8::MIN,
+            b: i16::MIN,
+            c: i32::MIN,
+            d: i64::MIN,
+            e: i64::MIN as i128,
+            f: u8::MIN,
+            g: u16::MIN,
+            h: u32::MIN,
+            i: u64::MIN,
+            j: u128::MIN,
+        },
+        Integers {
+            id: PrimaryKey(2),
+            boolean: true,
+            a: i8::MAX,
+            b: i16::MAX,
+            c: i32::MAX,
+            d: i64::MAX,
+            e: i64::MAX as i128,
+            f: u8::MAX,
+            g: u16::MAX,
+            h: u32::MAX,
+            i: u64::MAX,
+            j: u64::MAX as u128,
+        },
+    ];
+    for item in &items {
+        store
+            .insert(IntegersDescriptor, item.clone())
+            .await
+            .unwrap();
+    }
+
+    let mut read: Vec<Integers> = store.get_all(IntegersDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id.0);
+    assert_eq!(read, items);
+
+    store
+        .drop_table::<Integers, _>(IntegersDescriptor)
+        .await
+        .unwrap();
+}
+
+#[derive(Clone, Debug, PartialEq, StoreData)]
+#[datastore(name = "round_trip_floats")]
+struct Floats {
+    id: PrimaryKey<i32>,
+    a: f32,
+    b: f64,
+    c: FixedDecimal<10, 2>,
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_floats() {
+    let store = connect().await;
+
+    store
+        .drop_table::<Floats, _>(FloatsDescriptor)
+        .await
+        .unwrap();
+    store.create::<Floats, _>(FloatsDescriptor).await.unwrap();
+
+    // `f32` values with few significant digits are returned unchanged.
+    let items = vec![
+        Floats {
+            id: PrimaryKey(1),
+            a: 1.5,
+            b: std::f64::consts::PI,
+            c: FixedDecimal(19.99),
+        },
+        Floats {
+            id: PrimaryKey(2),
+            a: -0.25,
+            b: f64::MIN_POSITIVE,
+            c: FixedDecimal(-12345678.9),
+        },
+    ];
+    for item in &items {
+        store.insert(FloatsDescriptor, item.clone()).await.unwrap();
+    }
+
+    let mut read: Vec<Floats> = store.get_all(FloatsDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id.0);
+    assert_eq!(read, items);
+
+    store
+        .drop_table::<Floats, _>(FloatsDescriptor)
+        .await
+        .unwrap();
+}
+
+#[derive(Clone, Debug, PartialEq, StoreData)]
+#[datastore(name = "round_trip_strings")]
+struct Strings {
+    id: PrimaryKey<i32>,
+    a: char,
+    b: String,
+    c: Cow<'static, str>,
+    d: Box<str>,
+    e: Varchar<16>,
+    f: MediumText,
+    g: LongText,
+    h: Vec<u8>,
+    i: MediumBytes,
+    j: LongBytes,
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_strings() {
+    let store = connect().await;
+
+    store
+        .drop_table::<Strings, _>(StringsDescriptor)
+        .await
+        .unwrap();
+    store.create::<Strings, _>(StringsDescriptor).await.unwrap();
+
+    let items = vec![
+        Strings {
+            id: PrimaryKey(1),
+            a: 'a',
+            b: String::new(),
+            c: Cow::Borrowed(""),
+            d: Box::from(""),
+            e: Varchar(String::new()),
+            f: MediumText(String::new()),
+            g: LongText(String::new()),
+            h: Vec::new(),
+            i: MediumBytes(Vec::new()),
+            j: LongBytes(Vec::new()),
+        },
+        // Quotes, backslashes and multi-byte characters must be escaped correctly.
+        Strings {
+            id: PrimaryKey(2),
+            a: 'ß',
+            b: String::from("it's a \\ \"test\""),
+            c: Cow::Owned(String::from("日本語")),
+            d: Box::from("🦀"),
+            e: Varchar(String::from("O'Brien")),
+            f: MediumText(String::from("line\nbreak")),
+            g: LongText("x".repeat(70_000)),
+            h: vec![0, b'\'', b'\\', 255],
+            i: MediumBytes(vec![1, 2, 3]),
+            j: LongBytes(vec![0; 70_000]),
+        },
+    ];
+    for item in &items {
+        store.insert(StringsDescriptor, item.clone()).await.unwrap();
+    }
+
+    let mut read: Vec<Strings> = store.get_all(StringsDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id.0);
+    assert_eq!(read, items);
+
+    store
+        .drop_table::<Strings, _>(StringsDescriptor)
+        .await
+        .unwrap();
+}
+
+#[derive(Clone, Debug, PartialEq, StoreData)]
+#[datastore(name = "round_trip_misc")]
+struct Misc {
+    id: PrimaryKey<i32>,
+    a: Ipv4Addr,
+    b: Ipv6Addr,
+    c: IpAddr,
+    d: Duration,
+    e: Bits<12>,
+    f: Year,
+    g: Point,
+    h: Enum<Color>,
+    i: Set<Permissions>,
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_misc() {
+    let store = connect().await;
+
+    store.drop_table::<Misc, _>(MiscDescriptor).await.unwrap();
+    store.create::<Misc, _>(MiscDescriptor).await.unwrap();
+
+    let items = vec![
+        Misc {
+            id: PrimaryKey(1),
+            a: Ipv4Addr::new(127, 0, 0, 1),
+            b: Ipv6Addr::LOCALHOST,
+            c: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            d: Duration::ZERO,
+            e: Bits(0),
+            f: Year(1901),
+            g: Point::new(0.0, 0.0),
+            h: Enum(Color::Red),
+            i: Set(Permissions::default()),
+        },
+        Misc {
+            id: PrimaryKey(2),
+            a: Ipv4Addr::BROADCAST,
+            b: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+            c: IpAddr::V6(Ipv6Addr::LOCALHOST),
+            d: Duration::new(86_400, 123_456_789),
+            e: Bits(0xfff),
+            f: Year(2155),
+            g: Point::new(13.405, 52.52),
+            h: Enum(Color::Green),
+            i: Set(Permissions {
+                read: true,
+                write: true,
+            }),
+        },
+    ];
+    for item in &items {
+        store.insert(MiscDescriptor, item.clone()).await.unwrap();
+    }
+
+    let mut read: Vec<Misc> = store.get_all(MiscDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id.0);
+    assert_eq!(read, items);
+
+    store.drop_table::<Misc, _>(MiscDescriptor).await.unwrap();
+}
+
+#[derive(Clone, Debug, PartialEq, StoreData)]
+#[datastore(name = "round_trip_options")]
+struct Options {
+    id: PrimaryKey<i32>,
+    a: Option<bool>,
+    b: Option<i64>,
+    c: Option<u64>,
+    d: Option<f64>,
+    e: Option<String>,
+    f: Option<Vec<u8>>,
+    g: Option<Year>,
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_options() {
+    let store = connect().await;
+
+    store
+        .drop_table::<Options, _>(OptionsDescriptor)
+        .await
+        .unwrap();
+    store.create::<Options, _>(OptionsDescriptor).await.unwrap();
+
+    let items = vec![
+        Options {
+            id: PrimaryKey(1),
+            a: None,
+            b: None,
+            c: None,
+            d: None,
+            e: None,
+            f: None,
+            g: None,
+        },
+        Options {
+            id: PrimaryKey(2),
+            a: Some(true),
+            b: Some(-1),
+            c: Some(u64::MAX),
+            d: Some(0.5),
+            e: Some(String::new()),
+            f: Some(Vec::new()),
+            g: Some(Year(2024)),
+        },
+    ];
+    for item in &items {
+        store.insert(OptionsDescriptor, item.clone()).await.unwrap();
+    }
+
+    let mut read: Vec<Options> = store.get_all(OptionsDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id.0);
+    assert_eq!(read, items);
+
+    // Non-finite floats are written as `NULL`.
+    let item = Options {
+        id: PrimaryKey(3),
+        d: Some(f64::NAN),
+        ..items[0].clone()
+    };
+    store.insert(OptionsDescriptor, item).await.unwrap();
+
+    let read: Option<Options> = store
+        .get_one(OptionsDescriptor, OptionsQuery::default().id(PrimaryKey(3)))
+        .await
+        .unwrap();
+    assert_eq!(read.unwrap().d, None);
+
+    store
+        .drop_table::<Options, _>(OptionsDescriptor)
+        .await
+        .unwrap();
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct OwnerId;
+
+impl ColumnName for OwnerId {
+    const NAME: &'static str = "owner_id";
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Rank;
+
+impl ColumnName for Rank {
+    const NAME: &'static str = "user_rank";
+}
+
+#[derive(Clone, Debug, PartialEq, StoreData)]
+#[datastore(name = "round_trip_renamed")]
+struct RenamedOptions {
+    id: PrimaryKey<i32>,
+    owner: Option<Renamed<i64, OwnerId>>,
+    rank: OrDefault<Renamed<i32, Rank>>,
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_renamed_options() {
+    let store = connect().await;
+
+    store
+        .drop_table::<RenamedOptions, _>(RenamedOptionsDescriptor)
+        .await
+        .unwrap();
+    store
+        .create::<RenamedOptions, _>(RenamedOptionsDescriptor)
+        .await
+        .unwrap();
+
+    let items = vec![
+        RenamedOptions {
+            id: PrimaryKey(1),
+            owner: None,
+            rank: OrDefault(Renamed::new(0)),
+        },
+        RenamedOptions {
+            id: PrimaryKey(2),
+            owner: Some(Renamed::new(42)),
+            rank: OrDefault(Renamed::new(7)),
+        },
+    ];
+    for item in &items {
+        store
+            .insert(RenamedOptionsDescriptor, item.clone())
+            .await
+            .unwrap();
+    }
+
+    let mut read: Vec<RenamedOptions> = store.get_all(RenamedOptionsDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id.0);
+    assert_eq!(read, items);
+
+    let read: Vec<RenamedOptions> = store
+        .get(
+            RenamedOptionsDescriptor,
+            RenamedOptionsQuery::default().owner(None),
+        )
+        .await
+        .unwrap();
+    assert_eq!(read, items[..1]);
+
+    store
+        .drop_table::<RenamedOptions, _>(RenamedOptionsDescriptor)
+        .await
+        .unwrap();
+}
+
+#[derive(Clone, Debug, PartialEq, StoreData)]
+#[datastore(name = "round_trip_delete")]
+struct Entry {
+    id: PrimaryKey<u64>,
+    name: String,
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_insert_get_delete() {
+    let store = connect().await;
+
+    store.drop_table::<Entry, _>(EntryDescriptor).await.unwrap();
+    store.create::<Entry, _>(EntryDescriptor).await.unwrap();
+
+    for (id, name) in [(1, "a"), (2, "b"), (u64::MAX, "c")] {
+        let entry = Entry {
+            id: PrimaryKey(id),
+            name: name.to_owned(),
+        };
+        store.insert(EntryDescriptor, entry).await.unwrap();
+    }
+
+    let entry: Option<Entry> = store
+        .get_one(
+            EntryDescriptor,
+            EntryQuery::default().id(PrimaryKey(u64::MAX)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        entry,
+        Some(Entry {
+            id: PrimaryKey(u64::MAX),
+            name: String::from("c"),
+        })
+    );
+
+    store
+        .delete(
+            EntryDescriptor,
+            EntryQuery::default().name(String::from("b")),
+        )
+        .await
+        .unwrap();
+
+    let mut entries: Vec<Entry> = store.get_all(EntryDescriptor).await.unwrap();
+    entries.sort_by_key(|entry| entry.id.0);
+    assert_eq!(
+        entries.iter().map(|entry| entry.id.0).collect::<Vec<_>>(),
+        [1, u64::MAX]
+    );
+
+    store.drop_table::<Entry, _>(EntryDescriptor).await.unwrap();
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_types {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "round_trip_chrono")]
+    struct Chrono {
+        id: PrimaryKey<i32>,
+        a: chrono::NaiveDateTime,
+        b: chrono::DateTime<chrono::Utc>,
+    }
+
+    /// Requires a MySQL server, see [`common`].
+    #[tokio::test]
+    #[ignore]
+    async fn test_chrono() {
+        let store = connect().await;
+
+        store
+            .drop_table::<Chrono, _>(ChronoDescriptor)
+            .await
+            .unwrap();
+        store.create::<Chrono, _>(ChronoDescriptor).await.unwrap();
+
+        let datetime = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_micro_opt(23, 59, 59, 123_456)
+            .unwrap();
+        let item = Chrono {
+            id: PrimaryKey(1),
+            a: datetime,
+            b: chrono::DateTime::from_naive_utc_and_offset(datetime, chrono::Utc),
+        };
+        store.insert(ChronoDescriptor, item.clone()).await.unwrap();
+
+        let read: Vec<Chrono> = store.get_all(ChronoDescriptor).await.unwrap();
+        assert_eq!(read, [item]);
+
+        store
+            .drop_table::<Chrono, _>(ChronoDescriptor)
+            .await
+            .unwrap();
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_types {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "round_trip_time")]
+    struct Time {
+        id: PrimaryKey<i32>,
+        a: time::OffsetDateTime,
+        b: time::Date,
+        c: time::Time,
+    }
+
+    /// Requires a MySQL server, see [`common`].
+    #[tokio::test]
+    #[ignore]
+    async fn test_time() {
+        let store = connect().await;
+
+        store.drop_table::<Time, _>(TimeDescriptor).await.unwrap();
+        store.create::<Time, _>(TimeDescriptor).await.unwrap();
+
+        let date = time::Date::from_calendar_date(2024, time::Month::February, 29).unwrap();
+        let time = time::Time::from_hms_micro(23, 59, 59, 123_456).unwrap();
+        let item = Time {
+            id: PrimaryKey(1),
+            a: date.with_time(time).assume_utc(),
+            b: date,
+            c: time,
+        };
+        store.insert(TimeDescriptor, item.clone()).await.unwrap();
+
+        let read: Vec<Time> = store.get_all(TimeDescriptor).await.unwrap();
+        assert_eq!(read, [item]);
+
+        store.drop_table::<Time, _>(TimeDescriptor).await.unwrap();
+    }
+}
+
+#[cfg(feature = "decimal")]
+mod decimal_types {
+    use super::*;
+
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "round_trip_decimal")]
+    struct Decimals {
+        id: PrimaryKey<i32>,
+        a: Decimal,
+    }
+
+    /// Requires a MySQL server, see [`common`].
+    #[tokio::test]
+    #[ignore]
+    async fn test_decimal() {
+        let store = connect().await;
+
+        store
+            .drop_table::<Decimals, _>(DecimalsDescriptor)
+            .await
+            .unwrap();
+        store
+            .create::<Decimals, _>(DecimalsDescriptor)
+            .await
+            .unwrap();
+
+        let item = Decimals {
+            id: PrimaryKey(1),
+            a: Decimal::from_str("-12345.678901234567890123456789").unwrap(),
+        };
+        store
+            .insert(DecimalsDescriptor, item.clone())
+            .await
+            .unwrap();
+
+        let read: Vec<Decimals> = store.get_all(DecimalsDescriptor).await.unwrap();
+        assert_eq!(read, [item]);
+
+        store
+            .drop_table::<Decimals, _>(DecimalsDescriptor)
+            .await
+            .unwrap();
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod bytes_types {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "round_trip_bytes")]
+    struct Payloads {
+        id: PrimaryKey<i32>,
+        a: bytes::Bytes,
+        b: Option<bytes::Bytes>,
+    }
+
+    /// Requires a MySQL server, see [`common`].
+    #[tokio::test]
+    #[ignore]
+    async fn test_bytes() {
+        let store = connect().await;
+
+        store
+            .drop_table::<Payloads, _>(PayloadsDescriptor)
+            .await
+            .unwrap();
+        store
+            .create::<Payloads, _>(PayloadsDescriptor)
+            .await
+            .unwrap();
+
+        let items = vec![
+            Payloads {
+                id: PrimaryKey(1),
+                a: bytes::Bytes::new(),
+                b: None,
+            },
+            Payloads {
+                id: PrimaryKey(2),
+                a: bytes::Bytes::from_static(&[0, b'\'', b'\\', 255]),
+                b: Some(bytes::Bytes::from(vec![1; 1024])),
+            },
+        ];
+        for item in &items {
+            store
+                .insert(PayloadsDescriptor, item.clone())
+                .await
+                .unwrap();
+        }
+
+        let mut read: Vec<Payloads> = store.get_all(PayloadsDescriptor).await.unwrap();
+        read.sort_by_key(|item| item.id.0);
+        assert_eq!(read, items);
+
+        store
+            .drop_table::<Payloads, _>(PayloadsDescriptor)
+            .await
+            .unwrap();
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid_types {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "round_trip_uuid")]
+    struct Uuids {
+        id: PrimaryKey<uuid::Uuid>,
+        a: Option<uuid::Uuid>,
+    }
+
+    /// Requires a MySQL server, see [`common`].
+    #[tokio::test]
+    #[ignore]
+    async fn test_uuid() {
+        let store = connect().await;
+
+        store.drop_table::<Uuids, _>(UuidsDescriptor).await.unwrap();
+        store.create::<Uuids, _>(UuidsDescriptor).await.unwrap();
+
+        let item = Uuids {
+            id: PrimaryKey(uuid::Uuid::from_u128(
+                0x0123_4567_89ab_cdef_0123_4567_89ab_cdef,
+            )),
+            a: Some(uuid::Uuid::from_u128(u128::MAX)),
+        };
+        store.insert(UuidsDescriptor, item.clone()).await.unwrap();
+
+        let read: Vec<Uuids> = store.get_all(UuidsDescriptor).await.unwrap();
+        assert_eq!(read, [item]);
+
+        store.drop_table::<Uuids, _>(UuidsDescriptor).await.unwrap();
+    }
+}
+
+#[cfg(feature = "json")]
+mod json_types {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use datastore_mysql::Json;
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "round_trip_json")]
+    struct Documents {
+        id: PrimaryKey<i32>,
+        a: Json<Vec<String>>,
+        b: serde_json::Value,
+        c: HashMap<String, i64>,
+    }
+
+    /// Requires a MySQL server, see [`common`].
+    #[tokio::test]
+    #[ignore]
+    async fn test_json() {
+        let store = connect().await;
+
+        store
+            .drop_table::<Documents, _>(DocumentsDescriptor)
+            .await
+            .unwrap();
+        store
+            .create::<Documents, _>(DocumentsDescriptor)
+            .await
+            .unwrap();
+
+        let item = Documents {
+            id: PrimaryKey(1),
+            a: Json(vec![String::from("it's"), String::from("\"quoted\"")]),
+            b: serde_json::json!({ "nested": { "list": [1, 2.5, null, true] } }),
+            c: HashMap::from([(String::from("a"), 1), (String::from("b"), -2)]),
+        };
+        store
+            .insert(DocumentsDescriptor, item.clone())
+            .await
+            .unwrap();
+
+        let read: Vec<Documents> = store.get_all(DocumentsDescriptor).await.unwrap();
+        assert_eq!(read, [item]);
+
+        store
+            .drop_table::<Documents, _>(DocumentsDescriptor)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+//! Sessions of `MySqlStore::with_connection` against a real MySQL server.
+//!
+//! All tests are ignored by default. They require the MySQL server at `DATABASE_URL` or a
+//! docker daemon to start a container, see the [`common`] module.
+
+mod common;
+
+use datastore_mysql::{Error, LockMode, MySqlStoreConfig};
+
+use common::{connect, connect_with};
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_with_connection() {
+    let store = connect().await;
+
+    // Temporary tables and user variables only exist on the connection that created them.
+    let (rows, value) = store
+        .with_connection(|session| {
+            Box::pin(async move {
+                session
+                    .execute_raw("CREATE TEMPORARY TABLE test_session (id INT)", &[])
+                    .await?;
+                session
+                    .execute_raw("INSERT INTO test_session VALUES (1),(2)", &[])
+                    .await?;
+                session.execute_raw("SET @session_value = 42", &[]).await?;
+
+                let rows: i64 = session
+                    .query_raw_scalar("SELECT COUNT(*) FROM test_session", &[])
+                    .await?;
+                let value: i64 = session
+                    .query_raw_scalar("SELECT @session_value", &[])
+                    .await?;
+                Ok((rows, value))
+            })
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(rows, 2);
+    assert_eq!(value, 42);
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_with_connection_lock_tables() {
+    let config = MySqlStoreConfig {
+        max_connections: 1,
+        ..Default::default()
+    };
+    let store = connect_with(config).await;
+
+    store
+        .execute_raw("CREATE TABLE IF NOT EXISTS test_lock_tables (id INT)", &[])
+        .await
+        .unwrap();
+    store
+        .execute_raw(
+            "CREATE TABLE IF NOT EXISTS test_lock_tables_other (id INT)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    // The locks are released when the closure returns without unlocking.
+    store
+        .with_connection(|session| {
+            Box::pin(async move {
+                assert!(matches!(
+                    session.lock_tables(&[]).await,
+                    Err(Error::EmptyLockTables)
+                ));
+
+                session
+                    .lock_tables(&[("test_lock_tables", LockMode::Write)])
+                    .await?;
+                session
+                    .execute_raw("INSERT INTO test_lock_tables VALUES (1)", &[])
+                    .await?;
+
+                // ER_TABLE_NOT_LOCKED: tables without a lock can't be accessed.
+                let res: Result<i64, Error> = session
+                    .query_raw_scalar("SELECT COUNT(*) FROM test_lock_tables_other", &[])
+                    .await;
+                assert!(res.is_err());
+
+                Ok(())
+            })
+        })
+        .await
+        .unwrap();
+
+    // The pool only has a single connection, which held the locks. Other tables can
+    // only be accessed after the locks are released.
+    let count: i64 = store
+        .query_raw_scalar("SELECT COUNT(*) FROM test_lock_tables_other", &[])
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    store
+        .execute_raw("DROP TABLE test_lock_tables, test_lock_tables_other", &[])
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_import() {
+    let config = MySqlStoreConfig {
+        max_connections: 1,
+        ..Default::default()
+    };
+    let store = connect_with(config).await;
+
+    let checks: (i64, i64) = store
+        .import(|session| {
+            Box::pin(async move {
+                let foreign_key: i64 = session
+                    .query_raw_scalar("SELECT @@FOREIGN_KEY_CHECKS", &[])
+                    .await?;
+                let unique: i64 = session
+                    .query_raw_scalar("SELECT @@UNIQUE_CHECKS", &[])
+                    .await?;
+                Ok((foreign_key, unique))
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(checks, (0, 0));
+
+    // The checks are enabled again if the import fails.
+    let res: Result<(), Error> = store
+        .import(|_| Box::pin(async { Err(Error::Custom(String::from("failed"))) }))
+        .await;
+    assert!(matches!(res, Err(Error::Custom(_))));
+
+    // The pool only has a single connection, which was used by the import.
+    let checks: (i64, i64) = store
+        .with_connection(|session| {
+            Box::pin(async move {
+                let foreign_key: i64 = session
+                    .query_raw_scalar("SELECT @@FOREIGN_KEY_CHECKS", &[])
+                    .await?;
+                let unique: i64 = session
+                    .query_raw_scalar("SELECT @@UNIQUE_CHECKS", &[])
+                    .await?;
+                Ok((foreign_key, unique))
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(checks, (1, 1));
+}
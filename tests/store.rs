@@ -0,0 +1,2060 @@
+//! Operations of the store against a real MySQL server.
+//!
+//! All tests are ignored by default. They require the MySQL server at `DATABASE_URL` or a
+//! docker daemon to start a container, see the [`common`] module.
+
+mod common;
+
+use std::time::Duration;
+
+use datastore::{Store, StoreData};
+use datastore_mysql::{
+    AfterConnect, AutoIncrement, Bits, Bounded, Children, ColumnInfo, ColumnName, Defaulted, Enum,
+    Error, Filter, ForeignKey, MySqlStore, MySqlStoreConfig, OrDefault, Order, OrderBy, Point,
+    PrimaryKey, ReferentialAction, Renamed, Set, SqlEnum, SqlSet, UpdateExpr, Value,
+};
+
+use common::{connect, connect_with};
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_integer_bounds_round_trip() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_integer_bounds")]
+    struct Bounds {
+        a: u64,
+        b: i64,
+        c: u128,
+        d: i128,
+    }
+
+    let store = connect().await;
+
+    store
+        .drop_table::<Bounds, _>(BoundsDescriptor)
+        .await
+        .unwrap();
+    store.create::<Bounds, _>(BoundsDescriptor).await.unwrap();
+
+    let rows = vec![
+        Bounds {
+            a: u64::MAX,
+            b: i64::MIN,
+            c: u128::MAX,
+            d: i128::MIN,
+        },
+        Bounds {
+            a: i64::MAX as u64 + 1,
+            b: i64::MAX,
+            c: 0,
+            d: i128::MAX,
+        },
+    ];
+
+    for row in &rows {
+        store.insert(BoundsDescriptor, row.clone()).await.unwrap();
+    }
+
+    let mut read = store.get_all::<Bounds, _>(BoundsDescriptor).await.unwrap();
+    read.sort_by_key(|row| row.b);
+    assert_eq!(read, rows);
+
+    store
+        .drop_table::<Bounds, _>(BoundsDescriptor)
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_query_raw_scalar() {
+    let store = connect().await;
+
+    let sum: i64 = store
+        .query_raw_scalar("SELECT ? + 2", &[Value::from(1)])
+        .await
+        .unwrap();
+    assert_eq!(sum, 3);
+
+    let max: Option<i64> = store
+        .query_raw_scalar("SELECT MAX(a) FROM (SELECT 1 AS a) t WHERE a > 1", &[])
+        .await
+        .unwrap();
+    assert_eq!(max, None);
+
+    let res: Result<i64, Error> = store
+        .query_raw_scalar("SELECT 1 FROM DUAL WHERE FALSE", &[])
+        .await;
+    assert!(res.is_err());
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_u64_round_trip() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_u64")]
+    struct Item {
+        id: PrimaryKey<u64>,
+        value: i32,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    for id in [u64::MAX, i64::MAX as u64 + 1, 1] {
+        let item = Item {
+            id: PrimaryKey(id),
+            value: 0,
+        };
+        store.insert(ItemDescriptor, item).await.unwrap();
+    }
+
+    let item: Item = store
+        .get_one(ItemDescriptor, Filter::new().eq("id", &u64::MAX))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.id, PrimaryKey(u64::MAX));
+
+    let ids: Vec<(u64,)> = store
+        .query_raw_as(
+            "SELECT id FROM test_u64 WHERE id > ? ORDER BY id",
+            &[Value::from(i64::MAX as u64)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids, [(i64::MAX as u64 + 1,), (u64::MAX,)]);
+
+    // Signed expressions are read if they are not negative.
+    let ids: Vec<(u64,)> = store
+        .query_raw_as("SELECT CAST(id AS SIGNED) FROM test_u64 WHERE id = 1", &[])
+        .await
+        .unwrap();
+    assert_eq!(ids, [(1,)]);
+
+    let res: Result<Vec<(u64,)>, Error> = store.query_raw_as("SELECT -1", &[]).await;
+    assert!(matches!(
+        res,
+        Err(Error::Decode {
+            rust_type: "u64",
+            ..
+        })
+    ));
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_bool_round_trip() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_bool")]
+    struct Flag {
+        id: i32,
+        active: bool,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Flag, _>(FlagDescriptor).await.unwrap();
+    store.create::<Flag, _>(FlagDescriptor).await.unwrap();
+
+    for (id, active) in [(1, true), (2, false)] {
+        store
+            .insert(FlagDescriptor, Flag { id, active })
+            .await
+            .unwrap();
+    }
+
+    // The `TRUE` literal of a condition matches the stored `1`.
+    let read: Vec<Flag> = store
+        .get(FlagDescriptor, FlagQuery::default().active(true))
+        .await
+        .unwrap();
+    assert_eq!(
+        read,
+        vec![Flag {
+            id: 1,
+            active: true
+        }]
+    );
+
+    // Values other than `0` and `1` are read as `true`.
+    store
+        .execute_raw(
+            "INSERT INTO test_bool (id,active) VALUES (3,?)",
+            &[Value::Int(2)],
+        )
+        .await
+        .unwrap();
+
+    let mut read = store.get_all::<Flag, _>(FlagDescriptor).await.unwrap();
+    read.sort_by_key(|row| row.id);
+    assert_eq!(
+        read,
+        vec![
+            Flag {
+                id: 1,
+                active: true
+            },
+            Flag {
+                id: 2,
+                active: false
+            },
+            Flag {
+                id: 3,
+                active: true
+            },
+        ]
+    );
+
+    // `TRUE` is `1`, other non-zero values are not matched by the condition.
+    let read: Vec<Flag> = store
+        .get(FlagDescriptor, FlagQuery::default().active(true))
+        .await
+        .unwrap();
+    assert_eq!(read.len(), 1);
+
+    let read: Vec<Flag> = store
+        .get(FlagDescriptor, FlagQuery::default().active(false))
+        .await
+        .unwrap();
+    assert_eq!(
+        read,
+        vec![Flag {
+            id: 2,
+            active: false
+        }]
+    );
+
+    store.drop_table::<Flag, _>(FlagDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_max_rows() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_max_rows")]
+    struct Item {
+        id: i32,
+    }
+
+    let config = MySqlStoreConfig {
+        max_rows: Some(2),
+        ..Default::default()
+    };
+    let store = connect_with(config).await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    for id in 0..2 {
+        store.insert(ItemDescriptor, Item { id }).await.unwrap();
+    }
+
+    let rows = store.get_all::<Item, _>(ItemDescriptor).await.unwrap();
+    assert_eq!(rows.len(), 2);
+
+    store.insert(ItemDescriptor, Item { id: 2 }).await.unwrap();
+
+    let res = store.get_all::<Item, _>(ItemDescriptor).await;
+    assert!(matches!(res, Err(Error::TooManyRows)));
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[cfg(feature = "json")]
+#[tokio::test]
+#[ignore]
+async fn test_json_map_round_trip() {
+    use std::collections::HashMap;
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_json_map")]
+    struct Item {
+        id: i32,
+        attributes: HashMap<String, serde_json::Value>,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let mut attributes = HashMap::new();
+    attributes.insert(String::from("color"), serde_json::json!("red"));
+    attributes.insert(
+        String::from("size"),
+        serde_json::json!({ "w": 1, "h": [2, 3], "unit": null }),
+    );
+
+    let item = Item { id: 1, attributes };
+    store.insert(ItemDescriptor, item.clone()).await.unwrap();
+
+    // A `NULL` column is read as an empty map.
+    store
+        .execute_raw("ALTER TABLE test_json_map MODIFY attributes JSON NULL", &[])
+        .await
+        .unwrap();
+    store
+        .execute_raw(
+            "INSERT INTO test_json_map (id,attributes) VALUES (2,NULL)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let mut read = store.get_all::<Item, _>(ItemDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id);
+    assert_eq!(
+        read,
+        vec![
+            item,
+            Item {
+                id: 2,
+                attributes: HashMap::new(),
+            },
+        ]
+    );
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[cfg(feature = "json")]
+#[tokio::test]
+#[ignore]
+async fn test_json_value_round_trip() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_json_value")]
+    struct Item {
+        id: i32,
+        payload: serde_json::Value,
+        extra: Option<serde_json::Value>,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    // MySQL allows documents nested up to a depth of 100.
+    let mut nested = serde_json::json!("it's \"deep\"");
+    for depth in 0..90 {
+        nested = if depth % 2 == 0 {
+            serde_json::json!([nested])
+        } else {
+            serde_json::json!({ "a": nested })
+        };
+    }
+
+    let items = vec![
+        Item {
+            id: 1,
+            payload: nested,
+            extra: None,
+        },
+        Item {
+            id: 2,
+            payload: serde_json::Value::Null,
+            extra: Some(serde_json::Value::Null),
+        },
+    ];
+    for item in items.clone() {
+        store.insert(ItemDescriptor, item).await.unwrap();
+    }
+
+    let mut read = store.get_all::<Item, _>(ItemDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id);
+    assert_eq!(read, items);
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_count_distinct() {
+    #[derive(Clone, Debug, StoreData)]
+    #[datastore(name = "test_count_distinct")]
+    struct Visit {
+        id: i32,
+        user: Option<i32>,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Visit, _>(VisitDescriptor).await.unwrap();
+    store.create::<Visit, _>(VisitDescriptor).await.unwrap();
+
+    assert_eq!(
+        store
+            .count_distinct(VisitDescriptor, "user", Filter::new())
+            .await
+            .unwrap(),
+        0
+    );
+
+    let visits = [(1, Some(1)), (2, Some(1)), (3, Some(2)), (4, None)];
+    for (id, user) in visits {
+        store
+            .insert(VisitDescriptor, Visit { id, user })
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(
+        store
+            .count_distinct(VisitDescriptor, "user", Filter::new())
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        store
+            .count_distinct(VisitDescriptor, "user", Filter::new().gt("id", &1))
+            .await
+            .unwrap(),
+        2
+    );
+
+    store.drop_table::<Visit, _>(VisitDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_delete_by_ids() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_delete_by_ids")]
+    struct Item {
+        id: PrimaryKey<i32>,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let items: Vec<Item> = (0..2500).map(|id| Item { id: PrimaryKey(id) }).collect();
+    store.insert_batched(ItemDescriptor, items).await.unwrap();
+
+    let ids: Vec<i32> = (0..2500).filter(|id| id % 2 == 0).chain([5000]).collect();
+    assert_eq!(
+        store
+            .delete_by_ids(ItemDescriptor, "id", &ids)
+            .await
+            .unwrap(),
+        1250
+    );
+    assert_eq!(
+        store
+            .delete_by_ids::<Item, _, i32>(ItemDescriptor, "id", &[])
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(store.count_all(ItemDescriptor).await.unwrap(), 1250);
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_delete_one() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_delete_one")]
+    struct Item {
+        id: PrimaryKey<i32>,
+        group: i32,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let items = (1..=3).map(|id| Item {
+        id: PrimaryKey(id),
+        group: id % 2,
+    });
+    store
+        .insert_many(ItemDescriptor, items.collect())
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        store
+            .delete_one(ItemDescriptor, Filter::new().eq("group", &1))
+            .await,
+        Err(Error::TooManyRows)
+    ));
+    assert!(matches!(
+        store
+            .delete_one(ItemDescriptor, Filter::new().eq("id", &4))
+            .await,
+        Err(Error::NotFound)
+    ));
+    assert!(matches!(
+        store.delete_one(ItemDescriptor, Filter::new()).await,
+        Err(Error::EmptyDeleteConditions)
+    ));
+    assert_eq!(store.count_all(ItemDescriptor).await.unwrap(), 3);
+
+    store
+        .delete_one(ItemDescriptor, Filter::new().eq("group", &0))
+        .await
+        .unwrap();
+    assert_eq!(store.count_all(ItemDescriptor).await.unwrap(), 2);
+
+    let mut tx = store.begin().await.unwrap();
+    assert!(matches!(
+        tx.delete_one(ItemDescriptor, Filter::new().eq("group", &1))
+            .await,
+        Err(Error::TooManyRows)
+    ));
+    tx.delete_one(ItemDescriptor, Filter::new().eq("id", &1))
+        .await
+        .unwrap();
+    assert_eq!(tx.get_all(ItemDescriptor).await.unwrap().len(), 1);
+    tx.commit().await.unwrap();
+    assert_eq!(store.count_all(ItemDescriptor).await.unwrap(), 1);
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_get_exactly_one() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_exactly_one")]
+    struct Item {
+        id: i32,
+        name: String,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    for (id, name) in [(1, "a"), (2, "b"), (3, "b")] {
+        let item = Item {
+            id,
+            name: String::from(name),
+        };
+        store.insert(ItemDescriptor, item).await.unwrap();
+    }
+
+    let item: Item = store
+        .get_exactly_one(ItemDescriptor, Filter::new().eq("name", "a"))
+        .await
+        .unwrap();
+    assert_eq!(item.id, 1);
+
+    let res = store
+        .get_exactly_one::<Item, _, _>(ItemDescriptor, Filter::new().eq("name", "b"))
+        .await;
+    assert!(matches!(res, Err(Error::TooManyRows)));
+
+    let res = store
+        .get_exactly_one::<Item, _, _>(ItemDescriptor, Filter::new().eq("name", "c"))
+        .await;
+    assert!(matches!(res, Err(Error::NotFound)));
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_get_str_with() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_str_with")]
+    struct Note {
+        id: i32,
+        text: String,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Note, _>(NoteDescriptor).await.unwrap();
+    store.create::<Note, _>(NoteDescriptor).await.unwrap();
+
+    for (id, text) in [(1, "hello world"), (2, "héllo")] {
+        let note = Note {
+            id,
+            text: text.to_owned(),
+        };
+        store.insert(NoteDescriptor, note).await.unwrap();
+    }
+
+    let mut words = 0;
+    let mut texts = Vec::new();
+    store
+        .get_str_with::<Note, _, _, _>(NoteDescriptor, Filter::new(), "text", |text| {
+            words += text.split_whitespace().count();
+            texts.push(text.to_owned());
+        })
+        .await
+        .unwrap();
+    texts.sort();
+    assert_eq!(words, 3);
+    assert_eq!(texts, ["hello world", "héllo"]);
+
+    let res = store
+        .get_str_with::<Note, _, _, _>(NoteDescriptor, Filter::new(), "id", |_| {})
+        .await;
+    assert!(matches!(res, Err(Error::Decode { column, rust_type: "&str", .. }) if column == "id"));
+
+    store.drop_table::<Note, _>(NoteDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_children_roundtrip() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_children")]
+    struct Post {
+        id: PrimaryKey<i64>,
+        tags: Children<String>,
+    }
+
+    /// A row of the child table of `Post`.
+    #[derive(Debug, StoreData)]
+    #[datastore(name = "test_children_tags")]
+    struct Tag {
+        test_children_id: i64,
+        position: u32,
+        value: String,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Tag, _>(TagDescriptor).await.unwrap();
+    store.drop_table::<Post, _>(PostDescriptor).await.unwrap();
+    store.create::<Post, _>(PostDescriptor).await.unwrap();
+
+    let posts = vec![
+        Post {
+            id: PrimaryKey(1),
+            tags: Children(vec![String::from("b"), String::from("a")]),
+        },
+        Post {
+            id: PrimaryKey(2),
+            tags: Children::default(),
+        },
+    ];
+    for post in posts.clone() {
+        store.insert(PostDescriptor, post).await.unwrap();
+    }
+
+    let mut read = store.get_all::<Post, _>(PostDescriptor).await.unwrap();
+    read.sort_by_key(|post| post.id.0);
+    assert_eq!(read, posts);
+
+    let read = store
+        .get_one::<Post, _, _>(PostDescriptor, Filter::new().eq("id", &1_i64))
+        .await
+        .unwrap();
+    assert_eq!(read.as_ref(), Some(&posts[0]));
+
+    let read = store
+        .get_top::<Post, _, _>(PostDescriptor, Filter::new(), "id", Order::Asc, 1)
+        .await
+        .unwrap();
+    assert_eq!(read, posts[..1]);
+
+    let read = store
+        .get_ordered::<Post, _, _>(PostDescriptor, Filter::new(), &[OrderBy::asc("id")], None)
+        .await
+        .unwrap();
+    assert_eq!(read, posts);
+
+    let read = store
+        .get_by_ids::<Post, _, _>(PostDescriptor, "id", &[1_i64])
+        .await
+        .unwrap();
+    assert_eq!(read, posts[..1]);
+
+    let read = store
+        .get_exactly_one::<Post, _, _>(PostDescriptor, Filter::new().eq("id", &1_i64))
+        .await
+        .unwrap();
+    assert_eq!(read, posts[0]);
+    assert!(matches!(
+        store
+            .get_exactly_one::<Post, _, _>(PostDescriptor, Filter::new())
+            .await,
+        Err(Error::TooManyRows)
+    ));
+
+    let read = store
+        .get_page::<Post, _>(PostDescriptor, Some(1), Some(1))
+        .await
+        .unwrap();
+    assert_eq!(read.len(), 1);
+    assert!(posts.contains(&read[0]));
+
+    let read = store
+        .get_after::<Post, _, _>(PostDescriptor, "id", Some(&1_i64), 10)
+        .await
+        .unwrap();
+    assert_eq!(read, posts[1..]);
+
+    // Raw queries don't load the child rows.
+    let err = store
+        .query_raw::<Post>("SELECT id FROM test_children", &[])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::UnsupportedOperation {
+            operation: "Children field",
+            kind: "RAW"
+        }
+    ));
+
+    // Replacing the parent replaces the child rows.
+    let replaced = Post {
+        id: PrimaryKey(1),
+        tags: Children(vec![String::from("c")]),
+    };
+    store
+        .replace(PostDescriptor, replaced.clone())
+        .await
+        .unwrap();
+
+    // A skipped parent doesn't insert child rows.
+    let ignored = Post {
+        id: PrimaryKey(1),
+        tags: Children(vec![String::from("d")]),
+    };
+    assert_eq!(
+        store.insert_ignore(PostDescriptor, ignored).await.unwrap(),
+        0
+    );
+    let inserted = Post {
+        id: PrimaryKey(3),
+        tags: Children(vec![String::from("e")]),
+    };
+    assert_eq!(
+        store
+            .insert_ignore(PostDescriptor, inserted.clone())
+            .await
+            .unwrap(),
+        1
+    );
+
+    let mut read = store.get_all::<Post, _>(PostDescriptor).await.unwrap();
+    read.sort_by_key(|post| post.id.0);
+    assert_eq!(read, [replaced.clone(), posts[1].clone(), inserted.clone()]);
+
+    // Transactions handle the child rows like the store.
+    let mut tx = store.begin().await.unwrap();
+    let replaced = Post {
+        id: PrimaryKey(3),
+        tags: Children(vec![String::from("f"), String::from("g")]),
+    };
+    tx.replace(PostDescriptor, replaced.clone()).await.unwrap();
+    assert_eq!(tx.insert_ignore(PostDescriptor, inserted).await.unwrap(), 0);
+    let read = tx
+        .get_top::<Post, _, _>(PostDescriptor, Filter::new(), "id", Order::Desc, 1)
+        .await
+        .unwrap();
+    assert_eq!(read, std::slice::from_ref(&replaced));
+    let read = tx
+        .get_ordered::<Post, _, _>(
+            PostDescriptor,
+            Filter::new(),
+            &[OrderBy::desc("id")],
+            Some(1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(read, std::slice::from_ref(&replaced));
+    let read = tx
+        .get_by_ids::<Post, _, _>(PostDescriptor, "id", &[3_i64])
+        .await
+        .unwrap();
+    assert_eq!(read, std::slice::from_ref(&replaced));
+    let read = tx
+        .get_exactly_one::<Post, _, _>(PostDescriptor, Filter::new().eq("id", &3_i64))
+        .await
+        .unwrap();
+    assert_eq!(read, replaced);
+    let read = tx
+        .get_after::<Post, _, _>(PostDescriptor, "id", Some(&2_i64), 10)
+        .await
+        .unwrap();
+    assert_eq!(read, std::slice::from_ref(&replaced));
+    tx.commit().await.unwrap();
+
+    // Deleting the parent deletes the child rows.
+    store
+        .delete::<Post, _, _>(PostDescriptor, Filter::new().eq("id", &1_i64))
+        .await
+        .unwrap();
+    let tags = store.get_all::<Tag, _>(TagDescriptor).await.unwrap();
+    let parents: Vec<i64> = tags.iter().map(|tag| tag.test_children_id).collect();
+    assert_eq!(parents, [3, 3]);
+
+    store.drop_table::<Tag, _>(TagDescriptor).await.unwrap();
+    store.drop_table::<Post, _>(PostDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_time_zone() {
+    let store = connect().await;
+    let time_zone: Vec<(String,)> = store
+        .query_raw_as("SELECT @@session.time_zone", &[])
+        .await
+        .unwrap();
+    assert_eq!(time_zone, [(String::from("+00:00"),)]);
+
+    let config = MySqlStoreConfig {
+        time_zone: Some(String::from("+02:00")),
+        ..Default::default()
+    };
+    let store = MySqlStore::connect_with(store.server().url(), config)
+        .await
+        .unwrap();
+    let time_zone: Vec<(String,)> = store
+        .query_raw_as("SELECT @@session.time_zone", &[])
+        .await
+        .unwrap();
+    assert_eq!(time_zone, [(String::from("+02:00"),)]);
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_get_dynamic() {
+    let store = connect().await;
+
+    store
+        .execute_raw("DROP TABLE IF EXISTS test_get_dynamic", &[])
+        .await
+        .unwrap();
+    store
+        .execute_raw(
+            "CREATE TABLE test_get_dynamic (id INT NOT NULL, flag BOOLEAN, count BIGINT UNSIGNED, ratio FLOAT, price DECIMAL(10,2), created DATETIME, name TEXT, data BLOB)",
+            &[],
+        )
+        .await
+        .unwrap();
+    store
+        .execute_raw(
+            "INSERT INTO test_get_dynamic VALUES (1, TRUE, 18446744073709551615, 0.5, 19.99, '2024-01-31 12:30:05', 'Robb', x'0102'),(2, NULL, NULL, NULL, NULL, NULL, NULL, NULL)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let rows = store
+        .get_dynamic("test_get_dynamic", Filter::new().eq("id", &1))
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row["id"], Value::Int(1));
+    assert_eq!(row["flag"], Value::Bool(true));
+    assert_eq!(row["count"], Value::UInt(u64::MAX));
+    assert_eq!(row["ratio"], Value::Float(0.5));
+    assert_eq!(row["price"], Value::from("19.99"));
+    assert_eq!(row["created"], Value::from("2024-01-31 12:30:05"));
+    assert_eq!(row["name"], Value::from("Robb"));
+    assert_eq!(row["data"], Value::Bytes(vec![1, 2]));
+
+    let rows = store
+        .get_dynamic("test_get_dynamic", Filter::new().eq("id", &2))
+        .await
+        .unwrap();
+    assert_eq!(rows[0].len(), 8);
+    assert!(rows[0]
+        .iter()
+        .all(|(column, value)| column == "id" || *value == Value::Null));
+
+    store
+        .execute_raw("DROP TABLE test_get_dynamic", &[])
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_strict_sql_mode() {
+    let store = connect().await;
+    let sql_mode: Vec<(String,)> = store
+        .query_raw_as("SELECT @@session.sql_mode", &[])
+        .await
+        .unwrap();
+    assert!(sql_mode[0].0.contains("STRICT_ALL_TABLES"));
+    assert!(sql_mode[0].0.contains("NO_ZERO_DATE"));
+    assert!(sql_mode[0].0.contains("PIPES_AS_CONCAT"));
+
+    let config = MySqlStoreConfig {
+        strict_sql_mode: false,
+        ..Default::default()
+    };
+    let store = MySqlStore::connect_with(store.server().url(), config)
+        .await
+        .unwrap();
+    let sql_mode: Vec<(String, String)> = store
+        .query_raw_as("SELECT @@global.sql_mode, @@session.sql_mode", &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        sql_mode[0].1.contains("STRICT_ALL_TABLES"),
+        sql_mode[0].0.contains("STRICT_ALL_TABLES")
+    );
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_after_connect() {
+    let config = MySqlStoreConfig {
+        time_zone: Some(String::from("+02:00")),
+        after_connect: Some(AfterConnect::execute(String::from(
+            "SET @after_connect = @@session.time_zone",
+        ))),
+        ..Default::default()
+    };
+    let store = connect_with(config).await;
+    let value: Vec<(String,)> = store
+        .query_raw_as("SELECT @after_connect", &[])
+        .await
+        .unwrap();
+    assert_eq!(value, [(String::from("+02:00"),)]);
+
+    let config = MySqlStoreConfig {
+        after_connect: Some(AfterConnect::new(|_| {
+            Box::pin(async { Err(Error::Custom(String::from("no session"))) })
+        })),
+        ..Default::default()
+    };
+    assert!(MySqlStore::connect_with(store.server().url(), config)
+        .await
+        .is_err());
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_set_roundtrip() {
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    struct Features(Vec<&'static str>);
+
+    impl SqlSet for Features {
+        const MEMBERS: &'static [&'static str] = &["search", "export", "it's"];
+
+        fn members(&self) -> Vec<&'static str> {
+            self.0.clone()
+        }
+
+        fn from_members(names: &[&str]) -> Option<Self> {
+            names
+                .iter()
+                .map(|name| Self::MEMBERS.iter().copied().find(|member| member == name))
+                .collect::<Option<Vec<_>>>()
+                .map(Self)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_set")]
+    struct Account {
+        id: i32,
+        features: Set<Features>,
+    }
+
+    let store = connect().await;
+
+    store
+        .drop_table::<Account, _>(AccountDescriptor)
+        .await
+        .unwrap();
+    store.create::<Account, _>(AccountDescriptor).await.unwrap();
+
+    let accounts = vec![
+        Account {
+            id: 1,
+            features: Set(Features::default()),
+        },
+        Account {
+            id: 2,
+            features: Set(Features(vec!["search", "it's"])),
+        },
+    ];
+    for account in accounts.clone() {
+        store.insert(AccountDescriptor, account).await.unwrap();
+    }
+
+    let mut read = store
+        .get_all::<Account, _>(AccountDescriptor)
+        .await
+        .unwrap();
+    read.sort_by_key(|account| account.id);
+    assert_eq!(read, accounts);
+
+    store
+        .drop_table::<Account, _>(AccountDescriptor)
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_bits_roundtrip() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_bits")]
+    struct Flags {
+        id: i32,
+        small: Bits<3>,
+        large: Bits<64>,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Flags, _>(FlagsDescriptor).await.unwrap();
+    store.create::<Flags, _>(FlagsDescriptor).await.unwrap();
+
+    let flags = vec![
+        Flags {
+            id: 1,
+            small: Bits(0),
+            large: Bits(0),
+        },
+        Flags {
+            id: 2,
+            small: Bits(0b111),
+            large: Bits(u64::MAX),
+        },
+        Flags {
+            id: 3,
+            small: Bits(0b101),
+            large: Bits(0x8000_0000_0000_0001),
+        },
+    ];
+    for flag in flags.clone() {
+        store.insert(FlagsDescriptor, flag).await.unwrap();
+    }
+
+    let mut read = store.get_all::<Flags, _>(FlagsDescriptor).await.unwrap();
+    read.sort_by_key(|flag| flag.id);
+    assert_eq!(read, flags);
+
+    store.drop_table::<Flags, _>(FlagsDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_insert_batched() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_insert_batched")]
+    struct Item {
+        id: PrimaryKey<i32>,
+    }
+
+    let config = MySqlStoreConfig {
+        insert_batch_size: 2,
+        ..Default::default()
+    };
+    let store = connect_with(config).await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let items: Vec<Item> = (1..=5).map(|id| Item { id: PrimaryKey(id) }).collect();
+    store
+        .insert_batched(ItemDescriptor, items.clone())
+        .await
+        .unwrap();
+
+    let mut read = store.get_all::<Item, _>(ItemDescriptor).await.unwrap();
+    read.sort_by_key(|item| item.id.0);
+    assert_eq!(read, items);
+
+    // The duplicate key in the last batch rolls back all batches.
+    let items: Vec<Item> = [6, 7, 8, 1]
+        .into_iter()
+        .map(|id| Item { id: PrimaryKey(id) })
+        .collect();
+    assert!(store.insert_batched(ItemDescriptor, items).await.is_err());
+
+    let read = store.get_all::<Item, _>(ItemDescriptor).await.unwrap();
+    assert_eq!(read.len(), 5);
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_get_where() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_get_where")]
+    struct Item {
+        id: PrimaryKey<i32>,
+        name: Option<String>,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let items = vec![
+        Item {
+            id: PrimaryKey(1),
+            name: Some(String::from("a")),
+        },
+        Item {
+            id: PrimaryKey(2),
+            name: Some(String::from("b")),
+        },
+        Item {
+            id: PrimaryKey(3),
+            name: None,
+        },
+    ];
+    store
+        .insert_batched(ItemDescriptor, items.clone())
+        .await
+        .unwrap();
+
+    let read: Vec<Item> = store.get_where(ItemDescriptor, "name", "b").await.unwrap();
+    assert_eq!(read, [items[1].clone()]);
+
+    let read: Vec<Item> = store
+        .get_where(ItemDescriptor, "name", &None::<String>)
+        .await
+        .unwrap();
+    assert_eq!(read, [items[2].clone()]);
+
+    let item: Option<Item> = store.get_one_where(ItemDescriptor, "id", &1).await.unwrap();
+    assert_eq!(item, Some(items[0].clone()));
+
+    let item: Option<Item> = store.get_one_where(ItemDescriptor, "id", &4).await.unwrap();
+    assert_eq!(item, None);
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_create_with_foreign_keys_cascade() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_fk_parent")]
+    struct Parent {
+        id: PrimaryKey<i32>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_fk_child")]
+    struct Child {
+        id: PrimaryKey<i32>,
+        parent_id: i32,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Child, _>(ChildDescriptor).await.unwrap();
+    store
+        .drop_table::<Parent, _>(ParentDescriptor)
+        .await
+        .unwrap();
+    store.create::<Parent, _>(ParentDescriptor).await.unwrap();
+    store
+        .create_with_foreign_keys::<Child, _>(
+            ChildDescriptor,
+            &[ForeignKey::new(&["parent_id"], "test_fk_parent", &["id"])
+                .on_delete(ReferentialAction::Cascade)],
+        )
+        .await
+        .unwrap();
+
+    for id in [1, 2] {
+        store
+            .insert(ParentDescriptor, Parent { id: PrimaryKey(id) })
+            .await
+            .unwrap();
+        let child = Child {
+            id: PrimaryKey(id),
+            parent_id: id,
+        };
+        store.insert(ChildDescriptor, child).await.unwrap();
+    }
+
+    // A child without an existing parent is rejected.
+    let child = Child {
+        id: PrimaryKey(3),
+        parent_id: 3,
+    };
+    assert!(store.insert(ChildDescriptor, child).await.is_err());
+
+    store
+        .delete(ParentDescriptor, ParentQuery::default().id(PrimaryKey(1)))
+        .await
+        .unwrap();
+
+    let children: Vec<Child> = store.get_all(ChildDescriptor).await.unwrap();
+    assert_eq!(
+        children,
+        [Child {
+            id: PrimaryKey(2),
+            parent_id: 2,
+        }]
+    );
+
+    store.drop_table::<Child, _>(ChildDescriptor).await.unwrap();
+    store
+        .drop_table::<Parent, _>(ParentDescriptor)
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_get_all_results() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_get_all_results")]
+    struct Item {
+        id: PrimaryKey<i32>,
+        name: Option<String>,
+    }
+
+    // Reads the same table, but fails to read rows where `name` is `NULL`.
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_get_all_results")]
+    struct Named {
+        id: PrimaryKey<i32>,
+        name: String,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let items = vec![
+        Item {
+            id: PrimaryKey(1),
+            name: Some(String::from("a")),
+        },
+        Item {
+            id: PrimaryKey(2),
+            name: None,
+        },
+        Item {
+            id: PrimaryKey(3),
+            name: Some(String::from("c")),
+        },
+    ];
+    store.insert_batched(ItemDescriptor, items).await.unwrap();
+
+    let res: Result<Vec<Named>, Error> = store.get_all(NamedDescriptor).await;
+    assert!(res.is_err());
+
+    let rows: Vec<Result<Named, Error>> = store.get_all_results(NamedDescriptor).await.unwrap();
+    assert_eq!(rows.len(), 3);
+
+    let mut named: Vec<Named> = rows.into_iter().filter_map(Result::ok).collect();
+    named.sort_by_key(|item| item.id.0);
+    assert_eq!(
+        named,
+        [
+            Named {
+                id: PrimaryKey(1),
+                name: String::from("a"),
+            },
+            Named {
+                id: PrimaryKey(3),
+                name: String::from("c"),
+            },
+        ]
+    );
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_query_raw_as() {
+    let store = connect().await;
+
+    let rows: Vec<(i64, String, Option<i32>)> = store
+        .query_raw_as(
+            "SELECT 1, ?, NULL UNION ALL SELECT 2, 'b', 3",
+            &[Value::from("a")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        [
+            (1, String::from("a"), None),
+            (2, String::from("b"), Some(3))
+        ]
+    );
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_query_raw_as_positional() {
+    let store = connect().await;
+
+    // The columns are read in order, reading past the last column fails.
+    let res = store
+        .query_raw_as::<(i64, Option<i64>, String, String)>(
+            "SELECT 3 AS a, NULL AS b, 'hello' AS c",
+            &[],
+        )
+        .await;
+    assert!(matches!(
+        res,
+        Err(Error::Sqlx(sqlx::Error::ColumnIndexOutOfBounds {
+            index: 3,
+            len: 3
+        }))
+    ));
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_get_bytes_with() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_bytes_with")]
+    struct File {
+        id: i32,
+        content: Vec<u8>,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<File, _>(FileDescriptor).await.unwrap();
+    store.create::<File, _>(FileDescriptor).await.unwrap();
+
+    for id in 1..=2 {
+        let file = File {
+            id,
+            content: vec![id as u8; 4],
+        };
+        store.insert(FileDescriptor, file).await.unwrap();
+    }
+
+    let mut contents = Vec::new();
+    store
+        .get_bytes_with::<File, _, _, _>(FileDescriptor, Filter::new(), "content", |bytes| {
+            contents.push(bytes.to_vec())
+        })
+        .await
+        .unwrap();
+    contents.sort();
+    assert_eq!(contents, vec![vec![1; 4], vec![2; 4]]);
+
+    store.drop_table::<File, _>(FileDescriptor).await.unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_read_decode_error() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_read_decode_error")]
+    struct Item {
+        name: i64,
+    }
+
+    let store = connect().await;
+
+    let err = store
+        .query_raw::<Item>("SELECT 'hello' AS name", &[])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        &err,
+        Error::Decode { column, rust_type: "i64", .. } if column == "name"
+    ));
+    assert!(err
+        .to_string()
+        .starts_with("failed to decode column name as i64: "));
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_read_unknown_enum_variant() {
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum Status {
+        Active,
+        Other(String),
+    }
+
+    impl SqlEnum for Status {
+        const VARIANTS: &'static [&'static str] = &["Active"];
+
+        fn as_str(&self) -> &str {
+            match self {
+                Self::Active => "Active",
+                Self::Other(name) => name,
+            }
+        }
+
+        fn from_variant(name: &str) -> Option<Self> {
+            match name {
+                "Active" => Some(Self::Active),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct OtherStatus(Status);
+
+    impl SqlEnum for OtherStatus {
+        const VARIANTS: &'static [&'static str] = Status::VARIANTS;
+
+        fn as_str(&self) -> &str {
+            self.0.as_str()
+        }
+
+        fn from_variant(name: &str) -> Option<Self> {
+            Status::from_variant(name).map(Self)
+        }
+
+        fn from_unknown(name: &str) -> Option<Self> {
+            Some(Self(Status::Other(name.to_owned())))
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_read_enum")]
+    struct Item {
+        status: Enum<Status>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_read_enum")]
+    struct OtherItem {
+        status: Enum<OtherStatus>,
+    }
+
+    let store = connect().await;
+
+    let err = store
+        .query_raw::<Item>("SELECT 'Archived' AS status", &[])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::UnknownEnumVariant { column, value } if column == "status" && value == "Archived"
+    ));
+
+    let items = store
+        .query_raw::<OtherItem>("SELECT 'Archived' AS status", &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        items[0].status.clone().into_inner(),
+        OtherStatus(Status::Other(String::from("Archived")))
+    );
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_read_or_default() {
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct A;
+
+    impl ColumnName for A {
+        const NAME: &'static str = "a";
+    }
+
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct B;
+
+    impl ColumnName for B {
+        const NAME: &'static str = "b";
+    }
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_read_or_default")]
+    struct Item {
+        a: OrDefault<i64>,
+        missing: OrDefault<i64>,
+        b: OrDefault<Option<i64>>,
+        // Wrappers check the renamed column, not the column of the field.
+        renamed: OrDefault<Renamed<i64, A>>,
+        optional: Option<Renamed<i64, A>>,
+        null: Option<Renamed<i64, B>>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_read_or_default")]
+    struct NullItem {
+        b: OrDefault<i64>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_read_or_default")]
+    struct MissingItem {
+        missing: i64,
+    }
+
+    let store = connect().await;
+    let sql = "SELECT 3 AS a, NULL AS b";
+
+    let items = store.query_raw::<Item>(sql, &[]).await.unwrap();
+    assert_eq!(
+        items,
+        [Item {
+            a: OrDefault(3),
+            missing: OrDefault(0),
+            b: OrDefault(None),
+            renamed: OrDefault(Renamed::new(3)),
+            optional: Some(Renamed::new(3)),
+            null: None,
+        }]
+    );
+
+    // A `NULL` column is not missing.
+    assert!(store.query_raw::<NullItem>(sql, &[]).await.is_err());
+    assert!(store.query_raw::<MissingItem>(sql, &[]).await.is_err());
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_query_timeout() {
+    let config = MySqlStoreConfig {
+        query_timeout: Some(Duration::from_millis(100)),
+        ..Default::default()
+    };
+    let store = connect_with(config).await;
+
+    let res = store.execute_raw("SELECT SLEEP(5)", &[]).await;
+    assert!(matches!(res, Err(Error::Timeout)));
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_query_raw_alias() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_query_raw_alias")]
+    struct StatusCount {
+        total: i64,
+        status: String,
+    }
+
+    let store = connect().await;
+
+    let sql = "SELECT COUNT(*) AS total, status FROM \
+        (SELECT 'open' AS status UNION ALL SELECT 'open' UNION ALL SELECT 'closed') t \
+        GROUP BY status ORDER BY status";
+    let counts: Vec<StatusCount> = store.query_raw(sql, &[]).await.unwrap();
+    assert_eq!(
+        counts,
+        [
+            StatusCount {
+                total: 1,
+                status: String::from("closed"),
+            },
+            StatusCount {
+                total: 2,
+                status: String::from("open"),
+            },
+        ]
+    );
+
+    // The alias `total` collides with the selected column `total`.
+    let sql = "SELECT COUNT(*) AS total, status, total FROM \
+        (SELECT 'open' AS status, 5 AS total) t GROUP BY status, total";
+    let err = store.query_raw::<StatusCount>(sql, &[]).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::AmbiguousColumn { name } if name == "total"),
+        "{:?}",
+        err
+    );
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_update_exprs_increment() {
+    #[derive(Clone, Debug, StoreData)]
+    #[datastore(name = "test_update_exprs_increment")]
+    struct Counter {
+        id: PrimaryKey<i32>,
+        count: i64,
+    }
+
+    let store = connect().await;
+
+    store
+        .drop_table::<Counter, _>(CounterDescriptor)
+        .await
+        .unwrap();
+    store.create::<Counter, _>(CounterDescriptor).await.unwrap();
+    store
+        .insert(
+            CounterDescriptor,
+            Counter {
+                id: PrimaryKey(1),
+                count: 5,
+            },
+        )
+        .await
+        .unwrap();
+
+    let exprs = [("count", UpdateExpr::Increment(Value::from(1)))];
+    for _ in 0..3 {
+        store
+            .update_exprs(CounterDescriptor, &exprs, Filter::new().eq("id", &1))
+            .await
+            .unwrap();
+    }
+    store
+        .update_exprs(
+            CounterDescriptor,
+            &[("count", UpdateExpr::Least(Value::from(7)))],
+            Filter::new().eq("id", &1),
+        )
+        .await
+        .unwrap();
+
+    let counter: Counter = store
+        .get_one(CounterDescriptor, Filter::new().eq("id", &1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(counter.count, 7);
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_defaulted_roundtrip() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_defaulted")]
+    struct Account {
+        id: PrimaryKey<i32>,
+        credits: Defaulted<i64>,
+        note: Option<Defaulted<String>>,
+    }
+
+    let store = connect().await;
+
+    store
+        .drop_table::<Account, _>(AccountDescriptor)
+        .await
+        .unwrap();
+    store
+        .execute_raw(
+            "CREATE TABLE test_defaulted (id INT NOT NULL,credits BIGINT NOT NULL DEFAULT 100,note TEXT DEFAULT ('none'),PRIMARY KEY (id))",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let accounts = [
+        Account {
+            id: PrimaryKey(1),
+            credits: Defaulted::default(),
+            note: None,
+        },
+        Account {
+            id: PrimaryKey(2),
+            credits: Defaulted::new(5),
+            note: Some(Defaulted::default()),
+        },
+    ];
+    for account in accounts {
+        store.insert(AccountDescriptor, account).await.unwrap();
+    }
+
+    let account: Account = store
+        .get_one(AccountDescriptor, Filter::new().eq("id", &1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.credits, Defaulted::new(100));
+    // `None` is an explicit `NULL`.
+    assert_eq!(account.note, None);
+
+    let account: Account = store
+        .get_one(AccountDescriptor, Filter::new().eq("id", &2))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.credits, Defaulted::new(5));
+    assert_eq!(account.note, Some(Defaulted::new(String::from("none"))));
+
+    store
+        .drop_table::<Account, _>(AccountDescriptor)
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_run_migration() {
+    let store = connect().await;
+
+    store
+        .run_migration(
+            "DROP TABLE IF EXISTS test_run_migration;\n\
+             CREATE TABLE test_run_migration (id INT NOT NULL, name TEXT NOT NULL);\n\
+             INSERT INTO test_run_migration VALUES (1, 'a;b');",
+        )
+        .await
+        .unwrap();
+
+    // The failed statement rolls back the insert before it.
+    let res = store
+        .run_migration(
+            "INSERT INTO test_run_migration VALUES (2, 'c');\n\
+             INSERT INTO test_run_migration_missing VALUES (3);",
+        )
+        .await;
+    assert!(matches!(res, Err(Error::Query { .. })));
+
+    let names: Vec<(String,)> = store
+        .query_raw_as("SELECT name FROM test_run_migration", &[])
+        .await
+        .unwrap();
+    assert_eq!(names, [(String::from("a;b"),)]);
+
+    store
+        .execute_raw("DROP TABLE test_run_migration", &[])
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_rows_affected() {
+    #[derive(Clone, Debug, StoreData)]
+    #[datastore(name = "test_rows_affected")]
+    struct Item {
+        id: PrimaryKey<i32>,
+        version: i32,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let items = (1..=3).map(|id| Item {
+        id: PrimaryKey(id),
+        version: 0,
+    });
+    store
+        .insert_many(ItemDescriptor, items.collect())
+        .await
+        .unwrap();
+
+    let item = Item {
+        id: PrimaryKey(1),
+        version: 1,
+    };
+    let query = Filter::new().eq("id", &1).eq("version", &0);
+    assert_eq!(
+        store
+            .update(ItemDescriptor, item.clone(), query.clone())
+            .await
+            .unwrap(),
+        1
+    );
+    // The version no longer matches.
+    assert_eq!(store.update(ItemDescriptor, item, query).await.unwrap(), 0);
+
+    assert_eq!(
+        store
+            .update_fields(
+                ItemDescriptor,
+                &[("version", Value::from(0))],
+                Filter::new().eq("version", &0)
+            )
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        store
+            .delete(ItemDescriptor, Filter::new().eq("version", &0))
+            .await
+            .unwrap(),
+        2
+    );
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_describe_table() {
+    #[derive(Clone, Debug, StoreData)]
+    #[datastore(name = "test_describe_table")]
+    struct Item {
+        id: PrimaryKey<u32>,
+        name: Option<String>,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let columns = store.describe_table("test_describe_table").await.unwrap();
+    assert_eq!(
+        columns,
+        [
+            ColumnInfo {
+                name: String::from("id"),
+                column_type: String::from("int unsigned"),
+                nullable: false,
+                key: Some(String::from("PRI")),
+                default: None,
+            },
+            ColumnInfo {
+                name: String::from("name"),
+                column_type: String::from("text"),
+                nullable: true,
+                key: None,
+                default: None,
+            },
+        ]
+    );
+
+    assert!(store
+        .describe_table("test_missing")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_save() {
+    #[derive(Clone, Debug, StoreData)]
+    #[datastore(name = "test_save")]
+    struct Item {
+        id: PrimaryKey<AutoIncrement<u64>>,
+        name: String,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let mut item = Item {
+        id: PrimaryKey(AutoIncrement(0)),
+        name: String::from("first"),
+    };
+    let id = store.save(ItemDescriptor, item.clone()).await.unwrap();
+    assert_ne!(id, 0);
+
+    item.id = PrimaryKey(AutoIncrement(id));
+    item.name = String::from("second");
+    assert_eq!(store.save(ItemDescriptor, item).await.unwrap(), id);
+
+    let items = store.get_all(ItemDescriptor).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "second");
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_insert_ignore() {
+    #[derive(Clone, Debug, StoreData)]
+    #[datastore(name = "test_insert_ignore")]
+    struct Item {
+        id: PrimaryKey<i32>,
+        name: String,
+    }
+
+    let store = connect().await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let item = Item {
+        id: PrimaryKey(1),
+        name: String::from("first"),
+    };
+    assert_eq!(store.insert_ignore(ItemDescriptor, item).await.unwrap(), 1);
+
+    let item = Item {
+        id: PrimaryKey(1),
+        name: String::from("second"),
+    };
+    assert_eq!(store.insert_ignore(ItemDescriptor, item).await.unwrap(), 0);
+
+    let item = store
+        .get_one(ItemDescriptor, Filter::new().eq("id", &1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.name, "first");
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_point() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_point")]
+    struct Location {
+        id: PrimaryKey<i32>,
+        point: Point,
+    }
+
+    let store = connect().await;
+
+    store
+        .drop_table::<Location, _>(LocationDescriptor)
+        .await
+        .unwrap();
+    store
+        .create::<Location, _>(LocationDescriptor)
+        .await
+        .unwrap();
+
+    let location = Location {
+        id: PrimaryKey(1),
+        point: Point::new(13.405, 52.52),
+    };
+    store
+        .insert(LocationDescriptor, location.clone())
+        .await
+        .unwrap();
+
+    let read: Vec<Location> = store.get_all(LocationDescriptor).await.unwrap();
+    assert_eq!(read, [location]);
+
+    store
+        .drop_table::<Location, _>(LocationDescriptor)
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_bounded_round_trip() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_bounded")]
+    struct Rating {
+        id: PrimaryKey<u64>,
+        stars: Bounded<u8, 1, 5>,
+    }
+
+    let store = connect().await;
+
+    store
+        .drop_table::<Rating, _>(RatingDescriptor)
+        .await
+        .unwrap();
+    store.create::<Rating, _>(RatingDescriptor).await.unwrap();
+
+    let rating = Rating {
+        id: PrimaryKey(1),
+        stars: Bounded(5),
+    };
+    store
+        .insert(RatingDescriptor, rating.clone())
+        .await
+        .unwrap();
+
+    let err = store
+        .insert(
+            RatingDescriptor,
+            Rating {
+                id: PrimaryKey(2),
+                stars: Bounded(6),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::OutOfRange { min: 1, max: 5, .. }));
+
+    let read = store
+        .get(RatingDescriptor, RatingQuery::default().id(PrimaryKey(1)))
+        .await
+        .unwrap();
+    assert_eq!(read, vec![rating.clone()]);
+
+    let read = store.get_all::<Rating, _>(RatingDescriptor).await.unwrap();
+    assert_eq!(read, vec![rating]);
+
+    store
+        .drop_table::<Rating, _>(RatingDescriptor)
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_get_stream_fetch_size() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_get_stream_fetch_size")]
+    struct Item {
+        id: PrimaryKey<i32>,
+    }
+
+    let config = MySqlStoreConfig {
+        fetch_size: 2,
+        order_by_primary_key: true,
+        ..Default::default()
+    };
+    let store = connect_with(config).await;
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+    store.create::<Item, _>(ItemDescriptor).await.unwrap();
+
+    let items: Vec<Item> = (1..=5).map(|id| Item { id: PrimaryKey(id) }).collect();
+    store
+        .insert_batched(ItemDescriptor, items.clone())
+        .await
+        .unwrap();
+
+    // The last batch only contains a single row.
+    let read: Vec<Item> =
+        futures::TryStreamExt::try_collect(store.get_stream(ItemDescriptor, Filter::new()))
+            .await
+            .unwrap();
+    assert_eq!(read, items);
+
+    store.drop_table::<Item, _>(ItemDescriptor).await.unwrap();
+}
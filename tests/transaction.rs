@@ -0,0 +1,120 @@
+//! Transactions against a real MySQL server.
+//!
+//! All tests are ignored by default. They require the MySQL server at `DATABASE_URL` or a
+//! docker daemon to start a container, see the [`common`] module.
+
+mod common;
+
+use datastore::{Store, StoreData};
+use datastore_mysql::{Filter, PrimaryKey};
+
+use common::connect;
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_get_for_update() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_get_for_update")]
+    struct Account {
+        id: PrimaryKey<i32>,
+        balance: i64,
+    }
+
+    let store = connect().await;
+
+    store
+        .drop_table::<Account, _>(AccountDescriptor)
+        .await
+        .unwrap();
+    store.create::<Account, _>(AccountDescriptor).await.unwrap();
+
+    let account = Account {
+        id: PrimaryKey(1),
+        balance: 100,
+    };
+    store
+        .insert(AccountDescriptor, account.clone())
+        .await
+        .unwrap();
+
+    let mut tx = store.begin().await.unwrap();
+    let read: Option<Account> = tx
+        .get_one_for_update(AccountDescriptor, Filter::new().eq("id", &1))
+        .await
+        .unwrap();
+    assert_eq!(read, Some(account.clone()));
+
+    // The row stays locked until the first transaction ends.
+    let mut other = store.begin().await.unwrap();
+    other
+        .execute_raw("SET SESSION innodb_lock_wait_timeout = 1", &[])
+        .await
+        .unwrap();
+    assert!(other
+        .get_for_update::<Account, _, _>(AccountDescriptor, Filter::new().eq("id", &1))
+        .await
+        .is_err());
+    other.rollback().await.unwrap();
+
+    tx.commit().await.unwrap();
+
+    let mut other = store.begin().await.unwrap();
+    let read: Vec<Account> = other
+        .get_for_update(AccountDescriptor, Filter::new().eq("id", &1))
+        .await
+        .unwrap();
+    assert_eq!(read, [account]);
+    other.commit().await.unwrap();
+
+    store
+        .drop_table::<Account, _>(AccountDescriptor)
+        .await
+        .unwrap();
+}
+
+/// Requires a MySQL server, see [`common`].
+#[tokio::test]
+#[ignore]
+async fn test_savepoint() {
+    #[derive(Clone, Debug, PartialEq, StoreData)]
+    #[datastore(name = "test_savepoint")]
+    struct Record {
+        id: PrimaryKey<i32>,
+    }
+
+    let store = connect().await;
+
+    store
+        .drop_table::<Record, _>(RecordDescriptor)
+        .await
+        .unwrap();
+    store.create::<Record, _>(RecordDescriptor).await.unwrap();
+
+    let mut tx = store.begin().await.unwrap();
+    for id in [1, 2, 1, 3] {
+        let record = Record { id: PrimaryKey(id) };
+
+        tx.savepoint("record").await.unwrap();
+        if tx.insert(RecordDescriptor, record).await.is_err() {
+            tx.rollback_to("record").await.unwrap();
+        }
+    }
+    tx.commit().await.unwrap();
+
+    let mut read = store.get_all::<Record, _>(RecordDescriptor).await.unwrap();
+    read.sort_by_key(|record| record.id.0);
+    assert_eq!(
+        read,
+        vec![
+            Record { id: PrimaryKey(1) },
+            Record { id: PrimaryKey(2) },
+            Record { id: PrimaryKey(3) },
+        ]
+    );
+
+    store
+        .drop_table::<Record, _>(RecordDescriptor)
+        .await
+        .unwrap();
+}